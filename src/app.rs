@@ -3,16 +3,17 @@ use self::{
     rendering::calculate_mvp, window::*,
 };
 use crate::{
-    AnimationIndex, AnimationSlot, AnimationState, CameraState, EditorResponse, FileResult,
-    RenderState, SwingState, TEXT_COLOR_DARK, TEXT_COLOR_LIGHT, Thumbnail,
+    AnimationIndex, AnimationSlot, AnimationState, CameraState, EditorMessage, EditorResponse,
+    FileResult, RenderState, SwingState, TEXT_COLOR_DARK, TEXT_COLOR_LIGHT, Thumbnail,
     app::{anim_list::anim_list, shortcut::format_shortcut, swing_list::swing_list},
     capture::{render_animation_to_gif, render_animation_to_image_sequence, render_screenshot},
+    compare::FolderComparisonState,
     editors::{
         adj::{add_missing_adj_entries, adj_editor},
         anim::anim_editor,
         hlpb::hlpb_editor,
         matl::{matl_editor, preset_editor},
-        mesh::mesh_editor,
+        mesh::{mesh_editor, reassign_bone_influences},
         meshex::meshex_editor,
         modl::modl_editor,
         nutexb::nutexb_viewer,
@@ -22,7 +23,9 @@ use crate::{
     log::AppLogger,
     model_folder::{FileChanged, ModelFolderState},
     path::{folder_display_name, folder_editor_title, last_update_check_file},
-    preferences::AppPreferences,
+    preferences::{AnimationSlotAssignment, AppPreferences, FolderAssignments},
+    rename::{RenameState, RenameTarget},
+    search::{SearchItem, SearchResult, SearchState},
     thumbnail::generate_model_thumbnails,
     update::LatestReleaseInfo,
     update_color_theme,
@@ -37,11 +40,11 @@ use egui_wgpu::{CallbackResources, CallbackTrait, ScreenDescriptor};
 use log::error;
 use once_cell::sync::Lazy;
 use rfd::FileDialog;
-use ssbh_data::matl_data::MatlEntryData;
 use ssbh_data::prelude::*;
-use ssbh_wgpu::{ModelFiles, RenderModel, next_frame};
+use ssbh_data::{anim_data::GroupType, matl_data::MatlEntryData};
+use ssbh_wgpu::{ModelFiles, ModelFolder, RenderModel, next_frame};
 use std::{
-    collections::{HashSet, VecDeque},
+    collections::{BTreeMap, BTreeSet, HashSet, VecDeque},
     path::{Path, PathBuf},
     sync::Mutex,
 };
@@ -140,6 +143,7 @@ impl Editor for SkelData {
             &model.folder_path,
             name,
             skel,
+            find_file(&model.model.meshes, "model.numshb"),
             state,
             dark_mode,
         ))
@@ -161,7 +165,17 @@ impl Editor for AnimData {
         _: bool,
     ) -> Option<EditorResponse> {
         let (name, anim) = get_file_to_edit(&mut model.model.anims, *open_file_index)?;
-        Some(anim_editor(ctx, &model.folder_path, name, anim, state))
+        Some(anim_editor(
+            ctx,
+            &model.folder_path,
+            name,
+            anim,
+            find_file(&model.model.skels, "model.nusktb"),
+            find_file(&model.model.meshes, "model.numshb"),
+            find_file(&model.model.matls, "model.numatb"),
+            find_file(&model.model.hlpbs, "model.nuhlpb"),
+            state,
+        ))
     }
 
     fn set_changed(response: &EditorResponse, changed: &mut FileChanged, index: usize) {
@@ -205,16 +219,32 @@ impl Editor for MeshData {
         dark_mode: bool,
     ) -> Option<EditorResponse> {
         let (name, mesh) = get_file_to_edit(&mut model.model.meshes, *open_file_index)?;
-        Some(mesh_editor(
+        let mut modl_changed = false;
+        let response = mesh_editor(
             ctx,
             &model.folder_path,
             name,
             mesh,
             find_file(&model.model.skels, "model.nusktb"),
+            find_file_mut(&mut model.model.modls, "model.numdlb"),
+            &mut modl_changed,
             &model.validation.mesh_errors,
             dark_mode,
             state,
-        ))
+        );
+
+        // Adding or removing mesh objects also updates the model.numdlb entries.
+        if modl_changed
+            && let Some(index) = model
+                .model
+                .modls
+                .iter()
+                .position(|(name, _)| name == "model.numdlb")
+        {
+            model.changed.modls[index] = true;
+        }
+
+        Some(response)
     }
 
     fn set_changed(response: &EditorResponse, changed: &mut FileChanged, index: usize) {
@@ -265,6 +295,7 @@ fn open_editor<T: Editor>(
     open_file_index: &mut Option<usize>,
     state: &mut T::EditorState,
     model_actions: &mut VecDeque<RenderAction>,
+    editor_messages: &mut Vec<EditorMessage>,
     selected_folder_index: usize,
     dark_mode: bool,
 ) -> bool {
@@ -274,7 +305,7 @@ fn open_editor<T: Editor>(
 
             if let Some(message) = response.message {
                 match message {
-                    crate::EditorMessage::SelectMesh {
+                    EditorMessage::SelectMesh {
                         mesh_object_name,
                         mesh_object_subindex,
                     } => {
@@ -286,6 +317,8 @@ fn open_editor<T: Editor>(
                             },
                         ));
                     }
+                    // Other messages need access to the entire workspace.
+                    message => editor_messages.push(message),
                 }
             }
         }
@@ -452,6 +485,7 @@ pub struct UiState {
     pub log_window_open: bool,
     pub preferences_window_open: bool,
     pub device_info_window_open: bool,
+    pub search_open: bool,
 
     // TODO: Is there a better way to track this?
     // Clicking an item in the file list sets the selected index.
@@ -477,6 +511,13 @@ pub struct UiState {
     pub nutexb: NutexbViewerState,
     pub hlpb_editor: HlpbEditorState,
     pub mesh_editor: MeshEditorState,
+    pub rename: Option<RenameState>,
+    pub search: SearchState,
+    pub folder_comparison: Option<FolderComparisonState>,
+}
+
+fn file_position<T>(files: &[(String, T)], file_name: &str) -> Option<usize> {
+    files.iter().position(|(name, _)| name == file_name)
 }
 
 pub struct NutexbViewerState {
@@ -497,6 +538,34 @@ impl Default for NutexbViewerState {
 #[derive(Default)]
 pub struct SkelEditorState {
     pub mode: SkelMode,
+    pub selected_bone_index: Option<usize>,
+    pub transform_space: BoneTransformSpace,
+    pub merge_bones: MergeBonesState,
+    pub diff: SkelDiffState,
+}
+
+pub struct SkelDiffState {
+    pub is_open: bool,
+    pub reference: Option<(PathBuf, SkelData)>,
+    pub tolerance: f32,
+}
+
+impl Default for SkelDiffState {
+    fn default() -> Self {
+        Self {
+            is_open: false,
+            reference: None,
+            tolerance: 0.001,
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct MergeBonesState {
+    pub is_open: bool,
+    pub reference: Option<(PathBuf, SkelData)>,
+    /// Indices of the reference bones to add.
+    pub selected_bones: BTreeSet<usize>,
 }
 
 #[derive(PartialEq, Eq)]
@@ -511,6 +580,18 @@ impl Default for SkelMode {
     }
 }
 
+#[derive(PartialEq, Eq, Clone, Copy)]
+pub enum BoneTransformSpace {
+    Local,
+    World,
+}
+
+impl Default for BoneTransformSpace {
+    fn default() -> Self {
+        Self::Local
+    }
+}
+
 #[derive(PartialEq, Eq)]
 pub enum PresetMode {
     User,
@@ -531,11 +612,36 @@ pub struct MatlEditorState {
     pub selected_preset_index: usize,
     pub preset_mode: PresetMode,
     pub texture_to_edit_index: Option<usize>,
+    pub compare: MatlCompareState,
+}
+
+#[derive(Default)]
+pub struct MatlCompareState {
+    pub is_open: bool,
+    pub source: MatlCompareSource,
+    /// Materials from another numatb when using [MatlCompareSource::File].
+    pub reference_file: Option<(PathBuf, MatlData)>,
+    pub reference_index: usize,
+}
+
+#[derive(PartialEq, Eq, Clone, Copy)]
+pub enum MatlCompareSource {
+    Matl,
+    File,
+    UserPresets,
+    DefaultPresets,
+}
+
+impl Default for MatlCompareSource {
+    fn default() -> Self {
+        Self::Matl
+    }
 }
 
 #[derive(Default)]
 pub struct ModlEditorState {
     pub editor_tab: ModlEditorTab,
+    pub selected_entry_index: Option<usize>,
 }
 
 #[derive(PartialEq, Eq)]
@@ -572,6 +678,96 @@ impl Default for HlpbEditorTab {
 #[derive(Default)]
 pub struct MeshEditorState {
     pub selected_index: usize,
+    /// Additional mesh objects selected with Ctrl+click for merging.
+    pub merge_selection: BTreeSet<usize>,
+    pub weight_transfer: WeightTransferState,
+    pub transform: MeshTransformState,
+    pub statistics: MeshStatisticsState,
+    pub uv_tools: UvToolsState,
+}
+
+pub struct UvToolsState {
+    pub scale: [f32; 2],
+    pub offset: [f32; 2],
+    pub layout_open: bool,
+    pub layout_attribute_index: usize,
+}
+
+impl Default for UvToolsState {
+    fn default() -> Self {
+        Self {
+            scale: [1.0; 2],
+            offset: [0.0; 2],
+            layout_open: false,
+            layout_attribute_index: 0,
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct MeshStatisticsState {
+    pub is_open: bool,
+    pub sort_column: MeshStatisticsColumn,
+    pub sort_descending: bool,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum MeshStatisticsColumn {
+    Name,
+    Vertices,
+    Triangles,
+    Influences,
+    MaxInfluences,
+    BufferSize,
+}
+
+impl Default for MeshStatisticsColumn {
+    fn default() -> Self {
+        Self::Name
+    }
+}
+
+pub struct MeshTransformState {
+    pub is_open: bool,
+    pub translation: [f32; 3],
+    pub rotation_degrees: [f32; 3],
+    pub scale: [f32; 3],
+    pub mirror: [bool; 3],
+    pub apply_to_all: bool,
+}
+
+impl Default for MeshTransformState {
+    fn default() -> Self {
+        Self {
+            is_open: false,
+            translation: [0.0; 3],
+            rotation_degrees: [0.0; 3],
+            scale: [1.0; 3],
+            mirror: [false; 3],
+            apply_to_all: false,
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct WeightTransferState {
+    pub is_open: bool,
+    pub reference: Option<(PathBuf, MeshData)>,
+    pub source_index: Option<usize>,
+    pub method: WeightTransferMethod,
+    pub match_names: bool,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum WeightTransferMethod {
+    NearestVertex,
+    NearestSurface,
+}
+
+impl Default for WeightTransferMethod {
+    fn default() -> Self {
+        Self::NearestSurface
+    }
 }
 
 #[derive(Default)]
@@ -588,6 +784,7 @@ pub enum AnimEditorTab {
     Hierarchy,
     Graph,
     List,
+    Visibility,
 }
 
 impl Default for AnimEditorTab {
@@ -602,6 +799,128 @@ pub struct AnimEditorState {
     pub selected_group_index: Option<usize>,
     pub selected_node_index: Option<usize>,
     pub selected_track_index: Option<usize>,
+    pub graph: AnimGraphState,
+    pub add_node: AddNodeState,
+    pub frame_tools: FrameToolsState,
+    pub retarget: RetargetState,
+    pub filters: CurveFiltersState,
+    pub timeline: VisibilityTimelineState,
+    /// The group, node, and optional track index of the name being edited.
+    pub renaming: Option<(usize, usize, Option<usize>)>,
+}
+
+#[derive(Default)]
+pub struct FrameToolsState {
+    pub is_open: bool,
+    pub start: usize,
+    pub end: usize,
+    pub frame_count: usize,
+    pub offset: i64,
+}
+
+#[derive(Default)]
+pub struct VisibilityTimelineState {
+    /// The value to paint and the last painted frame while dragging.
+    pub paint: Option<(bool, usize)>,
+    /// The group index, node index, and start and end frame selected for copying.
+    pub selection: Option<(usize, usize, usize, usize)>,
+    pub copied: Vec<bool>,
+}
+
+pub struct CurveFiltersState {
+    pub is_open: bool,
+    pub filter: CurveFilter,
+    /// Apply the filter to all tracks instead of only the selected track.
+    pub all_tracks: bool,
+    /// Show the filtered values for the selected track in the graph.
+    pub preview: bool,
+    /// The standard deviation in frames for Gaussian smoothing.
+    pub sigma: f32,
+    /// The Butterworth cutoff frequency in cycles per frame.
+    pub cutoff: f32,
+    /// The maximum difference from the original values for keyframe reduction.
+    pub tolerance: f32,
+}
+
+impl Default for CurveFiltersState {
+    fn default() -> Self {
+        Self {
+            is_open: false,
+            filter: CurveFilter::default(),
+            all_tracks: false,
+            preview: true,
+            sigma: 1.0,
+            cutoff: 0.1,
+            tolerance: 0.01,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum CurveFilter {
+    QuaternionContinuity,
+    EulerFilter,
+    Gaussian,
+    Butterworth,
+    KeyframeReduction,
+}
+
+impl Default for CurveFilter {
+    fn default() -> Self {
+        Self::QuaternionContinuity
+    }
+}
+
+#[derive(Default)]
+pub struct RetargetState {
+    pub is_open: bool,
+    pub source_anim: Option<(PathBuf, AnimData)>,
+    pub source_skel: Option<(PathBuf, SkelData)>,
+    /// The source bone name for each target bone name.
+    pub bone_map: BTreeMap<String, String>,
+}
+
+pub struct AddNodeState {
+    pub is_open: bool,
+    pub group_type: GroupType,
+    pub node_name: String,
+    pub track_name: String,
+}
+
+impl Default for AddNodeState {
+    fn default() -> Self {
+        Self {
+            is_open: false,
+            group_type: GroupType::Transform,
+            node_name: String::new(),
+            track_name: String::new(),
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct AnimGraphState {
+    /// The group, node, and track indices for the selected keys.
+    pub track: (Option<usize>, Option<usize>, Option<usize>),
+    /// Selected (channel, frame) values in the graph.
+    pub selected_keys: BTreeSet<(usize, usize)>,
+    pub drag: Option<GraphDrag>,
+}
+
+pub enum GraphDrag {
+    /// Move the selected values by the vertical drag distance.
+    Move {
+        start_values: Vec<((usize, usize), f64)>,
+        offset: f64,
+    },
+    /// Scale the selected values relative to their average.
+    Scale {
+        start_values: Vec<((usize, usize), f64)>,
+        pivot: f64,
+        factor: f64,
+    },
+    /// Select the values in the rectangle from the start position to the pointer.
+    BoxSelect { start: [f64; 2] },
 }
 
 const ICON_SIZE: f32 = 18.0;
@@ -634,6 +953,9 @@ impl SsbhApp {
         // Don't clear existing files if the user cancels the dialog.
         if clear_workspace {
             self.clear_workspace();
+        } else {
+            // Keep any changes to existing folders before restoring assignments.
+            self.store_folder_assignments();
         }
 
         // Load recursively for nested folders like stages.
@@ -652,11 +974,11 @@ impl SsbhApp {
                 {
                     // The model.nuanmb always plays, so assign it automatically.
                     vec![AnimationSlot {
-                        is_enabled: true,
                         animation: Some(AnimationIndex {
                             folder_index: self.models.len() + i,
                             anim_index,
                         }),
+                        ..AnimationSlot::new()
                     }]
                 } else {
                     // Add a dummy animation to prompt the user to select one.
@@ -674,6 +996,10 @@ impl SsbhApp {
             .hidden_collisions
             .extend(std::iter::repeat_n(HashSet::new(), new_models.len()));
 
+        let new_folders: Vec<_> = new_models
+            .iter()
+            .map(|(p, _)| p.to_string_lossy().to_string())
+            .collect();
         for (path, model) in new_models {
             let model_state = load_model(path, model);
             self.models.push(model_state);
@@ -681,6 +1007,9 @@ impl SsbhApp {
 
         self.sort_files();
 
+        // Anim indices are only valid after sorting files.
+        self.restore_folder_assignments(&new_folders);
+
         // TODO: Only validate the models that were added?
         self.should_validate_models = true;
         self.should_update_thumbnails = true;
@@ -710,6 +1039,158 @@ impl SsbhApp {
         self.preferences.recent_folders.truncate(10);
     }
 
+    /// Remember the animation slots and swing.prc for each model folder for future sessions.
+    pub fn store_folder_assignments(&mut self) {
+        let is_loaded = |models: &[ModelFolderState], path: &str| {
+            models
+                .iter()
+                .any(|m| m.folder_path.to_string_lossy() == path)
+        };
+
+        for ((model, slots), swing_index) in self
+            .models
+            .iter()
+            .zip(self.animation_state.animations.iter())
+            .zip(self.swing_state.selected_swing_folders.iter())
+            .filter(|((model, _), _)| model.is_model_folder())
+        {
+            let mut animation_slots: Vec<_> = slots
+                .iter()
+                .map(|slot| {
+                    let anim = slot.animation.and_then(|anim_index| {
+                        let folder = self.models.get(anim_index.folder_index)?;
+                        let (name, _) = folder.model.anims.get(anim_index.anim_index)?;
+                        Some((
+                            folder.folder_path.to_string_lossy().to_string(),
+                            name.clone(),
+                        ))
+                    });
+                    let (folder_path, file_name) = anim.unzip();
+                    AnimationSlotAssignment {
+                        is_enabled: slot.is_enabled,
+                        folder_path,
+                        file_name,
+                        weight: slot.weight,
+                        blend_mode: slot.blend_mode,
+                        bone_mask: slot.bone_mask.clone(),
+                        frame_offset: slot.frame_offset,
+                        speed: slot.speed,
+                    }
+                })
+                .collect();
+
+            let mut swing_folder = swing_index
+                .and_then(|i| self.models.get(i))
+                .map(|m| m.folder_path.to_string_lossy().to_string());
+
+            // Don't lose assignments to folders that haven't been loaded yet.
+            // These can only be resolved after loading the folder, so empty slots keep them.
+            let path = model.folder_path.to_string_lossy().to_string();
+            if let Some(stored) = self.preferences.folder_assignments.remove(&path) {
+                for (i, stored_slot) in stored.animation_slots.into_iter().enumerate() {
+                    if stored_slot
+                        .folder_path
+                        .as_deref()
+                        .is_some_and(|f| !is_loaded(&self.models, f))
+                    {
+                        match animation_slots.get_mut(i) {
+                            Some(slot) if slot.folder_path.is_none() => *slot = stored_slot,
+                            Some(_) => (),
+                            None => animation_slots.push(stored_slot),
+                        }
+                    }
+                }
+
+                if swing_folder.is_none() {
+                    swing_folder = stored.swing_folder.filter(|f| !is_loaded(&self.models, f));
+                }
+            }
+
+            // Avoid growing the preferences for every folder ever loaded.
+            let assignments = FolderAssignments {
+                animation_slots,
+                swing_folder,
+            };
+            if !assignments.is_default() {
+                self.preferences
+                    .folder_assignments
+                    .insert(path, assignments);
+            }
+        }
+    }
+
+    /// Restore assignments for `new_folders` and any loaded folders that reference them.
+    fn restore_folder_assignments(&mut self, new_folders: &[String]) {
+        let folder_index = |models: &[ModelFolderState], path: &str| {
+            models
+                .iter()
+                .position(|m| m.folder_path.to_string_lossy() == path)
+        };
+
+        for i in 0..self.models.len() {
+            let path = self.models[i].folder_path.to_string_lossy().to_string();
+            let Some(assignments) = self.preferences.folder_assignments.get(&path) else {
+                continue;
+            };
+            // Preserve any changes to the assignments for existing folders.
+            if !new_folders.contains(&path)
+                && !assignments
+                    .referenced_folders()
+                    .any(|f| new_folders.iter().any(|n| n == f))
+            {
+                continue;
+            }
+
+            // Files that no longer exist or folders that aren't loaded leave the slot empty.
+            let slots: Vec<_> = assignments
+                .animation_slots
+                .iter()
+                .map(|slot| {
+                    let animation = slot
+                        .folder_path
+                        .as_ref()
+                        .zip(slot.file_name.as_ref())
+                        .and_then(|(folder_path, file_name)| {
+                            let folder_index = folder_index(&self.models, folder_path)?;
+                            let anim_index = self.models[folder_index]
+                                .model
+                                .anims
+                                .iter()
+                                .position(|(f, _)| f == file_name)?;
+                            Some(AnimationIndex {
+                                folder_index,
+                                anim_index,
+                            })
+                        });
+                    AnimationSlot {
+                        is_enabled: slot.is_enabled,
+                        animation,
+                        weight: slot.weight,
+                        blend_mode: slot.blend_mode,
+                        bone_mask: slot.bone_mask.clone(),
+                        frame_offset: slot.frame_offset,
+                        speed: slot.speed,
+                    }
+                })
+                .collect();
+            if !slots.is_empty()
+                && let Some(animations) = self.animation_state.animations.get_mut(i)
+            {
+                *animations = slots;
+            }
+
+            if let Some(swing_folder) = &assignments.swing_folder
+                && let Some(swing_index) = folder_index(&self.models, swing_folder)
+                && let Some(selected) = self.swing_state.selected_swing_folders.get_mut(i)
+            {
+                *selected = Some(swing_index);
+            }
+        }
+
+        self.animation_state.should_update_animations = true;
+        self.swing_state.should_update_swing = true;
+    }
+
     pub fn reload_workspace(&mut self) {
         // This also reloads animations since animations are stored as indices.
         for model in &mut self.models {
@@ -727,6 +1208,8 @@ impl SsbhApp {
     }
 
     pub fn clear_workspace(&mut self) {
+        self.store_folder_assignments();
+
         // TODO: Is it easier to have dedicated reset methods?
         self.models = Vec::new();
         self.render_actions
@@ -734,6 +1217,7 @@ impl SsbhApp {
         self.animation_state.animations = Vec::new();
         self.swing_state.selected_swing_folders = Vec::new();
         self.swing_state.hidden_collisions = Vec::new();
+        self.swing_state.selected_swing_bone = None;
         self.camera_state.anim_path = None;
         self.render_actions.push_back(RenderAction::UpdateCamera);
         // TODO: Reset selected indices?
@@ -944,6 +1428,43 @@ impl SsbhApp {
 
         log_window(ctx, &mut self.ui_state.log_window_open);
 
+        let renamed_files = rename_window(
+            ctx,
+            &mut self.ui_state.rename,
+            &mut self.models,
+            &mut self.material_presets,
+        );
+        if !renamed_files.is_empty() {
+            let mut folder_indices: Vec<_> = renamed_files
+                .iter()
+                .filter_map(|c| c.folder_index)
+                .collect();
+            folder_indices.sort_unstable();
+            folder_indices.dedup();
+            for folder_index in folder_indices {
+                self.render_actions
+                    .push_back(RenderAction::Model(RenderModelAction::Update(folder_index)));
+            }
+            self.animation_state.should_update_animations = true;
+            self.should_validate_models = true;
+        }
+
+        if let Some(result) = search_window(
+            ctx,
+            &mut self.ui_state.search_open,
+            &mut self.ui_state.search,
+            &self.models,
+        ) {
+            self.open_search_result(result);
+        }
+
+        if let Some(file_name) =
+            folder_comparison_window(ctx, &mut self.ui_state.folder_comparison, &self.models)
+            && let Some(comparison) = &self.ui_state.folder_comparison
+        {
+            self.open_file(comparison.folder_index, &file_name);
+        }
+
         if preferences_window(
             ctx,
             &mut self.preferences,
@@ -1112,6 +1633,7 @@ impl eframe::App for SsbhApp {
             error!("Failed to write update check time to {path:?}: {e}");
         }
 
+        self.store_folder_assignments();
         self.preferences.write_to_file();
     }
 }
@@ -1229,6 +1751,7 @@ impl CallbackTrait for ViewportCallback {
 impl SsbhApp {
     fn file_editors(&mut self, ctx: &Context, render_state: &mut RenderState) -> bool {
         let mut file_changed = false;
+        let mut editor_messages = Vec::new();
 
         // TODO: Use some sort of trait to clean up repetitive code?
         // The functions would take an additional ui parameter.
@@ -1245,7 +1768,7 @@ impl SsbhApp {
                     name,
                     &mut self.ui_state.matl_editor,
                     matl,
-                    find_file_mut(&mut model.model.modls, "model.numdlb"),
+                    find_file(&model.model.modls, "model.numdlb"),
                     &model.validation.matl_errors,
                     &model.thumbnails,
                     &self.default_thumbnails,
@@ -1256,10 +1779,13 @@ impl SsbhApp {
                     self.yellow_checkerboard,
                     self.preferences.dark_mode,
                 );
-                // TODO: This modifies the model.numdlb when renaming materials.
                 response.set_changed(&mut model.changed.matls[matl_index]);
                 file_changed |= response.changed;
 
+                if let Some(message) = response.message {
+                    editor_messages.push(message);
+                }
+
                 if !response.open {
                     // Close the window.
                     self.ui_state.open_matl = None;
@@ -1289,6 +1815,7 @@ impl SsbhApp {
                 &mut self.ui_state.open_mesh,
                 &mut self.ui_state.mesh_editor,
                 &mut self.render_actions,
+                &mut editor_messages,
                 folder_index,
                 self.preferences.dark_mode,
             ) {
@@ -1298,15 +1825,22 @@ impl SsbhApp {
                 file_changed = true;
             }
 
-            file_changed |= open_editor::<SkelData>(
+            if open_editor::<SkelData>(
                 ctx,
                 model,
                 &mut self.ui_state.open_skel,
                 &mut self.ui_state.skel_editor,
                 &mut self.render_actions,
+                &mut editor_messages,
                 folder_index,
                 self.preferences.dark_mode,
-            );
+            ) {
+                // Reload the skeleton to preview bone transform edits in the viewport.
+                self.render_actions
+                    .push_back(RenderAction::Model(RenderModelAction::Update(folder_index)));
+                self.animation_state.should_update_animations = true;
+                file_changed = true;
+            }
 
             if open_editor::<ModlData>(
                 ctx,
@@ -1314,6 +1848,7 @@ impl SsbhApp {
                 &mut self.ui_state.open_modl,
                 &mut self.ui_state.modl_editor,
                 &mut self.render_actions,
+                &mut editor_messages,
                 folder_index,
                 self.preferences.dark_mode,
             ) {
@@ -1334,6 +1869,7 @@ impl SsbhApp {
                 &mut self.ui_state.open_hlpb,
                 &mut self.ui_state.hlpb_editor,
                 &mut self.render_actions,
+                &mut editor_messages,
                 folder_index,
                 self.preferences.dark_mode,
             ) {
@@ -1348,6 +1884,7 @@ impl SsbhApp {
                 &mut self.ui_state.open_adj,
                 &mut (),
                 &mut self.render_actions,
+                &mut editor_messages,
                 folder_index,
                 self.preferences.dark_mode,
             );
@@ -1358,6 +1895,7 @@ impl SsbhApp {
                 &mut self.ui_state.open_anim,
                 &mut self.ui_state.anim_editor,
                 &mut self.render_actions,
+                &mut editor_messages,
                 folder_index,
                 self.preferences.dark_mode,
             ) {
@@ -1372,6 +1910,7 @@ impl SsbhApp {
                 &mut self.ui_state.open_meshex,
                 &mut (),
                 &mut self.render_actions,
+                &mut editor_messages,
                 folder_index,
                 self.preferences.dark_mode,
             ) {
@@ -1396,9 +1935,168 @@ impl SsbhApp {
             }
         }
 
+        if let Some(folder_index) = self.ui_state.selected_folder_index {
+            for message in editor_messages {
+                self.handle_editor_message(folder_index, message);
+            }
+        }
+
         file_changed
     }
 
+    fn open_search_result(&mut self, result: SearchResult) {
+        // Editors are only shown for the selected folder.
+        self.ui_state.selected_folder_index = Some(result.folder_index);
+
+        match result.item {
+            SearchItem::Bone {
+                file_index,
+                bone_index,
+            } => {
+                self.ui_state.open_skel = Some(file_index);
+                self.ui_state.skel_editor.selected_bone_index = Some(bone_index);
+            }
+            SearchItem::MeshObject {
+                file_index,
+                object_index,
+            } => {
+                self.ui_state.open_mesh = Some(file_index);
+                self.ui_state.mesh_editor.selected_index = object_index;
+            }
+            SearchItem::ModlEntry {
+                file_index,
+                entry_index,
+            } => {
+                self.ui_state.open_modl = Some(file_index);
+                self.ui_state.modl_editor.editor_tab = ModlEditorTab::Assignments;
+                self.ui_state.modl_editor.selected_entry_index = Some(entry_index);
+            }
+            SearchItem::Material {
+                file_index,
+                entry_index,
+            } => {
+                self.ui_state.open_matl = Some(file_index);
+                self.ui_state.matl_editor.selected_material_index = entry_index;
+            }
+            SearchItem::AnimNode {
+                file_index,
+                group_index,
+                node_index,
+            } => {
+                self.ui_state.open_anim = Some(file_index);
+                self.ui_state.anim_editor.editor_tab = AnimEditorTab::List;
+                self.ui_state.anim_editor.selected_group_index = Some(group_index);
+                self.ui_state.anim_editor.selected_node_index = Some(node_index);
+                self.ui_state.anim_editor.selected_track_index = None;
+            }
+            SearchItem::OrientConstraint {
+                file_index,
+                constraint_index,
+            } => {
+                self.ui_state.open_hlpb = Some(file_index);
+                self.ui_state.hlpb_editor.editor_tab = HlpbEditorTab::Orient;
+                self.ui_state.hlpb_editor.orient_constraint_index = constraint_index;
+            }
+            SearchItem::AimConstraint {
+                file_index,
+                constraint_index,
+            } => {
+                self.ui_state.open_hlpb = Some(file_index);
+                self.ui_state.hlpb_editor.editor_tab = HlpbEditorTab::Aim;
+                self.ui_state.hlpb_editor.aim_constraint_index = constraint_index;
+            }
+            SearchItem::SwingBone { swing_bone_index } => {
+                // Swing bones are listed in the right panel instead of an editor.
+                self.show_right_panel = true;
+                self.ui_state.right_panel_tab = PanelTab::Swing;
+                self.swing_state.selected_swing_bone =
+                    Some((result.folder_index, swing_bone_index));
+            }
+        }
+    }
+
+    fn open_file(&mut self, folder_index: usize, file_name: &str) {
+        let Some(model) = self.models.get(folder_index).map(|m| &m.model) else {
+            return;
+        };
+
+        // Editors are only shown for the selected folder.
+        self.ui_state.selected_folder_index = Some(folder_index);
+
+        if let Some(i) = file_position(&model.meshes, file_name) {
+            self.ui_state.open_mesh = Some(i);
+        } else if let Some(i) = file_position(&model.skels, file_name) {
+            self.ui_state.open_skel = Some(i);
+        } else if let Some(i) = file_position(&model.matls, file_name) {
+            self.ui_state.open_matl = Some(i);
+        } else if let Some(i) = file_position(&model.modls, file_name) {
+            self.ui_state.open_modl = Some(i);
+        } else if let Some(i) = file_position(&model.hlpbs, file_name) {
+            self.ui_state.open_hlpb = Some(i);
+        } else if let Some(i) = file_position(&model.adjs, file_name) {
+            self.ui_state.open_adj = Some(i);
+        } else if let Some(i) = file_position(&model.anims, file_name) {
+            self.ui_state.open_anim = Some(i);
+        } else if let Some(i) = file_position(&model.meshexes, file_name) {
+            self.ui_state.open_meshex = Some(i);
+        } else if let Some(i) = file_position(&model.nutexbs, file_name) {
+            self.ui_state.open_nutexb = Some(i);
+        }
+    }
+
+    fn handle_editor_message(&mut self, folder_index: usize, message: EditorMessage) {
+        match message {
+            EditorMessage::SelectMesh { .. } => (),
+            EditorMessage::RenameMeshObject {
+                mesh_object_name,
+                mesh_object_subindex,
+            } => {
+                self.ui_state.rename = Some(RenameState::new(
+                    folder_index,
+                    RenameTarget::MeshObject {
+                        name: mesh_object_name,
+                        subindex: mesh_object_subindex,
+                    },
+                ));
+            }
+            EditorMessage::RenameMaterial { material_label } => {
+                self.ui_state.rename = Some(RenameState::new(
+                    folder_index,
+                    RenameTarget::MaterialLabel {
+                        label: material_label,
+                    },
+                ));
+            }
+            EditorMessage::RenameBone { bone_name } => {
+                self.ui_state.rename = Some(RenameState::new(
+                    folder_index,
+                    RenameTarget::Bone { name: bone_name },
+                ));
+            }
+            EditorMessage::ReassignBoneWeights {
+                bone_name,
+                parent_bone_name,
+            } => {
+                if let Some(model) = self.models.get_mut(folder_index) {
+                    for ((_, mesh), changed) in model
+                        .model
+                        .meshes
+                        .iter_mut()
+                        .zip(model.changed.meshes.iter_mut())
+                    {
+                        for o in mesh.iter_mut().flat_map(|m| &mut m.objects) {
+                            *changed |= reassign_bone_influences(
+                                o,
+                                &bone_name,
+                                parent_bone_name.as_deref(),
+                            );
+                        }
+                    }
+                }
+            }
+        }
+    }
+
     pub fn max_final_frame_index(&self, render_state: &RenderState) -> f32 {
         // Find the minimum number of frames to cover all animations.
         // This should include stage animations like lighting and cameras.
@@ -1436,6 +2134,7 @@ impl SsbhApp {
             .auto_shrink([false; 2])
             .show(ui, |ui| {
                 let mut folder_to_remove = None;
+                let mut folder_to_compare = None;
 
                 for (folder_index, model) in self
                     .models
@@ -1505,6 +2204,12 @@ impl SsbhApp {
                                 model.changed.meshexes.push(true);
                             }
 
+                            if ui.button("Compare With Folder...").clicked()
+                                && let Some(path) = FileDialog::new().pick_folder()
+                            {
+                                folder_to_compare = Some((folder_index, path));
+                            }
+
                             ui.separator();
 
                             // Use "Remove" since this doesn't delete the folder on disk.
@@ -1514,10 +2219,26 @@ impl SsbhApp {
                         });
                 }
 
+                if let Some((folder_index, path)) = folder_to_compare
+                    && let Some(model) = self.models.get(folder_index)
+                {
+                    let reference = ModelFolder::load_folder(&path);
+                    self.ui_state.folder_comparison = Some(FolderComparisonState::new(
+                        folder_index,
+                        &model.model,
+                        path,
+                        reference,
+                    ));
+                }
+
                 if let Some(folder_to_remove) = folder_to_remove {
+                    self.store_folder_assignments();
                     if self.models.get(folder_to_remove).is_some() {
                         self.models.remove(folder_to_remove);
                     }
+                    // Folder indices shift after removing a folder.
+                    self.ui_state.folder_comparison = None;
+                    self.swing_state.selected_swing_bone = None;
                     self.render_actions
                         .push_back(RenderAction::Model(RenderModelAction::Remove(
                             folder_to_remove,
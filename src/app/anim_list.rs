@@ -1,11 +1,11 @@
 use crate::{
-    AnimationIndex, AnimationSlot, ModelFolderState,
+    AnimationIndex, AnimationSlot, BlendMode, ModelFolderState,
     app::{SsbhApp, folder_display_name},
     model_folder::find_anim_folders,
     widgets::EyeCheckBox,
 };
 use egui::{
-    CollapsingHeader, Context, Label, RichText, TextWrapMode, Ui,
+    CollapsingHeader, ComboBox, Context, DragValue, Grid, Id, Label, RichText, TextWrapMode, Ui,
     collapsing_header::CollapsingState,
 };
 
@@ -136,6 +136,8 @@ fn show_anim_slot(
             });
         })
         .body(|ui| {
+            update_animations |= slot_settings(ui, anim_slot, &models[model_index], id);
+
             if let Some((_, Some(anim))) = anim_slot
                 .animation
                 .as_ref()
@@ -213,3 +215,87 @@ fn anim_combo_box(
 
     changed
 }
+
+fn slot_settings(
+    ui: &mut Ui,
+    anim_slot: &mut AnimationSlot,
+    model: &ModelFolderState,
+    id: Id,
+) -> bool {
+    let mut changed = false;
+
+    Grid::new(id.with("settings")).show(ui, |ui| {
+        ui.label("Weight");
+        changed |= ui
+            .add(
+                DragValue::new(&mut anim_slot.weight)
+                    .range(0.0..=1.0)
+                    .speed(0.01),
+            )
+            .changed();
+        ui.end_row();
+
+        ui.label("Mode");
+        ComboBox::from_id_salt(id.with("mode"))
+            .selected_text(match anim_slot.blend_mode {
+                BlendMode::Override => "Override",
+                BlendMode::Additive => "Additive",
+            })
+            .show_ui(ui, |ui| {
+                changed |= ui
+                    .selectable_value(&mut anim_slot.blend_mode, BlendMode::Override, "Override")
+                    .on_hover_text("Replace the transforms from previous slots.")
+                    .changed();
+                changed |= ui
+                    .selectable_value(&mut anim_slot.blend_mode, BlendMode::Additive, "Additive")
+                    .on_hover_text("Add the difference from the rest pose to previous slots.")
+                    .changed();
+            });
+        ui.end_row();
+
+        ui.label("Bone Mask");
+        let skel = model
+            .model
+            .skels
+            .iter()
+            .find(|(f, _)| f == "model.nusktb")
+            .and_then(|(_, m)| m.as_ref());
+        ComboBox::from_id_salt(id.with("mask"))
+            .selected_text(anim_slot.bone_mask.as_deref().unwrap_or("All Bones"))
+            .height(300.0)
+            .show_ui(ui, |ui| {
+                changed |= ui
+                    .selectable_value(&mut anim_slot.bone_mask, None, "All Bones")
+                    .changed();
+                for bone in skel.iter().flat_map(|s| &s.bones) {
+                    changed |= ui
+                        .selectable_value(
+                            &mut anim_slot.bone_mask,
+                            Some(bone.name.clone()),
+                            &bone.name,
+                        )
+                        .on_hover_text("Only animate this bone and its children.")
+                        .changed();
+                }
+            });
+        ui.end_row();
+
+        ui.label("Frame Offset");
+        changed |= ui
+            .add(DragValue::new(&mut anim_slot.frame_offset).speed(0.1))
+            .changed();
+        ui.end_row();
+
+        ui.label("Speed");
+        changed |= ui
+            .add(
+                DragValue::new(&mut anim_slot.speed)
+                    .range(0.0..=10.0)
+                    .speed(0.01),
+            )
+            .changed();
+        ui.end_row();
+    });
+
+    changed
+}
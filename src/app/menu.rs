@@ -106,6 +106,10 @@ pub fn menu_bar(app: &mut SsbhApp, ui: &mut Ui) {
                 app.ui_state.preset_editor_open = true;
             }
 
+            if ui.button("Search Workspace").clicked() {
+                app.ui_state.search_open = true;
+            }
+
             if ui.button("⛭ Preferences").clicked() {
                 app.ui_state.preferences_window_open = true;
             }
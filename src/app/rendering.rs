@@ -1,6 +1,6 @@
 use ssbh_wgpu::{CameraTransforms, SsbhRenderer, animation::camera::animate_camera};
 
-use crate::{CameraState, CameraValues, RenderState};
+use crate::{CameraState, CameraValues, RenderState, blend::blend_anims};
 
 use super::SsbhApp;
 
@@ -119,7 +119,7 @@ impl SsbhApp {
             .zip(self.animation_state.animations.iter())
         {
             // Only render enabled animations.
-            let animations = model_animations
+            let slots: Vec<_> = model_animations
                 .iter()
                 .filter(|anim_slot| anim_slot.is_enabled)
                 .filter_map(|anim_slot| {
@@ -127,17 +127,38 @@ impl SsbhApp {
                         .animation
                         .and_then(|anim_index| anim_index.get_animation(&self.models))
                         .and_then(|(_, a)| a.as_ref())
-                });
+                        .map(|a| (anim_slot, a))
+                })
+                .collect();
+
+            let skel = model
+                .model
+                .skels
+                .iter()
+                .find(|(f, _)| f == "model.nusktb")
+                .and_then(|(_, m)| m.as_ref());
+
+            // Sample and blend slots on the CPU only if necessary.
+            let (blended, current_frame) = if slots.iter().all(|(s, _)| s.is_unblended()) {
+                (None, self.animation_state.current_frame)
+            } else {
+                let blended = blend_anims(
+                    &slots,
+                    skel,
+                    self.animation_state.current_frame,
+                    self.animation_state.should_loop,
+                );
+                (Some(blended), 0.0)
+            };
+            let animations: Vec<_> = match &blended {
+                Some(blended) => vec![blended],
+                None => slots.iter().map(|(_, a)| *a).collect(),
+            };
 
             render_model.apply_anims(
                 queue,
-                animations,
-                model
-                    .model
-                    .skels
-                    .iter()
-                    .find(|(f, _)| f == "model.nusktb")
-                    .and_then(|(_, m)| m.as_ref()),
+                animations.into_iter(),
+                skel,
                 model
                     .model
                     .matls
@@ -155,7 +176,7 @@ impl SsbhApp {
                     None
                 },
                 &render_state.shared_data,
-                self.animation_state.current_frame,
+                current_frame,
             );
         }
     }
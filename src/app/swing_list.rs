@@ -46,24 +46,34 @@ pub fn swing_list(ctx: &Context, app: &mut SsbhApp, ui: &mut Ui) {
                         }
                     });
 
-                    if let Some(swing_prc) = get_swing_prc(i, &app.swing_state, &app.models)
+                    if let Some((prc_index, swing_prc)) =
+                        get_swing_prc(i, &app.swing_state, &app.models)
                         && let Some(hidden_collisions) =
                             app.swing_state.hidden_collisions.get_mut(i)
                     {
-                        list_swing_bones(ctx, id, ui, swing_prc, hidden_collisions);
+                        list_swing_bones(
+                            ctx,
+                            id,
+                            ui,
+                            swing_prc,
+                            prc_index,
+                            &mut app.swing_state.selected_swing_bone,
+                            hidden_collisions,
+                        );
                     }
                 }
             });
     }
 }
 
+/// The folder index and swing.prc assigned to the folder at `model_index`.
 fn get_swing_prc<'a>(
     model_index: usize,
     state: &SwingState,
     models: &'a [ModelFolderState],
-) -> Option<&'a SwingPrc> {
+) -> Option<(usize, &'a SwingPrc)> {
     let prc_index = state.selected_swing_folders.get(model_index)?.as_ref()?;
-    models.get(*prc_index)?.swing_prc.as_ref()
+    Some((*prc_index, models.get(*prc_index)?.swing_prc.as_ref()?))
 }
 
 fn list_swing_bones(
@@ -71,6 +81,8 @@ fn list_swing_bones(
     id: egui::Id,
     ui: &mut Ui,
     swing_prc: &SwingPrc,
+    prc_index: usize,
+    selected_swing_bone: &mut Option<(usize, usize)>,
     hidden_collisions: &mut HashSet<u64>,
 ) {
     for (i, swing_bone) in swing_prc.swingbones.iter().enumerate() {
@@ -78,7 +90,13 @@ fn list_swing_bones(
         CollapsingState::load_with_default_open(ctx, id, true)
             .show_header(ui, |ui| {
                 let name = swing_bone.name;
-                ui.label(format!("swingbones[{i}] {name}"));
+                let is_selected = *selected_swing_bone == Some((prc_index, i));
+                if ui
+                    .selectable_label(is_selected, format!("swingbones[{i}] {name}"))
+                    .clicked()
+                {
+                    *selected_swing_bone = (!is_selected).then_some((prc_index, i));
+                }
             })
             .body(|ui| {
                 list_params(ctx, id, ui, &swing_bone.params, hidden_collisions);
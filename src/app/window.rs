@@ -1,15 +1,21 @@
 mod camera;
+mod compare;
 mod device_info;
 mod log;
 mod new_release;
 mod preferences;
+mod rename;
 mod render_settings;
+mod search;
 mod stage_lighting;
 
 pub use self::log::log_window;
 pub use camera::camera_settings_window;
+pub use compare::folder_comparison_window;
 pub use device_info::device_info_window;
 pub use new_release::new_release_window;
 pub use preferences::preferences_window;
+pub use rename::rename_window;
 pub use render_settings::render_settings_window;
+pub use search::search_window;
 pub use stage_lighting::stage_lighting_window;
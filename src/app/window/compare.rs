@@ -0,0 +1,117 @@
+use egui::{CollapsingHeader, RichText, ScrollArea};
+use log::error;
+use rfd::FileDialog;
+
+use crate::{
+    app::{ERROR_COLOR, WARNING_COLOR},
+    compare::{FileDiffKind, FolderComparisonState, compare_folders, comparison_markdown},
+    model_folder::ModelFolderState,
+    path::folder_display_name,
+};
+
+/// Show the differences between a folder and a reference folder.
+/// Returns the name of the file to open if one was clicked.
+pub fn folder_comparison_window(
+    ctx: &egui::Context,
+    state: &mut Option<FolderComparisonState>,
+    models: &[ModelFolderState],
+) -> Option<String> {
+    let mut file_to_open = None;
+
+    let mut open = true;
+    if let Some(comparison) = state
+        && let Some(model) = models.get(comparison.folder_index)
+    {
+        egui::Window::new("Folder Comparison")
+            .open(&mut open)
+            .resizable(true)
+            .default_width(600.0)
+            .show(ctx, |ui| {
+                ui.label(format!("Folder: {}", model.folder_path.to_string_lossy()));
+                ui.label(format!(
+                    "Reference: {}",
+                    comparison.reference_path.to_string_lossy()
+                ));
+
+                ui.horizontal(|ui| {
+                    if ui.button("Refresh").clicked() {
+                        // Include any unsaved edits to the folder.
+                        comparison.diffs = compare_folders(&model.model, &comparison.reference);
+                    }
+
+                    if ui.button("Export...").clicked()
+                        && let Some(file) = FileDialog::new()
+                            .add_filter("Markdown", &["md"])
+                            .save_file()
+                    {
+                        let reference_name = comparison
+                            .reference_path
+                            .file_name()
+                            .map(|f| f.to_string_lossy().to_string())
+                            .unwrap_or_default();
+                        let markdown = comparison_markdown(
+                            &comparison.diffs,
+                            &folder_display_name(model),
+                            &reference_name,
+                        );
+                        if let Err(e) = std::fs::write(&file, markdown) {
+                            error!("Failed to save {file:?}: {e}");
+                        }
+                    }
+                });
+                ui.separator();
+
+                if comparison.diffs.is_empty() {
+                    ui.label("No differences found.");
+                    return;
+                }
+
+                ScrollArea::vertical()
+                    .auto_shrink([false; 2])
+                    .show(ui, |ui| {
+                        for diff in &comparison.diffs {
+                            ui.horizontal(|ui| {
+                                let kind = match diff.kind {
+                                    FileDiffKind::Added => RichText::new("Added"),
+                                    FileDiffKind::Removed => {
+                                        RichText::new("Removed").color(ERROR_COLOR)
+                                    }
+                                    FileDiffKind::Modified => {
+                                        RichText::new("Modified").color(WARNING_COLOR)
+                                    }
+                                };
+                                ui.label(kind);
+
+                                // Removed files only exist in the reference folder.
+                                if diff.kind == FileDiffKind::Removed {
+                                    ui.label(&diff.file_name);
+                                } else if ui.link(&diff.file_name).clicked() {
+                                    file_to_open = Some(diff.file_name.clone());
+                                }
+                            });
+
+                            if !diff.changes.is_empty() {
+                                CollapsingHeader::new(format!("{} changes", diff.changes.len()))
+                                    .id_salt(("folder_comparison", &diff.file_name))
+                                    .show(ui, |ui| {
+                                        for change in &diff.changes {
+                                            ui.label(change);
+                                        }
+                                    });
+                            }
+                        }
+                    });
+            });
+    }
+
+    // Close the window if the folder was removed.
+    if !open
+        || state
+            .as_ref()
+            .is_some_and(|c| models.get(c.folder_index).is_none())
+    {
+        *state = None;
+    }
+
+    file_to_open
+}
@@ -0,0 +1,135 @@
+use egui::{Button, DragValue, Grid, ScrollArea, TextEdit};
+use ssbh_data::matl_data::MatlEntryData;
+
+use crate::{
+    app::warning_icon_text,
+    horizontal_separator_empty,
+    material::save_material_presets,
+    model_folder::ModelFolderState,
+    path::{folder_editor_title, presets_file},
+    rename::{RenameChange, RenameState, RenameTarget, rename, rename_error},
+};
+
+/// Show a preview of the files affected by a rename and return the applied changes.
+pub fn rename_window(
+    ctx: &egui::Context,
+    state: &mut Option<RenameState>,
+    models: &mut [ModelFolderState],
+    material_presets: &mut [MatlEntryData],
+) -> Vec<RenameChange> {
+    let mut applied_changes = Vec::new();
+
+    let mut open = true;
+    let mut should_close = false;
+    if let Some(rename_state) = state {
+        egui::Window::new("Rename")
+            .open(&mut open)
+            .resizable(false)
+            .show(ctx, |ui| {
+                Grid::new("rename_grid").show(ui, |ui| match &rename_state.target {
+                    RenameTarget::MeshObject { name, subindex } => {
+                        ui.label("Mesh Object");
+                        ui.label(format!("{name} ({subindex})"));
+                        ui.end_row();
+
+                        ui.label("New Name");
+                        ui.add(TextEdit::singleline(&mut rename_state.new_name).clip_text(false));
+                        ui.end_row();
+
+                        ui.label("New Subindex");
+                        ui.add(DragValue::new(&mut rename_state.new_subindex));
+                        ui.end_row();
+                    }
+                    RenameTarget::MaterialLabel { label } => {
+                        ui.label("Material Label");
+                        ui.label(label);
+                        ui.end_row();
+
+                        ui.label("New Label");
+                        ui.add(TextEdit::singleline(&mut rename_state.new_name).clip_text(false));
+                        ui.end_row();
+                    }
+                    RenameTarget::Bone { name } => {
+                        ui.label("Bone");
+                        ui.label(name);
+                        ui.end_row();
+
+                        ui.label("New Name");
+                        ui.add(TextEdit::singleline(&mut rename_state.new_name).clip_text(false));
+                        ui.end_row();
+                    }
+                });
+
+                let error = rename_error(models, rename_state);
+                if let Some(error) = &error {
+                    ui.label(warning_icon_text(error));
+                }
+                horizontal_separator_empty(ui);
+
+                // Preview the changes without modifying any files.
+                let changes = rename(models, material_presets, rename_state, |_, _| false);
+
+                ui.heading("Affected Files");
+                ScrollArea::vertical().max_height(300.0).show(ui, |ui| {
+                    for change in &changes {
+                        let key = (change.folder_index, change.file_name.clone());
+                        let mut enabled = !rename_state.skipped_files.contains(&key);
+
+                        let folder = change.folder_index.map(|i| &models[i].folder_path);
+                        let file_name = match folder {
+                            Some(folder) => folder_editor_title(folder, &change.file_name),
+                            None => change.file_name.clone(),
+                        };
+                        let mut response = ui.checkbox(
+                            &mut enabled,
+                            format!("{file_name} ({} references)", change.count),
+                        );
+                        if let Some(folder) = folder {
+                            response = response.on_hover_text(folder.to_string_lossy());
+                        }
+
+                        if response.changed() {
+                            if enabled {
+                                rename_state.skipped_files.remove(&key);
+                            } else {
+                                rename_state.skipped_files.insert(key);
+                            }
+                        }
+                    }
+                });
+                horizontal_separator_empty(ui);
+
+                ui.horizontal(|ui| {
+                    if ui
+                        .add_enabled(
+                            error.is_none() && !changes.is_empty(),
+                            Button::new("Rename"),
+                        )
+                        .clicked()
+                    {
+                        let skipped_files = &rename_state.skipped_files;
+                        applied_changes =
+                            rename(models, material_presets, rename_state, |i, file_name| {
+                                !skipped_files.contains(&(i, file_name.to_owned()))
+                            });
+
+                        // Presets aren't part of the workspace, so save them immediately.
+                        if applied_changes.iter().any(|c| c.folder_index.is_none()) {
+                            save_material_presets(material_presets, presets_file());
+                        }
+                        should_close = true;
+                    }
+
+                    if ui.button("Cancel").clicked() {
+                        should_close = true;
+                    }
+                });
+            });
+    }
+
+    if !open || should_close {
+        *state = None;
+    }
+
+    applied_changes
+}
@@ -0,0 +1,84 @@
+use egui::{CollapsingHeader, Grid, ScrollArea, TextEdit};
+
+use crate::{
+    model_folder::ModelFolderState,
+    path::folder_editor_title,
+    search::{SearchResult, SearchState, search_workspace},
+};
+
+/// Show the search panel and return the result to open if one was clicked.
+pub fn search_window(
+    ctx: &egui::Context,
+    open: &mut bool,
+    state: &mut SearchState,
+    models: &[ModelFolderState],
+) -> Option<SearchResult> {
+    let mut result_to_open = None;
+
+    egui::Window::new("Search Workspace")
+        .open(open)
+        .resizable(true)
+        .default_width(600.0)
+        .show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                let response = ui.add(
+                    TextEdit::singleline(&mut state.query)
+                        .hint_text("Bone, material, texture, or mesh object name")
+                        .desired_width(400.0),
+                );
+                // Searching large workspaces every frame is expensive.
+                let submitted =
+                    response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+                if ui.button("Search").clicked() || submitted {
+                    state.results = search_workspace(models, &state.query);
+                }
+            });
+            ui.label(format!("{} results", state.results.len()));
+            ui.separator();
+
+            ScrollArea::vertical()
+                .auto_shrink([false; 2])
+                .show(ui, |ui| {
+                    let mut categories: Vec<_> =
+                        state.results.iter().map(|r| r.item.category()).collect();
+                    categories.sort_unstable();
+                    categories.dedup();
+
+                    for category in categories {
+                        let results: Vec<_> = state
+                            .results
+                            .iter()
+                            .filter(|r| r.item.category() == category)
+                            .collect();
+
+                        CollapsingHeader::new(format!("{category} ({})", results.len()))
+                            .default_open(true)
+                            .show(ui, |ui| {
+                                Grid::new(("search_grid", category))
+                                    .striped(true)
+                                    .show(ui, |ui| {
+                                        for result in results {
+                                            // Folders may be removed after searching.
+                                            let Some(model) = models.get(result.folder_index)
+                                            else {
+                                                continue;
+                                            };
+                                            ui.label(folder_editor_title(
+                                                &model.folder_path,
+                                                &result.file_name,
+                                            ))
+                                            .on_hover_text(model.folder_path.to_string_lossy());
+
+                                            if ui.link(&result.description).clicked() {
+                                                result_to_open = Some(result.clone());
+                                            }
+                                            ui.end_row();
+                                        }
+                                    });
+                            });
+                    }
+                });
+        });
+
+    result_to_open
+}
@@ -0,0 +1,391 @@
+use std::collections::HashSet;
+
+use glam::{Quat, Vec3};
+use ssbh_data::{
+    Vector3, Vector4,
+    anim_data::{GroupData, GroupType, NodeData, TrackData, TrackValues, Transform, UvTransform},
+    prelude::*,
+};
+
+use crate::{AnimationSlot, BlendMode};
+
+/// Combine the anims for each slot into a single anim with one frame for `current_frame`.
+///
+/// Transform tracks are blended using each slot's weight, mode, and bone mask.
+/// Other tracks from later slots replace tracks from earlier slots.
+pub fn blend_anims(
+    slots: &[(&AnimationSlot, &AnimData)],
+    skel: Option<&SkelData>,
+    current_frame: f32,
+    should_loop: bool,
+) -> AnimData {
+    let mut groups: Vec<GroupData> = Vec::new();
+
+    for (slot, anim) in slots {
+        let frame = slot_frame(slot, anim, current_frame, should_loop);
+        let mask = slot
+            .bone_mask
+            .as_ref()
+            .and_then(|root| skel.map(|skel| masked_bones(skel, root)));
+
+        for group in &anim.groups {
+            let blended_group = match groups.iter_mut().find(|g| g.group_type == group.group_type) {
+                Some(g) => g,
+                None => {
+                    groups.push(GroupData {
+                        group_type: group.group_type,
+                        nodes: Vec::new(),
+                    });
+                    groups.last_mut().unwrap()
+                }
+            };
+
+            for node in &group.nodes {
+                if group.group_type == GroupType::Transform
+                    && mask.as_ref().is_some_and(|m| !m.contains(&node.name))
+                {
+                    continue;
+                }
+
+                let blended_node =
+                    match blended_group.nodes.iter_mut().find(|n| n.name == node.name) {
+                        Some(n) => n,
+                        None => {
+                            blended_group.nodes.push(NodeData {
+                                name: node.name.clone(),
+                                tracks: Vec::new(),
+                            });
+                            blended_group.nodes.last_mut().unwrap()
+                        }
+                    };
+
+                for track in &node.tracks {
+                    let values = sample_values(&track.values, frame);
+                    let previous = blended_node
+                        .tracks
+                        .iter_mut()
+                        .find(|t| t.name == track.name);
+
+                    match (&values, previous) {
+                        (TrackValues::Transform(v), previous) if !v.is_empty() => {
+                            let rest = skel
+                                .and_then(|s| s.bones.iter().find(|b| b.name == node.name))
+                                .map(|b| {
+                                    glam::Mat4::from_cols_array_2d(&b.transform)
+                                        .to_scale_rotation_translation()
+                                });
+                            let previous_value = previous.as_ref().and_then(|t| match &t.values {
+                                TrackValues::Transform(v) => v.first().cloned(),
+                                _ => None,
+                            });
+
+                            let value = blend_transform(
+                                previous_value.as_ref(),
+                                &v[0],
+                                rest,
+                                slot.weight,
+                                slot.blend_mode,
+                            );
+                            let new_track = TrackData {
+                                values: TrackValues::Transform(vec![value]),
+                                ..track.clone()
+                            };
+                            match previous {
+                                Some(previous) => *previous = new_track,
+                                None => blended_node.tracks.push(new_track),
+                            }
+                        }
+                        (_, Some(previous)) => {
+                            if slot.weight > 0.0 {
+                                previous.values = values;
+                            }
+                        }
+                        (_, None) => blended_node.tracks.push(TrackData {
+                            values,
+                            ..track.clone()
+                        }),
+                    }
+                }
+            }
+        }
+    }
+
+    AnimData {
+        major_version: 2,
+        minor_version: 0,
+        final_frame_index: 0.0,
+        groups,
+    }
+}
+
+/// The frame in the slot's anim for the global `current_frame`.
+pub fn slot_frame(
+    slot: &AnimationSlot,
+    anim: &AnimData,
+    current_frame: f32,
+    should_loop: bool,
+) -> f32 {
+    let final_frame = anim.final_frame_index.max(0.0);
+    let frame = current_frame * slot.speed + slot.frame_offset;
+    if should_loop {
+        frame.rem_euclid(final_frame + 1.0)
+    } else {
+        frame.clamp(0.0, final_frame)
+    }
+}
+
+/// The names of `root` and all of its descendants.
+fn masked_bones(skel: &SkelData, root: &str) -> HashSet<String> {
+    skel.bones
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| {
+            let mut index = Some(*i);
+            // Limit the iterations in case the skeleton has cycles.
+            for _ in 0..skel.bones.len() {
+                match index.and_then(|i| skel.bones.get(i)) {
+                    Some(bone) if bone.name == root => return true,
+                    Some(bone) => index = bone.parent_index,
+                    None => return false,
+                }
+            }
+            false
+        })
+        .map(|(_, b)| b.name.clone())
+        .collect()
+}
+
+fn blend_transform(
+    previous: Option<&Transform>,
+    value: &Transform,
+    rest: Option<(Vec3, Quat, Vec3)>,
+    weight: f32,
+    mode: BlendMode,
+) -> Transform {
+    let (s, r, t) = decompose(value);
+    let weight = weight.clamp(0.0, 1.0);
+
+    let (s, r, t) = match mode {
+        BlendMode::Override => {
+            // Blend with the rest pose if no previous slots animate this bone.
+            let Some((s0, r0, t0)) = previous.map(decompose).or(rest) else {
+                return value.clone();
+            };
+            (s0.lerp(s, weight), r0.slerp(r, weight), t0.lerp(t, weight))
+        }
+        BlendMode::Additive => {
+            let (rest_s, rest_r, rest_t) = rest.unwrap_or((Vec3::ONE, Quat::IDENTITY, Vec3::ZERO));
+            let (s0, r0, t0) = previous.map(decompose).unwrap_or((rest_s, rest_r, rest_t));
+
+            let scale_ratio = Vec3::select(rest_s.cmpeq(Vec3::ZERO), Vec3::ONE, s / rest_s);
+            let rotation_delta = Quat::IDENTITY.slerp(rest_r.inverse() * r, weight);
+            (
+                s0 * Vec3::ONE.lerp(scale_ratio, weight),
+                (r0 * rotation_delta).normalize(),
+                t0 + (t - rest_t) * weight,
+            )
+        }
+    };
+
+    Transform {
+        scale: Vector3::new(s.x, s.y, s.z),
+        rotation: Vector4::new(r.x, r.y, r.z, r.w),
+        translation: Vector3::new(t.x, t.y, t.z),
+    }
+}
+
+fn decompose(value: &Transform) -> (Vec3, Quat, Vec3) {
+    (
+        Vec3::new(value.scale.x, value.scale.y, value.scale.z),
+        Quat::from_xyzw(
+            value.rotation.x,
+            value.rotation.y,
+            value.rotation.z,
+            value.rotation.w,
+        )
+        .normalize(),
+        Vec3::new(
+            value.translation.x,
+            value.translation.y,
+            value.translation.z,
+        ),
+    )
+}
+
+/// The interpolated value at `frame` or an empty list if there are no values.
+fn sample_values(values: &TrackValues, frame: f32) -> TrackValues {
+    match values {
+        TrackValues::Transform(v) => TrackValues::Transform(sample(v, frame, |a, b, t| {
+            let (s0, r0, t0) = decompose(a);
+            let (s1, r1, t1) = decompose(b);
+            let (s, r, t) = (s0.lerp(s1, t), r0.slerp(r1, t), t0.lerp(t1, t));
+            Transform {
+                scale: Vector3::new(s.x, s.y, s.z),
+                rotation: Vector4::new(r.x, r.y, r.z, r.w),
+                translation: Vector3::new(t.x, t.y, t.z),
+            }
+        })),
+        TrackValues::UvTransform(v) => {
+            TrackValues::UvTransform(sample(v, frame, |a, b, t| UvTransform {
+                scale_u: lerp(a.scale_u, b.scale_u, t),
+                scale_v: lerp(a.scale_v, b.scale_v, t),
+                rotation: lerp(a.rotation, b.rotation, t),
+                translate_u: lerp(a.translate_u, b.translate_u, t),
+                translate_v: lerp(a.translate_v, b.translate_v, t),
+            }))
+        }
+        TrackValues::Float(v) => TrackValues::Float(sample(v, frame, |a, b, t| lerp(*a, *b, t))),
+        TrackValues::PatternIndex(v) => TrackValues::PatternIndex(sample(v, frame, |a, _, _| *a)),
+        TrackValues::Boolean(v) => TrackValues::Boolean(sample(v, frame, |a, _, _| *a)),
+        TrackValues::Vector4(v) => TrackValues::Vector4(sample(v, frame, |a, b, t| {
+            Vector4::new(
+                lerp(a.x, b.x, t),
+                lerp(a.y, b.y, t),
+                lerp(a.z, b.z, t),
+                lerp(a.w, b.w, t),
+            )
+        })),
+    }
+}
+
+fn sample<T, F: Fn(&T, &T, f32) -> T>(values: &[T], frame: f32, interpolate: F) -> Vec<T> {
+    if values.is_empty() {
+        return Vec::new();
+    }
+
+    // Frames past the end use the last value.
+    let last = values.len() - 1;
+    let current = (frame.max(0.0).floor() as usize).min(last);
+    let next = (current + 1).min(last);
+    let factor = if current == next {
+        0.0
+    } else {
+        frame - current as f32
+    };
+    vec![interpolate(&values[current], &values[next], factor)]
+}
+
+fn lerp(a: f32, b: f32, t: f32) -> f32 {
+    a + (b - a) * t
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::bone;
+    use glam::Mat4;
+    use ssbh_data::anim_data::TransformFlags;
+
+    fn transform(rotation: Quat, translation: Vec3) -> Transform {
+        Transform {
+            scale: Vector3::new(1.0, 1.0, 1.0),
+            rotation: Vector4::new(rotation.x, rotation.y, rotation.z, rotation.w),
+            translation: Vector3::new(translation.x, translation.y, translation.z),
+        }
+    }
+
+    fn anim(nodes: Vec<(&str, Vec<Transform>)>) -> AnimData {
+        AnimData {
+            major_version: 2,
+            minor_version: 0,
+            final_frame_index: 1.0,
+            groups: vec![GroupData {
+                group_type: GroupType::Transform,
+                nodes: nodes
+                    .into_iter()
+                    .map(|(name, values)| NodeData {
+                        name: name.to_owned(),
+                        tracks: vec![TrackData {
+                            name: "Transform".to_owned(),
+                            compensate_scale: false,
+                            transform_flags: TransformFlags::default(),
+                            values: TrackValues::Transform(values),
+                        }],
+                    })
+                    .collect(),
+            }],
+        }
+    }
+
+    fn translation(anim: &AnimData, name: &str) -> Vec3 {
+        let node = anim.groups[0]
+            .nodes
+            .iter()
+            .find(|n| n.name == name)
+            .unwrap();
+        match &node.tracks[0].values {
+            TrackValues::Transform(v) => decompose(&v[0]).2,
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn blend_override_weight_and_mask() {
+        let skel = SkelData {
+            major_version: 1,
+            minor_version: 0,
+            bones: vec![
+                bone("Hip", None, Mat4::IDENTITY),
+                bone("Waist", Some(0), Mat4::IDENTITY),
+                bone("Head", Some(1), Mat4::IDENTITY),
+            ],
+        };
+        let body = anim(vec![
+            ("Hip", vec![transform(Quat::IDENTITY, Vec3::X)]),
+            ("Head", vec![transform(Quat::IDENTITY, Vec3::X)]),
+        ]);
+        let face = anim(vec![
+            ("Hip", vec![transform(Quat::IDENTITY, Vec3::Y)]),
+            ("Head", vec![transform(Quat::IDENTITY, Vec3::Y)]),
+        ]);
+
+        let body_slot = AnimationSlot::new();
+        let face_slot = AnimationSlot {
+            weight: 0.5,
+            bone_mask: Some("Waist".to_owned()),
+            ..AnimationSlot::new()
+        };
+
+        let blended = blend_anims(
+            &[(&body_slot, &body), (&face_slot, &face)],
+            Some(&skel),
+            0.0,
+            true,
+        );
+        assert_eq!(Vec3::X, translation(&blended, "Hip"));
+        assert_eq!(Vec3::new(0.5, 0.5, 0.0), translation(&blended, "Head"));
+    }
+
+    #[test]
+    fn blend_additive_offset_speed() {
+        let body = anim(vec![("Hip", vec![transform(Quat::IDENTITY, Vec3::X)])]);
+        let offset = anim(vec![(
+            "Hip",
+            vec![
+                transform(Quat::IDENTITY, Vec3::ZERO),
+                transform(Quat::from_rotation_z(1.0), Vec3::Y),
+            ],
+        )]);
+
+        let additive_slot = AnimationSlot {
+            blend_mode: BlendMode::Additive,
+            frame_offset: 0.5,
+            speed: 2.0,
+            ..AnimationSlot::new()
+        };
+
+        // The additive anim plays frame 0.25 * 2.0 + 0.5 = 1.0.
+        let blended = blend_anims(
+            &[(&AnimationSlot::new(), &body), (&additive_slot, &offset)],
+            None,
+            0.25,
+            false,
+        );
+        let TrackValues::Transform(v) = &blended.groups[0].nodes[0].tracks[0].values else {
+            panic!()
+        };
+        let (_, r, t) = decompose(&v[0]);
+        assert!(t.abs_diff_eq(Vec3::new(1.0, 1.0, 0.0), 1e-6));
+        assert!(r.abs_diff_eq(Quat::from_rotation_z(1.0), 1e-6));
+    }
+}
@@ -0,0 +1,478 @@
+use std::{fmt::Write, path::PathBuf};
+
+use nutexb::NutexbFile;
+use ssbh_data::{matl_data::MatlEntryData, prelude::*};
+use ssbh_wgpu::ModelFolder;
+
+use crate::editors::{matl::matl_entry_diff, skel::skel_diff};
+
+pub struct FolderComparisonState {
+    pub folder_index: usize,
+    pub reference_path: PathBuf,
+    pub reference: ModelFolder,
+    pub diffs: Vec<FileDiff>,
+}
+
+impl FolderComparisonState {
+    pub fn new(
+        folder_index: usize,
+        folder: &ModelFolder,
+        reference_path: PathBuf,
+        reference: ModelFolder,
+    ) -> Self {
+        Self {
+            folder_index,
+            reference_path,
+            diffs: compare_folders(folder, &reference),
+            reference,
+        }
+    }
+}
+
+/// Changes to a file compared to the file with the same name in the reference folder.
+#[derive(Debug, PartialEq)]
+pub struct FileDiff {
+    pub file_name: String,
+    pub kind: FileDiffKind,
+    pub changes: Vec<String>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum FileDiffKind {
+    Added,
+    Removed,
+    Modified,
+}
+
+// Use a tolerance to ignore floating point errors from resaving skeletons.
+const SKEL_TOLERANCE: f32 = 0.001;
+
+/// Compare each file in `folder` to the file with the same name in `reference`.
+/// Unchanged files are not included.
+pub fn compare_folders(folder: &ModelFolder, reference: &ModelFolder) -> Vec<FileDiff> {
+    let mut diffs = Vec::new();
+    compare_files(&mut diffs, &folder.meshes, &reference.meshes, mesh_changes);
+    compare_files(&mut diffs, &folder.skels, &reference.skels, skel_changes);
+    compare_files(&mut diffs, &folder.matls, &reference.matls, matl_changes);
+    compare_files(&mut diffs, &folder.modls, &reference.modls, modl_changes);
+    compare_files(
+        &mut diffs,
+        &folder.hlpbs,
+        &reference.hlpbs,
+        contents_changes,
+    );
+    compare_files(&mut diffs, &folder.adjs, &reference.adjs, contents_changes);
+    compare_files(
+        &mut diffs,
+        &folder.meshexes,
+        &reference.meshexes,
+        contents_changes,
+    );
+    compare_files(
+        &mut diffs,
+        &folder.anims,
+        &reference.anims,
+        contents_changes,
+    );
+    compare_files(
+        &mut diffs,
+        &folder.nutexbs,
+        &reference.nutexbs,
+        nutexb_changes,
+    );
+    diffs
+}
+
+pub fn comparison_markdown(
+    diffs: &[FileDiff],
+    folder_name: &str,
+    reference_folder_name: &str,
+) -> String {
+    let mut text = String::new();
+
+    // Writing to a String can't fail.
+    writeln!(
+        &mut text,
+        "# Comparison of {folder_name} with {reference_folder_name}"
+    )
+    .unwrap();
+    if diffs.is_empty() {
+        writeln!(&mut text, "\nNo differences found.").unwrap();
+    }
+
+    for diff in diffs {
+        writeln!(&mut text, "\n## {} ({:?})", diff.file_name, diff.kind).unwrap();
+        for change in &diff.changes {
+            writeln!(&mut text, "- {change}").unwrap();
+        }
+    }
+    text
+}
+
+fn compare_files<T>(
+    diffs: &mut Vec<FileDiff>,
+    files: &[(String, Option<T>)],
+    reference_files: &[(String, Option<T>)],
+    changes: impl Fn(&T, &T) -> Vec<String>,
+) {
+    for (name, file) in files {
+        match reference_files.iter().find(|(n, _)| n == name) {
+            Some((_, reference)) => {
+                let changes = match (file, reference) {
+                    (Some(file), Some(reference)) => changes(file, reference),
+                    (Some(_), None) => vec!["The reference file failed to load.".to_owned()],
+                    (None, Some(_)) => vec!["The file failed to load.".to_owned()],
+                    (None, None) => Vec::new(),
+                };
+                if !changes.is_empty() {
+                    diffs.push(FileDiff {
+                        file_name: name.clone(),
+                        kind: FileDiffKind::Modified,
+                        changes,
+                    });
+                }
+            }
+            None => diffs.push(FileDiff {
+                file_name: name.clone(),
+                kind: FileDiffKind::Added,
+                changes: Vec::new(),
+            }),
+        }
+    }
+
+    for (name, _) in reference_files {
+        if !files.iter().any(|(n, _)| n == name) {
+            diffs.push(FileDiff {
+                file_name: name.clone(),
+                kind: FileDiffKind::Removed,
+                changes: Vec::new(),
+            });
+        }
+    }
+}
+
+fn contents_changes<T: PartialEq>(file: &T, reference: &T) -> Vec<String> {
+    if file != reference {
+        vec!["The file contents changed.".to_owned()]
+    } else {
+        Vec::new()
+    }
+}
+
+fn mesh_changes(mesh: &MeshData, reference: &MeshData) -> Vec<String> {
+    let mut changes = Vec::new();
+
+    for o in &mesh.objects {
+        match reference
+            .objects
+            .iter()
+            .find(|r| r.name == o.name && r.subindex == o.subindex)
+        {
+            Some(r) => {
+                let vertex_count = o.vertex_count().unwrap_or_default();
+                let reference_vertex_count = r.vertex_count().unwrap_or_default();
+                if vertex_count != reference_vertex_count {
+                    changes.push(format!(
+                        "Changed vertex count for {} ({}): {reference_vertex_count} -> {vertex_count}",
+                        o.name, o.subindex
+                    ));
+                }
+                if o.vertex_indices.len() != r.vertex_indices.len() {
+                    changes.push(format!(
+                        "Changed triangle count for {} ({}): {} -> {}",
+                        o.name,
+                        o.subindex,
+                        r.vertex_indices.len() / 3,
+                        o.vertex_indices.len() / 3
+                    ));
+                }
+            }
+            None => changes.push(format!("Added mesh object {} ({})", o.name, o.subindex)),
+        }
+    }
+
+    for r in &reference.objects {
+        if !mesh
+            .objects
+            .iter()
+            .any(|o| o.name == r.name && o.subindex == r.subindex)
+        {
+            changes.push(format!("Removed mesh object {} ({})", r.name, r.subindex));
+        }
+    }
+
+    changes
+}
+
+fn skel_changes(skel: &SkelData, reference: &SkelData) -> Vec<String> {
+    let diff = skel_diff(skel, reference, SKEL_TOLERANCE);
+
+    let optional_name = |name: &Option<String>| name.clone().unwrap_or("None".to_owned());
+
+    diff.added
+        .iter()
+        .map(|name| format!("Added bone {name}"))
+        .chain(
+            diff.removed
+                .iter()
+                .map(|name| format!("Removed bone {name}")),
+        )
+        .chain(diff.reparented.iter().map(|b| {
+            format!(
+                "Changed parent for {}: {} -> {}",
+                b.name,
+                optional_name(&b.reference_parent),
+                optional_name(&b.parent)
+            )
+        }))
+        .chain(
+            diff.changed_transforms
+                .iter()
+                .map(|t| format!("Changed transform for {}", t.name)),
+        )
+        .chain(diff.reordered.iter().map(|b| {
+            format!(
+                "Changed index for {}: {} -> {}",
+                b.name, b.reference_index, b.index
+            )
+        }))
+        .collect()
+}
+
+fn matl_changes(matl: &MatlData, reference: &MatlData) -> Vec<String> {
+    let mut changes = Vec::new();
+
+    for e in &matl.entries {
+        match reference
+            .entries
+            .iter()
+            .find(|r| r.material_label == e.material_label)
+        {
+            Some(r) => changes.extend(
+                matl_entry_changes(e, r)
+                    .into_iter()
+                    .map(|c| format!("{}: {c}", e.material_label)),
+            ),
+            None => changes.push(format!("Added material {}", e.material_label)),
+        }
+    }
+
+    for r in &reference.entries {
+        if !matl
+            .entries
+            .iter()
+            .any(|e| e.material_label == r.material_label)
+        {
+            changes.push(format!("Removed material {}", r.material_label));
+        }
+    }
+
+    changes
+}
+
+/// Describe the shader and parameter differences between two materials.
+fn matl_entry_changes(entry: &MatlEntryData, reference: &MatlEntryData) -> Vec<String> {
+    let mut changes = Vec::new();
+    if entry.shader_label != reference.shader_label {
+        changes.push(format!(
+            "Changed shader label: {} -> {}",
+            reference.shader_label, entry.shader_label
+        ));
+    }
+
+    changes.extend(matl_entry_diff(entry, reference).into_iter().map(|d| {
+        match (d.value, d.reference_value) {
+            (Some(value), Some(reference_value)) => {
+                format!("Changed {}: {reference_value} -> {value}", d.param_id)
+            }
+            (Some(value), None) => format!("Added {}: {value}", d.param_id),
+            (None, _) => format!("Removed {}", d.param_id),
+        }
+    }));
+    changes
+}
+
+fn modl_changes(modl: &ModlData, reference: &ModlData) -> Vec<String> {
+    let mut changes = Vec::new();
+
+    for e in &modl.entries {
+        match reference.entries.iter().find(|r| {
+            r.mesh_object_name == e.mesh_object_name
+                && r.mesh_object_subindex == e.mesh_object_subindex
+        }) {
+            Some(r) => {
+                if e.material_label != r.material_label {
+                    changes.push(format!(
+                        "Changed material for {} ({}): {} -> {}",
+                        e.mesh_object_name,
+                        e.mesh_object_subindex,
+                        r.material_label,
+                        e.material_label
+                    ));
+                }
+            }
+            None => changes.push(format!(
+                "Added entry {} ({}) with material {}",
+                e.mesh_object_name, e.mesh_object_subindex, e.material_label
+            )),
+        }
+    }
+
+    for r in &reference.entries {
+        if !modl.entries.iter().any(|e| {
+            e.mesh_object_name == r.mesh_object_name
+                && e.mesh_object_subindex == r.mesh_object_subindex
+        }) {
+            changes.push(format!(
+                "Removed entry {} ({})",
+                r.mesh_object_name, r.mesh_object_subindex
+            ));
+        }
+    }
+
+    changes
+}
+
+fn nutexb_changes(nutexb: &NutexbFile, reference: &NutexbFile) -> Vec<String> {
+    let mut changes = Vec::new();
+
+    let footer = &nutexb.footer;
+    let reference_footer = &reference.footer;
+    let dimensions = (footer.width, footer.height, footer.depth);
+    let reference_dimensions = (
+        reference_footer.width,
+        reference_footer.height,
+        reference_footer.depth,
+    );
+    if dimensions != reference_dimensions {
+        changes.push(format!(
+            "Changed dimensions: {}x{}x{} -> {}x{}x{}",
+            reference_dimensions.0,
+            reference_dimensions.1,
+            reference_dimensions.2,
+            dimensions.0,
+            dimensions.1,
+            dimensions.2
+        ));
+    }
+    if footer.image_format != reference_footer.image_format {
+        changes.push(format!(
+            "Changed format: {:?} -> {:?}",
+            reference_footer.image_format, footer.image_format
+        ));
+    }
+    if footer.mipmap_count != reference_footer.mipmap_count {
+        changes.push(format!(
+            "Changed mipmap count: {} -> {}",
+            reference_footer.mipmap_count, footer.mipmap_count
+        ));
+    }
+    if footer.layer_count != reference_footer.layer_count {
+        changes.push(format!(
+            "Changed layer count: {} -> {}",
+            reference_footer.layer_count, footer.layer_count
+        ));
+    }
+
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ssbh_data::{
+        mesh_data::{AttributeData, MeshObjectData, VectorData},
+        modl_data::ModlEntryData,
+    };
+
+    fn mesh_object(name: &str, vertex_count: usize) -> MeshObjectData {
+        MeshObjectData {
+            name: name.to_owned(),
+            positions: vec![AttributeData {
+                name: "Position0".to_owned(),
+                data: VectorData::Vector3(vec![[0.0; 3]; vertex_count]),
+            }],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn compare_meshes() {
+        let mesh = MeshData {
+            major_version: 1,
+            minor_version: 10,
+            objects: vec![mesh_object("a", 3), mesh_object("c", 3)],
+        };
+        let reference = MeshData {
+            major_version: 1,
+            minor_version: 10,
+            objects: vec![mesh_object("a", 4), mesh_object("b", 3)],
+        };
+        assert_eq!(
+            vec![
+                "Changed vertex count for a (0): 4 -> 3",
+                "Added mesh object c (0)",
+                "Removed mesh object b (0)"
+            ],
+            mesh_changes(&mesh, &reference)
+        );
+    }
+
+    #[test]
+    fn compare_files_added_removed() {
+        let modl = |label: &str| ModlData {
+            major_version: 1,
+            minor_version: 0,
+            model_name: String::new(),
+            skeleton_file_name: String::new(),
+            material_file_names: Vec::new(),
+            animation_file_name: None,
+            mesh_file_name: String::new(),
+            entries: vec![ModlEntryData {
+                mesh_object_name: "a".to_owned(),
+                mesh_object_subindex: 0,
+                material_label: label.to_owned(),
+            }],
+        };
+
+        let mut diffs = Vec::new();
+        compare_files(
+            &mut diffs,
+            &[
+                ("model.numdlb".to_owned(), Some(modl("b"))),
+                ("added.numdlb".to_owned(), Some(modl("a"))),
+            ],
+            &[
+                ("model.numdlb".to_owned(), Some(modl("a"))),
+                ("removed.numdlb".to_owned(), Some(modl("a"))),
+            ],
+            modl_changes,
+        );
+
+        assert_eq!(
+            vec![
+                FileDiff {
+                    file_name: "model.numdlb".to_owned(),
+                    kind: FileDiffKind::Modified,
+                    changes: vec!["Changed material for a (0): a -> b".to_owned()]
+                },
+                FileDiff {
+                    file_name: "added.numdlb".to_owned(),
+                    kind: FileDiffKind::Added,
+                    changes: Vec::new()
+                },
+                FileDiff {
+                    file_name: "removed.numdlb".to_owned(),
+                    kind: FileDiffKind::Removed,
+                    changes: Vec::new()
+                },
+            ],
+            diffs
+        );
+
+        assert_eq!(
+            "# Comparison of a with b\n\n## model.numdlb (Modified)\n- Changed material for a (0): a -> b\n\n## added.numdlb (Added)\n\n## removed.numdlb (Removed)\n",
+            comparison_markdown(&diffs, "a", "b")
+        );
+    }
+}
@@ -1,5 +1,5 @@
 use crate::{
-    EditorResponse, path::folder_editor_title, save_file, save_file_as,
+    EditorResponse, export_json, import_json, path::folder_editor_title, save_file, save_file_as,
     validation::AdjValidationError,
 };
 use egui::{ScrollArea, special_emojis::GITHUB};
@@ -33,6 +33,15 @@ pub fn adj_editor(
                     if ui.button("Save As...").clicked() {
                         saved |= save_file_as(adj, folder_name, file_name, "Adj", "adjb");
                     }
+
+                    ui.separator();
+                    if ui.button("Import JSON...").clicked() {
+                        changed |= import_json(adj, folder_name);
+                    }
+
+                    if ui.button("Export JSON...").clicked() {
+                        export_json(adj, folder_name, file_name);
+                    }
                 });
 
                 ui.menu_button("Help", |ui| {
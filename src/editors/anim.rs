@@ -1,15 +1,15 @@
 use crate::{
     EditorResponse,
-    app::{AnimEditorState, AnimEditorTab},
+    app::{AnimEditorState, AnimEditorTab, FrameToolsState},
+    export_json, import_json,
     path::folder_editor_title,
     save_file, save_file_as,
 };
 use egui::{
-    CentralPanel, CollapsingHeader, DragValue, RichText, ScrollArea, SidePanel,
-    special_emojis::GITHUB,
+    Button, CentralPanel, CollapsingHeader, DragValue, Label, RichText, ScrollArea, Sense,
+    SidePanel, special_emojis::GITHUB,
 };
 use egui_extras::{Column, TableBuilder};
-use egui_plot::{Legend, Line, Plot, PlotPoint};
 
 use ssbh_data::{
     anim_data::{GroupData, TrackData, TrackValues},
@@ -17,11 +17,33 @@ use ssbh_data::{
 };
 use std::path::Path;
 
+mod bake;
+mod csv;
+mod filters;
+mod frames;
+mod graph;
+mod nodes;
+mod retarget;
+mod timeline;
+use bake::bake_helper_bones;
+use csv::{CsvAction, CsvTracks, csv_context_menu, export_csv, import_csv};
+use filters::filters_window;
+use frames::{frame_count, frame_tools_window};
+use graph::graph_view;
+use nodes::{add_node_window, copy_node_tracks, node_names};
+use retarget::retarget_window;
+use timeline::timeline_view;
+
+#[allow(clippy::too_many_arguments)]
 pub fn anim_editor(
     ctx: &egui::Context,
     folder_name: &Path,
     file_name: &str,
     anim: &mut AnimData,
+    skel: Option<&SkelData>,
+    mesh: Option<&MeshData>,
+    matl: Option<&MatlData>,
+    hlpb: Option<&HlpbData>,
     state: &mut AnimEditorState,
 ) -> EditorResponse {
     let mut open = true;
@@ -44,6 +66,71 @@ pub fn anim_editor(
                     if ui.button("Save As...").clicked() {
                         saved |= save_file_as(anim, folder_name, file_name, "Anim", "nuanmb");
                     }
+
+                    ui.separator();
+                    if ui.button("Import JSON...").clicked() {
+                        changed |= import_json(anim, folder_name);
+                    }
+
+                    if ui.button("Export JSON...").clicked() {
+                        export_json(anim, folder_name, file_name);
+                    }
+
+                    ui.separator();
+                    if ui
+                        .add_enabled(
+                            skel.is_some() && hlpb.is_some(),
+                            Button::new("Bake Helper Bones..."),
+                        )
+                        .on_hover_text(
+                            "Save a copy with transform tracks for the bones in the model.nuhlpb.",
+                        )
+                        .on_disabled_hover_text(
+                            "The folder does not contain a model.nusktb and model.nuhlpb.",
+                        )
+                        .clicked()
+                        && let (Some(skel), Some(hlpb)) = (skel, hlpb)
+                    {
+                        let baked = bake_helper_bones(anim, skel, hlpb);
+                        let stem = Path::new(file_name)
+                            .file_stem()
+                            .map(|s| s.to_string_lossy().to_string())
+                            .unwrap_or_default();
+                        save_file_as(
+                            &baked,
+                            folder_name,
+                            &format!("{stem}_baked.nuanmb"),
+                            "Anim",
+                            "nuanmb",
+                        );
+                    }
+                });
+
+                ui.menu_button("Frames", |ui| {
+                    if ui.button("Frame Tools...").clicked() {
+                        let count = frame_count(anim);
+                        state.frame_tools = FrameToolsState {
+                            is_open: true,
+                            start: 0,
+                            end: count - 1,
+                            frame_count: count,
+                            offset: 0,
+                        };
+                    }
+
+                    if ui.button("Curve Filters...").clicked() {
+                        state.filters.is_open = true;
+                    }
+
+                    if ui.button("Retarget...").clicked() {
+                        state.retarget.is_open = true;
+                    }
+                });
+
+                ui.menu_button("Node", |ui| {
+                    if ui.button("Add Node...").clicked() {
+                        state.add_node.is_open = true;
+                    }
                 });
 
                 ui.menu_button("Help", |ui| {
@@ -73,15 +160,31 @@ pub fn anim_editor(
                     AnimEditorTab::List,
                     RichText::new("List").heading(),
                 );
+                ui.selectable_value(
+                    &mut state.editor_tab,
+                    AnimEditorTab::Visibility,
+                    RichText::new("Visibility").heading(),
+                );
             });
 
             changed |= match state.editor_tab {
-                AnimEditorTab::Hierarchy => hierarchy_view(ui, anim, state),
-                AnimEditorTab::Graph => graph_view(ui, anim, state),
-                AnimEditorTab::List => list_view(ui, anim, state),
+                AnimEditorTab::Hierarchy => hierarchy_view(ui, anim, state, skel, mesh, matl),
+                AnimEditorTab::Graph => graph_view(ui, folder_name, anim, state),
+                AnimEditorTab::List => list_view(ui, folder_name, anim, state),
+                AnimEditorTab::Visibility => timeline_view(ui, anim, &mut state.timeline, mesh),
             };
         });
 
+    changed |= add_node_window(ctx, &mut state.add_node, anim, skel, mesh, matl);
+    changed |= frame_tools_window(ctx, &mut state.frame_tools, anim);
+    let selected_track = state
+        .selected_group_index
+        .zip(state.selected_node_index)
+        .zip(state.selected_track_index)
+        .map(|((g, n), t)| (g, n, t));
+    changed |= filters_window(ctx, &mut state.filters, anim, selected_track);
+    retarget_window(ctx, &mut state.retarget, skel, folder_name);
+
     EditorResponse {
         open,
         changed,
@@ -90,178 +193,163 @@ pub fn anim_editor(
     }
 }
 
-fn hierarchy_view(ui: &mut egui::Ui, anim: &mut AnimData, _state: &mut AnimEditorState) -> bool {
+fn hierarchy_view(
+    ui: &mut egui::Ui,
+    anim: &mut AnimData,
+    state: &mut AnimEditorState,
+    skel: Option<&SkelData>,
+    mesh: Option<&MeshData>,
+    matl: Option<&MatlData>,
+) -> bool {
     let mut changed = false;
+
+    let mut group_to_remove = None;
+    let mut node_to_remove = None;
+    let mut track_to_remove = None;
+    let mut node_to_copy = None;
+
     ScrollArea::vertical()
         .auto_shrink([false; 2])
         .show(ui, |ui| {
-            // TODO: Make names editable?
-            for group in &mut anim.groups {
+            for (g, group) in anim.groups.iter_mut().enumerate() {
+                let names = node_names(group.group_type, skel, mesh, matl);
+
                 CollapsingHeader::new(group.group_type.to_string())
+                    .id_salt(("anim_group", g))
                     .default_open(false)
                     .show(ui, |ui| {
-                        for node in &mut group.nodes {
+                        for (n, node) in group.nodes.iter_mut().enumerate() {
+                            if state.renaming == Some((g, n, None)) {
+                                changed |= edit_name(ui, &mut node.name, &mut state.renaming);
+                            }
+
+                            // Use a stable ID to keep the header open while renaming.
                             CollapsingHeader::new(&node.name)
+                                .id_salt(("anim_node", g, n))
                                 .default_open(true)
                                 .show(ui, |ui| {
-                                    for track in &mut node.tracks {
+                                    for (t, track) in node.tracks.iter_mut().enumerate() {
+                                        if state.renaming == Some((g, n, Some(t))) {
+                                            changed |=
+                                                edit_name(ui, &mut track.name, &mut state.renaming);
+                                        } else {
+                                            ui.add(Label::new(&track.name).sense(Sense::click()))
+                                                .context_menu(|ui| {
+                                                    if ui.button("Rename").clicked() {
+                                                        state.renaming = Some((g, n, Some(t)));
+                                                    }
+
+                                                    if ui.button("Delete").clicked() {
+                                                        track_to_remove = Some((g, n, t));
+                                                    }
+                                                });
+                                        }
                                         changed |= edit_track(ui, track);
                                     }
+                                })
+                                .header_response
+                                .context_menu(|ui| {
+                                    if ui.button("Rename").clicked() {
+                                        state.renaming = Some((g, n, None));
+                                    }
+
+                                    ui.menu_button("Copy Tracks To", |ui| {
+                                        ScrollArea::vertical().max_height(300.0).show(ui, |ui| {
+                                            for name in
+                                                names.iter().filter(|other| **other != node.name)
+                                            {
+                                                if ui.button(name).clicked() {
+                                                    node_to_copy = Some((g, n, name.clone()));
+                                                }
+                                            }
+                                        });
+                                    });
+                                    ui.separator();
+
+                                    if ui.button("Delete").clicked() {
+                                        node_to_remove = Some((g, n));
+                                    }
                                 });
                         }
+                    })
+                    .header_response
+                    .context_menu(|ui| {
+                        if ui.button("Add Node...").clicked() {
+                            state.add_node.is_open = true;
+                            state.add_node.group_type = group.group_type;
+                        }
+                        ui.separator();
+
+                        if ui.button("Delete").clicked() {
+                            group_to_remove = Some(g);
+                        }
                     });
             }
         });
-    changed
-}
-
-fn graph_view(ui: &mut egui::Ui, anim: &mut AnimData, state: &mut AnimEditorState) -> bool {
-    select_track_panel(ui, anim, state);
-
-    CentralPanel::default().show_inside(ui, |ui| {
-        let label_fmt = |name: &str, value: &PlotPoint| {
-            if name.is_empty() {
-                // Don't show values when not hovering near a line.
-                String::new()
-            } else {
-                format!("{name}\nframe = {}\nvalue = {}", value.x, value.y)
-            }
-        };
 
-        // Add a legend for labels and visibility toggles
-        let plot = Plot::new("anim_plot")
-            .allow_drag(false)
-            .allow_zoom(false)
-            .allow_scroll(false)
-            .label_formatter(label_fmt)
-            .legend(Legend::default().follow_insertion_order(true));
+    if let Some((g, n, name)) = node_to_copy
+        && let Some(group) = anim.groups.get_mut(g)
+    {
+        copy_node_tracks(group, n, &name);
+        changed = true;
+    }
 
-        let mut shapes = Vec::new();
+    let mut removed = false;
+    if let Some((g, n, t)) = track_to_remove
+        && let Some(node) = anim
+            .groups
+            .get_mut(g)
+            .and_then(|group| group.nodes.get_mut(n))
+    {
+        node.tracks.remove(t);
+        removed = true;
+    }
+    if let Some((g, n)) = node_to_remove
+        && let Some(group) = anim.groups.get_mut(g)
+    {
+        group.nodes.remove(n);
+        removed = true;
+    }
+    if let Some(g) = group_to_remove {
+        anim.groups.remove(g);
+        removed = true;
+    }
 
-        if let Some(track) = selected_track(&mut anim.groups, state) {
-            match &track.values {
-                TrackValues::Transform(values) => {
-                    let mut translation_x = Vec::new();
-                    let mut translation_y = Vec::new();
-                    let mut translation_z = Vec::new();
-
-                    let mut rotation_x = Vec::new();
-                    let mut rotation_y = Vec::new();
-                    let mut rotation_z = Vec::new();
-                    let mut rotation_w = Vec::new();
-
-                    let mut scale_x = Vec::new();
-                    let mut scale_y = Vec::new();
-                    let mut scale_z = Vec::new();
-
-                    for (i, t) in values.iter().enumerate() {
-                        translation_x.push([i as f64, t.translation.x as f64]);
-                        translation_y.push([i as f64, t.translation.y as f64]);
-                        translation_z.push([i as f64, t.translation.z as f64]);
-
-                        rotation_x.push([i as f64, t.rotation.x as f64]);
-                        rotation_y.push([i as f64, t.rotation.y as f64]);
-                        rotation_z.push([i as f64, t.rotation.z as f64]);
-                        rotation_w.push([i as f64, t.rotation.w as f64]);
-
-                        scale_x.push([i as f64, t.scale.x as f64]);
-                        scale_y.push([i as f64, t.scale.y as f64]);
-                        scale_z.push([i as f64, t.scale.z as f64]);
-                    }
-                    shapes.push(Line::new("translation.x", translation_x));
-                    shapes.push(Line::new("translation.y", translation_y));
-                    shapes.push(Line::new("translation.z", translation_z));
-
-                    shapes.push(Line::new("rotation.x", rotation_x));
-                    shapes.push(Line::new("rotation.y", rotation_y));
-                    shapes.push(Line::new("rotation.z", rotation_z));
-                    shapes.push(Line::new("rotation.w", rotation_w));
-
-                    shapes.push(Line::new("scale.x", scale_x));
-                    shapes.push(Line::new("scale.y", scale_y));
-                    shapes.push(Line::new("scale.z", scale_z));
-                }
-                TrackValues::UvTransform(values) => {
-                    let mut scale_us = Vec::new();
-                    let mut scale_vs = Vec::new();
-                    let mut rotations = Vec::new();
-                    let mut translate_us = Vec::new();
-                    let mut translate_vs = Vec::new();
-
-                    for (i, v) in values.iter().enumerate() {
-                        scale_us.push([i as f64, v.scale_u as f64]);
-                        scale_vs.push([i as f64, v.scale_v as f64]);
-
-                        rotations.push([i as f64, v.rotation as f64]);
-
-                        translate_us.push([i as f64, v.translate_u as f64]);
-                        translate_vs.push([i as f64, v.translate_v as f64]);
-                    }
-                    shapes.push(Line::new("scale_u", scale_us));
-                    shapes.push(Line::new("scale_v", scale_vs));
-
-                    shapes.push(Line::new("rotation", rotations));
-
-                    shapes.push(Line::new("translate_u", translate_us));
-                    shapes.push(Line::new("translate_v", translate_vs));
-                }
-                TrackValues::Float(values) => {
-                    let mut points = Vec::new();
-                    for (i, v) in values.iter().enumerate() {
-                        points.push([i as f64, *v as f64]);
-                    }
-                    shapes.push(Line::new("value", points));
-                }
-                TrackValues::PatternIndex(values) => {
-                    let mut points = Vec::new();
-                    for (i, v) in values.iter().enumerate() {
-                        points.push([i as f64, *v as f64]);
-                    }
-                    shapes.push(Line::new("value", points));
-                }
-                TrackValues::Boolean(values) => {
-                    let mut points = Vec::new();
-                    for (i, v) in values.iter().enumerate() {
-                        points.push([i as f64, if *v { 1.0 } else { 0.0 }]);
-                        // Each value lasts until the next frame.
-                        if i < values.len() - 1 {
-                            points.push([(i + 1) as f64, if *v { 1.0 } else { 0.0 }]);
-                        }
-                    }
-                    shapes.push(Line::new("value", points));
-                }
-                TrackValues::Vector4(values) => {
-                    let mut xs = Vec::new();
-                    let mut ys = Vec::new();
-                    let mut zs = Vec::new();
-                    let mut ws = Vec::new();
-
-                    for (i, v) in values.iter().enumerate() {
-                        xs.push([i as f64, v.x as f64]);
-                        ys.push([i as f64, v.y as f64]);
-                        zs.push([i as f64, v.z as f64]);
-                        ws.push([i as f64, v.w as f64]);
-                    }
-                    shapes.push(Line::new("x", xs));
-                    shapes.push(Line::new("y", ys));
-                    shapes.push(Line::new("z", zs));
-                    shapes.push(Line::new("w", ws));
-                }
-            }
-        }
+    if removed {
+        // Indices may no longer refer to the same items.
+        state.renaming = None;
+        state.selected_group_index = None;
+        state.selected_node_index = None;
+        state.selected_track_index = None;
+        changed = true;
+    }
 
-        plot.show(ui, |plot_ui| {
-            for shape in shapes {
-                plot_ui.line(shape);
-            }
-        });
-    });
+    changed
+}
 
-    // The graph view is readonly.
-    false
+fn edit_name(
+    ui: &mut egui::Ui,
+    name: &mut String,
+    renaming: &mut Option<(usize, usize, Option<usize>)>,
+) -> bool {
+    let response = ui.text_edit_singleline(name);
+    if response.lost_focus() {
+        *renaming = None;
+    } else if ui.memory(|m| m.focused().is_none()) {
+        // Focus the text when renaming starts.
+        response.request_focus();
+    }
+    response.changed()
 }
 
-fn select_track_panel(ui: &mut egui::Ui, anim: &mut AnimData, state: &mut AnimEditorState) {
+fn select_track_panel(
+    ui: &mut egui::Ui,
+    folder_name: &Path,
+    anim: &mut AnimData,
+    state: &mut AnimEditorState,
+) -> bool {
+    let mut action = None;
+
     SidePanel::left("anim_left_panel")
         .default_width(300.0)
         .show_inside(ui, |ui| {
@@ -289,7 +377,14 @@ fn select_track_panel(ui: &mut egui::Ui, anim: &mut AnimData, state: &mut AnimEd
                                                     node.name,
                                                     t.values.len()
                                                 ),
-                                            );
+                                            )
+                                            .context_menu(|ui| {
+                                                csv_context_menu(
+                                                    ui,
+                                                    CsvTracks::Track(g, n, 0),
+                                                    &mut action,
+                                                );
+                                            });
                                             if selected {
                                                 state.selected_group_index = Some(g);
                                                 state.selected_node_index = Some(n);
@@ -315,21 +410,49 @@ fn select_track_panel(ui: &mut egui::Ui, anim: &mut AnimData, state: &mut AnimEd
                                                                 track.name,
                                                                 track.values.len()
                                                             ),
-                                                        );
+                                                        )
+                                                        .context_menu(|ui| {
+                                                            csv_context_menu(
+                                                                ui,
+                                                                CsvTracks::Track(g, n, t),
+                                                                &mut action,
+                                                            );
+                                                        });
                                                         if selected {
                                                             state.selected_group_index = Some(g);
                                                             state.selected_node_index = Some(n);
                                                             state.selected_track_index = Some(t);
                                                         }
                                                     }
+                                                })
+                                                .header_response
+                                                .context_menu(|ui| {
+                                                    csv_context_menu(
+                                                        ui,
+                                                        CsvTracks::Node(g, n),
+                                                        &mut action,
+                                                    );
                                                 });
                                         }
                                     }
                                 }
+                            })
+                            .header_response
+                            .context_menu(|ui| {
+                                csv_context_menu(ui, CsvTracks::Group(g), &mut action);
                             });
                     }
                 });
         });
+
+    match action {
+        Some(CsvAction::Import(tracks)) => import_csv(anim, tracks, folder_name),
+        Some(CsvAction::Export(tracks)) => {
+            export_csv(anim, tracks, folder_name);
+            false
+        }
+        None => false,
+    }
 }
 
 fn selected_track<'a>(
@@ -347,7 +470,6 @@ fn selected_track<'a>(
 fn edit_track(ui: &mut egui::Ui, track: &mut ssbh_data::anim_data::TrackData) -> bool {
     let mut changed = false;
 
-    ui.label(&track.name);
     ui.indent("indent", |ui| {
         changed |= ui
             .checkbox(&mut track.compensate_scale, "Compensate Scale")
@@ -383,10 +505,13 @@ fn edit_track(ui: &mut egui::Ui, track: &mut ssbh_data::anim_data::TrackData) ->
     changed
 }
 
-fn list_view(ui: &mut egui::Ui, anim: &mut AnimData, state: &mut AnimEditorState) -> bool {
-    select_track_panel(ui, anim, state);
-
-    let mut changed = false;
+fn list_view(
+    ui: &mut egui::Ui,
+    folder_name: &Path,
+    anim: &mut AnimData,
+    state: &mut AnimEditorState,
+) -> bool {
+    let mut changed = select_track_panel(ui, folder_name, anim, state);
 
     CentralPanel::default().show_inside(ui, |ui| {
         if let Some(track) = selected_track(&mut anim.groups, state) {
@@ -0,0 +1,428 @@
+use glam::{EulerRot, Mat3, Mat4, Quat, Vec3};
+use ssbh_data::{
+    Vector3, Vector4,
+    anim_data::{GroupType, TrackData, TrackValues, Transform, TransformFlags},
+    hlpb_data::{AimConstraintData, OrientConstraintData},
+    prelude::*,
+};
+
+use super::{
+    frames::frame_count,
+    nodes::{add_track, find_track},
+};
+
+/// Evaluate the constraints in `hlpb` on every frame of `anim`
+/// and return a copy of `anim` with transform tracks for the constrained bones.
+pub fn bake_helper_bones(anim: &AnimData, skel: &SkelData, hlpb: &HlpbData) -> AnimData {
+    let bone_index = |name: &str| skel.bones.iter().position(|b| b.name == name);
+
+    let mut targets: Vec<_> = hlpb
+        .aim_constraints
+        .iter()
+        .filter_map(|c| bone_index(&c.target_bone_name1))
+        .chain(
+            hlpb.orient_constraints
+                .iter()
+                .filter_map(|c| bone_index(&c.target_bone_name)),
+        )
+        .collect();
+    targets.sort();
+    targets.dedup();
+
+    let mut values = vec![Vec::new(); targets.len()];
+    for frame in 0..frame_count(anim) {
+        let transforms = helper_bone_transforms(anim, skel, hlpb, frame);
+        for (target_values, target) in values.iter_mut().zip(&targets) {
+            let (s, r, t) = transforms[*target].to_scale_rotation_translation();
+            target_values.push(Transform {
+                scale: Vector3::new(s.x, s.y, s.z),
+                rotation: Vector4::new(r.x, r.y, r.z, r.w),
+                translation: Vector3::new(t.x, t.y, t.z),
+            });
+        }
+    }
+
+    let mut baked = anim.clone();
+    for (target, values) in targets.into_iter().zip(values) {
+        let name = &skel.bones[target].name;
+        let track = baked
+            .groups
+            .iter_mut()
+            .filter(|g| g.group_type == GroupType::Transform)
+            .flat_map(|g| &mut g.nodes)
+            .filter(|n| &n.name == name)
+            .flat_map(|n| &mut n.tracks)
+            .find(|t| t.name == "Transform");
+        match track {
+            Some(track) => track.values = TrackValues::Transform(values),
+            None => add_track(
+                &mut baked,
+                GroupType::Transform,
+                name,
+                TrackData {
+                    name: "Transform".to_owned(),
+                    compensate_scale: false,
+                    transform_flags: TransformFlags::default(),
+                    values: TrackValues::Transform(values),
+                },
+            ),
+        }
+    }
+
+    baked
+}
+
+/// The local transform of each bone at `frame` after applying the helper bone constraints.
+fn helper_bone_transforms(
+    anim: &AnimData,
+    skel: &SkelData,
+    hlpb: &HlpbData,
+    frame: usize,
+) -> Vec<Mat4> {
+    let mut transforms: Vec<_> = skel
+        .bones
+        .iter()
+        .map(
+            |b| match find_track(anim, GroupType::Transform, &b.name, "Transform") {
+                Some(TrackData {
+                    values: TrackValues::Transform(values),
+                    ..
+                }) if !values.is_empty() => {
+                    let v = &values[frame.min(values.len() - 1)];
+                    Mat4::from_scale_rotation_translation(
+                        Vec3::new(v.scale.x, v.scale.y, v.scale.z),
+                        quat(&v.rotation),
+                        Vec3::new(v.translation.x, v.translation.y, v.translation.z),
+                    )
+                }
+                _ => Mat4::from_cols_array_2d(&b.transform),
+            },
+        )
+        .collect();
+
+    // Later constraints may depend on bones moved by earlier constraints.
+    for constraint in &hlpb.aim_constraints {
+        apply_aim_constraint(skel, &mut transforms, constraint);
+    }
+    for constraint in &hlpb.orient_constraints {
+        apply_orient_constraint(skel, &mut transforms, constraint);
+    }
+
+    transforms
+}
+
+fn world_transform(skel: &SkelData, transforms: &[Mat4], index: usize) -> Mat4 {
+    let mut transform = transforms[index];
+    let mut parent = skel.bones[index].parent_index;
+    // Limit the iterations in case the skeleton has cycles.
+    for _ in 0..skel.bones.len() {
+        match parent.filter(|p| *p < transforms.len()) {
+            Some(p) => {
+                transform = transforms[p] * transform;
+                parent = skel.bones[p].parent_index;
+            }
+            None => break,
+        }
+    }
+    transform
+}
+
+fn parent_world_transform(skel: &SkelData, transforms: &[Mat4], index: usize) -> Mat4 {
+    skel.bones[index]
+        .parent_index
+        .filter(|p| *p < transforms.len())
+        .map(|p| world_transform(skel, transforms, p))
+        .unwrap_or(Mat4::IDENTITY)
+}
+
+/// Rotate the target bone by the source bone's rotation relative to the first parent.
+/// Each XYZ Euler angle is scaled by `constraint_axes` and clamped to the range in degrees.
+///
+/// This is an approximation of the in game behavior.
+/// The `unk_type` field is ignored.
+fn apply_orient_constraint(
+    skel: &SkelData,
+    transforms: &mut [Mat4],
+    constraint: &OrientConstraintData,
+) -> Option<()> {
+    let bone_index = |name: &str| skel.bones.iter().position(|b| b.name == name);
+    let source = bone_index(&constraint.source_bone_name)?;
+    let target = bone_index(&constraint.target_bone_name)?;
+    let parent1 = bone_index(&constraint.parent_bone_name1)?;
+    let parent2 = bone_index(&constraint.parent_bone_name2)?;
+
+    let world_rotation = |i| {
+        world_transform(skel, transforms, i)
+            .to_scale_rotation_translation()
+            .1
+    };
+
+    // Find the source rotation relative to the first parent.
+    let relative = world_rotation(parent1).inverse() * world_rotation(source);
+    let relative = quat(&constraint.quat1) * relative * quat(&constraint.quat2);
+
+    // Weight and limit each axis in degrees.
+    let (x, y, z) = relative.to_euler(EulerRot::XYZ);
+    let axis = |angle: f32, weight: f32, min: f32, max: f32| {
+        (angle.to_degrees() * weight).clamp(min, max).to_radians()
+    };
+    let (axes, min, max) = (
+        &constraint.constraint_axes,
+        &constraint.range_min,
+        &constraint.range_max,
+    );
+    let relative = Quat::from_euler(
+        EulerRot::XYZ,
+        axis(x, axes.x, min.x, max.x),
+        axis(y, axes.y, min.y, max.y),
+        axis(z, axes.z, min.z, max.z),
+    );
+
+    // Apply the rotation relative to the second parent.
+    let rotation = world_rotation(parent2) * relative;
+    let parent_rotation = parent_world_transform(skel, transforms, target)
+        .to_scale_rotation_translation()
+        .1;
+
+    let (s, _, t) = transforms[target].to_scale_rotation_translation();
+    transforms[target] =
+        Mat4::from_scale_rotation_translation(s, parent_rotation.inverse() * rotation, t);
+    Some(())
+}
+
+/// Rotate the target bone to point its `aim` axis towards the aim bone.
+///
+/// This is an approximation of the in game behavior.
+/// Only the first aim and target bones are used, so `aim_bone_name2` and `target_bone_name2` are ignored.
+/// The `aim_type1`, `aim_type2`, `unk1`, and `unk2` fields are also ignored,
+/// which assumes the "DEFAULT" aim type used by most fighters.
+fn apply_aim_constraint(
+    skel: &SkelData,
+    transforms: &mut [Mat4],
+    constraint: &AimConstraintData,
+) -> Option<()> {
+    let bone_index = |name: &str| skel.bones.iter().position(|b| b.name == name);
+    let aim = bone_index(&constraint.aim_bone_name1)?;
+    let target = bone_index(&constraint.target_bone_name1)?;
+
+    // Find the direction to the aim bone in the target's parent space.
+    let aim_position = world_transform(skel, transforms, aim).w_axis.truncate();
+    let aim_position = parent_world_transform(skel, transforms, target)
+        .inverse()
+        .transform_point3(aim_position);
+
+    let (s, r, t) = transforms[target].to_scale_rotation_translation();
+    let direction = (aim_position - t).try_normalize()?;
+
+    let aim_axis = Vec3::new(constraint.aim.x, constraint.aim.y, constraint.aim.z);
+    let up_axis = Vec3::new(constraint.up.x, constraint.up.y, constraint.up.z);
+    let rotation = look_rotation(aim_axis, up_axis, direction, r * up_axis)?;
+    let rotation = quat(&constraint.quat1) * rotation * quat(&constraint.quat2);
+
+    transforms[target] = Mat4::from_scale_rotation_translation(s, rotation, t);
+    Some(())
+}
+
+/// The rotation that points `aim` along `direction` while keeping `up` close to `up_direction`.
+fn look_rotation(aim: Vec3, up: Vec3, direction: Vec3, up_direction: Vec3) -> Option<Quat> {
+    let basis = |x: Vec3, up: Vec3| {
+        let z = x.cross(up).try_normalize()?;
+        Some(Mat3::from_cols(x, z.cross(x), z))
+    };
+    let from = basis(aim.try_normalize()?, up)?;
+    let to = basis(direction, up_direction)?;
+    Some(Quat::from_mat3(&(to * from.transpose())).normalize())
+}
+
+fn quat(v: &Vector4) -> Quat {
+    Quat::from_xyzw(v.x, v.y, v.z, v.w).normalize()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{bone, skel};
+    use ssbh_data::anim_data::{GroupData, NodeData};
+
+    fn transform_value(transform: Mat4) -> Transform {
+        let (s, r, t) = transform.to_scale_rotation_translation();
+        Transform {
+            scale: Vector3::new(s.x, s.y, s.z),
+            rotation: Vector4::new(r.x, r.y, r.z, r.w),
+            translation: Vector3::new(t.x, t.y, t.z),
+        }
+    }
+
+    fn baked_rotation(anim: &AnimData, name: &str, frame: usize) -> Quat {
+        match find_track(anim, GroupType::Transform, name, "Transform") {
+            Some(TrackData {
+                values: TrackValues::Transform(values),
+                ..
+            }) => quat(&values[frame].rotation),
+            _ => panic!("missing track for {name}"),
+        }
+    }
+
+    fn hlpb(
+        aim_constraints: Vec<AimConstraintData>,
+        orient_constraints: Vec<OrientConstraintData>,
+    ) -> HlpbData {
+        HlpbData {
+            major_version: 1,
+            minor_version: 0,
+            aim_constraints,
+            orient_constraints,
+        }
+    }
+
+    #[test]
+    fn bake_orient_constraint() {
+        let skel = skel(vec![
+            bone("Trans", None, Mat4::IDENTITY),
+            bone("ArmL", Some(0), Mat4::IDENTITY),
+            bone("H_ArmL", Some(0), Mat4::from_translation(Vec3::Y)),
+        ]);
+        let hlpb = hlpb(
+            Vec::new(),
+            vec![OrientConstraintData {
+                name: "nuHelperBoneRotateInterp1".to_owned(),
+                parent_bone_name1: "Trans".to_owned(),
+                parent_bone_name2: "Trans".to_owned(),
+                source_bone_name: "ArmL".to_owned(),
+                target_bone_name: "H_ArmL".to_owned(),
+                unk_type: 1,
+                constraint_axes: Vector3::new(1.0, 1.0, 0.5),
+                quat1: Vector4::new(0.0, 0.0, 0.0, 1.0),
+                quat2: Vector4::new(0.0, 0.0, 0.0, 1.0),
+                range_min: Vector3::new(-180.0, -180.0, -180.0),
+                range_max: Vector3::new(180.0, 180.0, 180.0),
+            }],
+        );
+        let anim = AnimData {
+            major_version: 2,
+            minor_version: 0,
+            final_frame_index: 1.0,
+            groups: vec![GroupData {
+                group_type: GroupType::Transform,
+                nodes: vec![NodeData {
+                    name: "ArmL".to_owned(),
+                    tracks: vec![TrackData {
+                        name: "Transform".to_owned(),
+                        compensate_scale: false,
+                        transform_flags: TransformFlags::default(),
+                        values: TrackValues::Transform(vec![
+                            transform_value(Mat4::IDENTITY),
+                            transform_value(Mat4::from_rotation_z(1.0)),
+                        ]),
+                    }],
+                }],
+            }],
+        };
+
+        let baked = bake_helper_bones(&anim, &skel, &hlpb);
+
+        assert!(baked_rotation(&baked, "H_ArmL", 0).abs_diff_eq(Quat::IDENTITY, 1e-5));
+        assert!(baked_rotation(&baked, "H_ArmL", 1).abs_diff_eq(Quat::from_rotation_z(0.5), 1e-5));
+        // The original anim should not be modified.
+        assert_eq!(1, anim.groups[0].nodes.len());
+    }
+
+    #[test]
+    fn bake_orient_constraint_range_and_parent() {
+        let skel = skel(vec![
+            bone("Trans", None, Mat4::IDENTITY),
+            bone("ArmL", Some(0), Mat4::IDENTITY),
+            bone(
+                "ShoulderL",
+                Some(0),
+                Mat4::from_rotation_y(90f32.to_radians()),
+            ),
+            bone("H_ArmL", Some(0), Mat4::IDENTITY),
+        ]);
+        let hlpb = hlpb(
+            Vec::new(),
+            vec![OrientConstraintData {
+                name: "nuHelperBoneRotateInterp1".to_owned(),
+                parent_bone_name1: "Trans".to_owned(),
+                parent_bone_name2: "ShoulderL".to_owned(),
+                source_bone_name: "ArmL".to_owned(),
+                target_bone_name: "H_ArmL".to_owned(),
+                unk_type: 1,
+                constraint_axes: Vector3::new(1.0, 1.0, 1.0),
+                quat1: Vector4::new(0.0, 0.0, 0.0, 1.0),
+                quat2: Vector4::new(0.0, 0.0, 0.0, 1.0),
+                range_min: Vector3::new(-180.0, -180.0, -45.0),
+                range_max: Vector3::new(180.0, 180.0, 45.0),
+            }],
+        );
+        let anim = AnimData {
+            major_version: 2,
+            minor_version: 0,
+            final_frame_index: 0.0,
+            groups: vec![GroupData {
+                group_type: GroupType::Transform,
+                nodes: vec![NodeData {
+                    name: "ArmL".to_owned(),
+                    tracks: vec![TrackData {
+                        name: "Transform".to_owned(),
+                        compensate_scale: false,
+                        transform_flags: TransformFlags::default(),
+                        values: TrackValues::Transform(vec![transform_value(
+                            Mat4::from_rotation_z(90f32.to_radians()),
+                        )]),
+                    }],
+                }],
+            }],
+        };
+
+        let baked = bake_helper_bones(&anim, &skel, &hlpb);
+
+        // The clamped rotation is applied relative to the second parent.
+        let expected =
+            Quat::from_rotation_y(90f32.to_radians()) * Quat::from_rotation_z(45f32.to_radians());
+        assert!(baked_rotation(&baked, "H_ArmL", 0).abs_diff_eq(expected, 1e-5));
+    }
+
+    #[test]
+    fn bake_aim_constraint() {
+        let skel = skel(vec![
+            bone("Trans", None, Mat4::IDENTITY),
+            bone("H_Aim", Some(0), Mat4::IDENTITY),
+            bone(
+                "Target",
+                Some(0),
+                Mat4::from_translation(Vec3::new(0.0, 0.0, 2.0)),
+            ),
+        ]);
+        let hlpb = hlpb(
+            vec![AimConstraintData {
+                name: "nuHelperBoneRotateAim1".to_owned(),
+                aim_bone_name1: "Target".to_owned(),
+                aim_bone_name2: "Target".to_owned(),
+                aim_type1: "DEFAULT".to_owned(),
+                aim_type2: "DEFAULT".to_owned(),
+                target_bone_name1: "H_Aim".to_owned(),
+                target_bone_name2: "H_Aim".to_owned(),
+                unk1: 0,
+                unk2: 1,
+                aim: Vector3::new(1.0, 0.0, 0.0),
+                up: Vector3::new(0.0, 1.0, 0.0),
+                quat1: Vector4::new(0.0, 0.0, 0.0, 1.0),
+                quat2: Vector4::new(0.0, 0.0, 0.0, 1.0),
+            }],
+            Vec::new(),
+        );
+        let anim = AnimData {
+            major_version: 2,
+            minor_version: 0,
+            final_frame_index: 0.0,
+            groups: Vec::new(),
+        };
+
+        let baked = bake_helper_bones(&anim, &skel, &hlpb);
+
+        let rotation = baked_rotation(&baked, "H_Aim", 0);
+        assert!((rotation * Vec3::X).abs_diff_eq(Vec3::Z, 1e-5));
+        assert!((rotation * Vec3::Y).abs_diff_eq(Vec3::Y, 1e-5));
+    }
+}
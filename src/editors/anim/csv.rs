@@ -0,0 +1,611 @@
+use std::path::Path;
+
+use log::error;
+use rfd::FileDialog;
+use ssbh_data::{
+    Vector3, Vector4,
+    anim_data::{GroupData, TrackValues, Transform, UvTransform},
+    prelude::*,
+};
+use thiserror::Error;
+
+/// The tracks to import or export as CSV.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CsvTracks {
+    Group(usize),
+    Node(usize, usize),
+    Track(usize, usize, usize),
+}
+
+pub enum CsvAction {
+    Import(CsvTracks),
+    Export(CsvTracks),
+}
+
+#[derive(Debug, PartialEq, Eq, Error)]
+pub enum CsvError {
+    #[error("The CSV file does not contain a header row.")]
+    MissingHeader,
+
+    #[error("Expected the first column to be \"frame\" but found {found:?}.")]
+    MissingFrameColumn { found: String },
+
+    #[error("Expected {expected} columns for {track} with {kind} values but found {found}.")]
+    ColumnCount {
+        track: String,
+        kind: &'static str,
+        expected: usize,
+        found: usize,
+    },
+
+    #[error("Missing column {column:?}.")]
+    MissingColumn { column: String },
+
+    #[error("Column {column:?} does not match any track.")]
+    UnknownColumn { column: String },
+
+    #[error("Line {line} has {found} columns but the header has {expected}.")]
+    RowLength {
+        line: usize,
+        expected: usize,
+        found: usize,
+    },
+
+    #[error("Invalid value {value:?} for column {column:?} on line {line}.")]
+    InvalidValue {
+        line: usize,
+        column: String,
+        value: String,
+    },
+
+    #[error("The CSV file does not contain any values for {track}.")]
+    NoValues { track: String },
+}
+
+pub fn csv_context_menu(ui: &mut egui::Ui, tracks: CsvTracks, action: &mut Option<CsvAction>) {
+    if ui.button("Import CSV...").clicked() {
+        *action = Some(CsvAction::Import(tracks));
+    }
+
+    if ui.button("Export CSV...").clicked() {
+        *action = Some(CsvAction::Export(tracks));
+    }
+}
+
+pub fn export_csv(anim: &AnimData, tracks: CsvTracks, folder_name: &Path) {
+    let Some(file_name) = csv_file_name(anim, tracks) else {
+        return;
+    };
+
+    if let Some(file) = FileDialog::new()
+        .set_directory(folder_name)
+        .set_file_name(file_name)
+        .add_filter("CSV", &["csv"])
+        .save_file()
+        && let Err(e) = std::fs::write(&file, anim_to_csv(anim, tracks))
+    {
+        error!("Failed to save {file:?}: {e}");
+    }
+}
+
+pub fn import_csv(anim: &mut AnimData, tracks: CsvTracks, folder_name: &Path) -> bool {
+    if let Some(file) = FileDialog::new()
+        .set_directory(folder_name)
+        .add_filter("CSV", &["csv"])
+        .pick_file()
+    {
+        match std::fs::read_to_string(&file) {
+            Ok(csv) => match anim_from_csv(anim, tracks, &csv) {
+                Ok(()) => true,
+                Err(e) => {
+                    error!("Failed to import {file:?}: {e}");
+                    false
+                }
+            },
+            Err(e) => {
+                error!("Failed to read {file:?}: {e}");
+                false
+            }
+        }
+    } else {
+        false
+    }
+}
+
+fn csv_file_name(anim: &AnimData, tracks: CsvTracks) -> Option<String> {
+    match tracks {
+        CsvTracks::Group(g) => Some(format!("{}.csv", anim.groups.get(g)?.group_type)),
+        CsvTracks::Node(g, n) => Some(format!("{}.csv", anim.groups.get(g)?.nodes.get(n)?.name)),
+        CsvTracks::Track(g, n, t) => {
+            let node = anim.groups.get(g)?.nodes.get(n)?;
+            Some(format!("{}_{}.csv", node.name, node.tracks.get(t)?.name))
+        }
+    }
+}
+
+/// The node index, track index, and column prefix for each track.
+/// Exporting more than one track prefixes columns with the node and track name.
+fn csv_tracks(group: &GroupData, tracks: CsvTracks) -> Vec<(usize, usize, String)> {
+    let prefixed = move |n: usize| {
+        group.nodes[n]
+            .tracks
+            .iter()
+            .enumerate()
+            .map(move |(t, track)| (n, t, format!("{}/{}/", group.nodes[n].name, track.name)))
+    };
+
+    match tracks {
+        CsvTracks::Group(_) => (0..group.nodes.len()).flat_map(prefixed).collect(),
+        CsvTracks::Node(_, n) if n < group.nodes.len() => prefixed(n).collect(),
+        CsvTracks::Track(_, n, t)
+            if group.nodes.get(n).is_some_and(|node| t < node.tracks.len()) =>
+        {
+            vec![(n, t, String::new())]
+        }
+        _ => Vec::new(),
+    }
+}
+
+fn group_index(tracks: CsvTracks) -> usize {
+    match tracks {
+        CsvTracks::Group(g) | CsvTracks::Node(g, _) | CsvTracks::Track(g, _, _) => g,
+    }
+}
+
+/// Convert the values to CSV with one row per frame and one column per component.
+/// Tracks with fewer frames leave the remaining cells empty.
+pub fn anim_to_csv(anim: &AnimData, tracks: CsvTracks) -> String {
+    let Some(group) = anim.groups.get(group_index(tracks)) else {
+        return String::new();
+    };
+    let tracks = csv_tracks(group, tracks);
+
+    let mut header = vec!["frame".to_owned()];
+    for (n, t, prefix) in &tracks {
+        let values = &group.nodes[*n].tracks[*t].values;
+        header.extend(
+            component_names(values)
+                .iter()
+                .map(|c| quote_field(&format!("{prefix}{c}"))),
+        );
+    }
+
+    let mut csv = header.join(",");
+    csv.push('\n');
+
+    let frame_count = tracks
+        .iter()
+        .map(|(n, t, _)| group.nodes[*n].tracks[*t].values.len())
+        .max()
+        .unwrap_or_default();
+    for frame in 0..frame_count {
+        let mut row = vec![frame.to_string()];
+        for (n, t, _) in &tracks {
+            let values = &group.nodes[*n].tracks[*t].values;
+            match frame_components(values, frame) {
+                Some(components) => row.extend(components),
+                None => row.extend(component_names(values).iter().map(|_| String::new())),
+            }
+        }
+        csv.push_str(&row.join(","));
+        csv.push('\n');
+    }
+
+    csv
+}
+
+/// Replace the values with the rows from `csv`.
+/// Columns are matched by name, and the frame column is ignored.
+/// No values are changed if any of the tracks fail to parse.
+pub fn anim_from_csv(anim: &mut AnimData, tracks: CsvTracks, csv: &str) -> Result<(), CsvError> {
+    let Some(group) = anim.groups.get_mut(group_index(tracks)) else {
+        return Ok(());
+    };
+    let is_single_track = matches!(tracks, CsvTracks::Track(..));
+    let tracks = csv_tracks(group, tracks);
+
+    // Keep the original line numbers for error messages.
+    let mut lines = csv
+        .lines()
+        .enumerate()
+        .map(|(i, l)| (i + 1, l.trim()))
+        .filter(|(_, l)| !l.is_empty());
+    let (_, header) = lines.next().ok_or(CsvError::MissingHeader)?;
+    let header = split_fields(header);
+    if header[0] != "frame" {
+        return Err(CsvError::MissingFrameColumn {
+            found: header[0].clone(),
+        });
+    }
+
+    let rows = lines
+        .map(|(line, l)| {
+            let row = split_fields(l);
+            if row.len() == header.len() {
+                Ok((line, row))
+            } else {
+                Err(CsvError::RowLength {
+                    line,
+                    expected: header.len(),
+                    found: row.len(),
+                })
+            }
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if !is_single_track
+        && let Some(column) = header
+            .iter()
+            .skip(1)
+            .find(|c| !tracks.iter().any(|(_, _, prefix)| c.starts_with(prefix)))
+    {
+        return Err(CsvError::UnknownColumn {
+            column: column.to_string(),
+        });
+    }
+
+    let mut new_values = Vec::new();
+    for (n, t, prefix) in &tracks {
+        let track = &group.nodes[*n].tracks[*t];
+        let track_name = format!("{}/{}", group.nodes[*n].name, track.name);
+
+        let found = header
+            .iter()
+            .skip(1)
+            .filter(|c| c.starts_with(prefix.as_str()))
+            .count();
+        if found == 0 && !is_single_track {
+            // Allow importing only some of the tracks.
+            continue;
+        }
+
+        let names = component_names(&track.values);
+        if found != names.len() {
+            return Err(CsvError::ColumnCount {
+                track: track_name,
+                kind: values_kind(&track.values),
+                expected: names.len(),
+                found,
+            });
+        }
+
+        let columns = names
+            .iter()
+            .map(|name| {
+                let column = format!("{prefix}{name}");
+                header
+                    .iter()
+                    .position(|c| *c == column)
+                    .ok_or(CsvError::MissingColumn { column })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // Tracks with fewer frames end with empty cells.
+        let track_rows: Vec<_> = rows
+            .iter()
+            .map(|(line, row)| {
+                let cells: Vec<_> = columns.iter().map(|c| row[*c].as_str()).collect();
+                (*line, cells)
+            })
+            .collect();
+        let row_count = track_rows
+            .iter()
+            .rposition(|(_, cells)| cells.iter().any(|c| !c.is_empty()))
+            .map(|i| i + 1)
+            .ok_or_else(|| CsvError::NoValues {
+                track: track_name.clone(),
+            })?;
+
+        let values = parse_values(&track.values, &track_rows[..row_count], prefix)?;
+        new_values.push((*n, *t, values));
+    }
+
+    let mut final_frame_index = anim.final_frame_index;
+    for (n, t, values) in new_values {
+        final_frame_index = final_frame_index.max(values.len() as f32 - 1.0);
+        group.nodes[n].tracks[t].values = values;
+    }
+    anim.final_frame_index = final_frame_index;
+
+    Ok(())
+}
+
+/// Quote `field` and escape any quotes since names may contain commas.
+fn quote_field(field: &str) -> String {
+    format!("\"{}\"", field.replace('"', "\"\""))
+}
+
+/// Split a line into trimmed fields with support for quoted fields containing commas.
+fn split_fields(line: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut is_quoted = false;
+
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if is_quoted && chars.peek() == Some(&'"') => {
+                // Escaped quotes use two quote characters.
+                field.push('"');
+                chars.next();
+            }
+            '"' => is_quoted = !is_quoted,
+            ',' if !is_quoted => fields.push(std::mem::take(&mut field).trim().to_owned()),
+            _ => field.push(c),
+        }
+    }
+    fields.push(field.trim().to_owned());
+    fields
+}
+
+fn component_names(values: &TrackValues) -> &'static [&'static str] {
+    match values {
+        TrackValues::Transform(_) => &[
+            "scale.x",
+            "scale.y",
+            "scale.z",
+            "rotation.x",
+            "rotation.y",
+            "rotation.z",
+            "rotation.w",
+            "translation.x",
+            "translation.y",
+            "translation.z",
+        ],
+        TrackValues::UvTransform(_) => &[
+            "scale_u",
+            "scale_v",
+            "rotation",
+            "translate_u",
+            "translate_v",
+        ],
+        TrackValues::Float(_) | TrackValues::PatternIndex(_) | TrackValues::Boolean(_) => {
+            &["value"]
+        }
+        TrackValues::Vector4(_) => &["x", "y", "z", "w"],
+    }
+}
+
+fn values_kind(values: &TrackValues) -> &'static str {
+    match values {
+        TrackValues::Transform(_) => "Transform",
+        TrackValues::UvTransform(_) => "UvTransform",
+        TrackValues::Float(_) => "Float",
+        TrackValues::PatternIndex(_) => "PatternIndex",
+        TrackValues::Boolean(_) => "Boolean",
+        TrackValues::Vector4(_) => "Vector4",
+    }
+}
+
+fn frame_components(values: &TrackValues, frame: usize) -> Option<Vec<String>> {
+    let components = match values {
+        TrackValues::Transform(v) => {
+            let v = v.get(frame)?;
+            [
+                v.scale.x,
+                v.scale.y,
+                v.scale.z,
+                v.rotation.x,
+                v.rotation.y,
+                v.rotation.z,
+                v.rotation.w,
+                v.translation.x,
+                v.translation.y,
+                v.translation.z,
+            ]
+            .iter()
+            .map(f32::to_string)
+            .collect()
+        }
+        TrackValues::UvTransform(v) => {
+            let v = v.get(frame)?;
+            [
+                v.scale_u,
+                v.scale_v,
+                v.rotation,
+                v.translate_u,
+                v.translate_v,
+            ]
+            .iter()
+            .map(f32::to_string)
+            .collect()
+        }
+        TrackValues::Float(v) => vec![v.get(frame)?.to_string()],
+        TrackValues::PatternIndex(v) => vec![v.get(frame)?.to_string()],
+        TrackValues::Boolean(v) => vec![v.get(frame)?.to_string()],
+        TrackValues::Vector4(v) => {
+            let v = v.get(frame)?;
+            [v.x, v.y, v.z, v.w].iter().map(f32::to_string).collect()
+        }
+    };
+    Some(components)
+}
+
+fn parse_values(
+    values: &TrackValues,
+    rows: &[(usize, Vec<&str>)],
+    prefix: &str,
+) -> Result<TrackValues, CsvError> {
+    let names = component_names(values);
+    let parse = |line: usize, cells: &[&str], i: usize| -> Result<f32, CsvError> {
+        cells[i].parse().map_err(|_| CsvError::InvalidValue {
+            line,
+            column: format!("{prefix}{}", names[i]),
+            value: cells[i].to_owned(),
+        })
+    };
+
+    Ok(match values {
+        TrackValues::Transform(_) => TrackValues::Transform(
+            rows.iter()
+                .map(|(line, c)| {
+                    let p = |i| parse(*line, c, i);
+                    Ok(Transform {
+                        scale: Vector3::new(p(0)?, p(1)?, p(2)?),
+                        rotation: Vector4::new(p(3)?, p(4)?, p(5)?, p(6)?),
+                        translation: Vector3::new(p(7)?, p(8)?, p(9)?),
+                    })
+                })
+                .collect::<Result<_, _>>()?,
+        ),
+        TrackValues::UvTransform(_) => TrackValues::UvTransform(
+            rows.iter()
+                .map(|(line, c)| {
+                    let p = |i| parse(*line, c, i);
+                    Ok(UvTransform {
+                        scale_u: p(0)?,
+                        scale_v: p(1)?,
+                        rotation: p(2)?,
+                        translate_u: p(3)?,
+                        translate_v: p(4)?,
+                    })
+                })
+                .collect::<Result<_, _>>()?,
+        ),
+        TrackValues::Float(_) => TrackValues::Float(
+            rows.iter()
+                .map(|(line, c)| parse(*line, c, 0))
+                .collect::<Result<_, _>>()?,
+        ),
+        TrackValues::PatternIndex(_) => TrackValues::PatternIndex(
+            rows.iter()
+                .map(|(line, c)| {
+                    c[0].parse().map_err(|_| CsvError::InvalidValue {
+                        line: *line,
+                        column: format!("{prefix}{}", names[0]),
+                        value: c[0].to_owned(),
+                    })
+                })
+                .collect::<Result<_, _>>()?,
+        ),
+        TrackValues::Boolean(_) => TrackValues::Boolean(
+            rows.iter()
+                .map(|(line, c)| match c[0].to_ascii_lowercase().as_str() {
+                    "true" | "1" => Ok(true),
+                    "false" | "0" => Ok(false),
+                    _ => Err(CsvError::InvalidValue {
+                        line: *line,
+                        column: format!("{prefix}{}", names[0]),
+                        value: c[0].to_owned(),
+                    }),
+                })
+                .collect::<Result<_, _>>()?,
+        ),
+        TrackValues::Vector4(_) => TrackValues::Vector4(
+            rows.iter()
+                .map(|(line, c)| {
+                    let p = |i| parse(*line, c, i);
+                    Ok(Vector4::new(p(0)?, p(1)?, p(2)?, p(3)?))
+                })
+                .collect::<Result<_, _>>()?,
+        ),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ssbh_data::anim_data::{GroupType, NodeData, TrackData, TransformFlags};
+
+    fn track(name: &str, values: TrackValues) -> TrackData {
+        TrackData {
+            name: name.to_owned(),
+            compensate_scale: false,
+            transform_flags: TransformFlags::default(),
+            values,
+        }
+    }
+
+    fn anim() -> AnimData {
+        AnimData {
+            major_version: 2,
+            minor_version: 0,
+            final_frame_index: 1.0,
+            groups: vec![GroupData {
+                group_type: GroupType::Material,
+                nodes: vec![NodeData {
+                    name: "a".to_owned(),
+                    tracks: vec![
+                        track("CustomFloat0", TrackValues::Float(vec![0.5, 1.0])),
+                        track("CustomBoolean0", TrackValues::Boolean(vec![true])),
+                    ],
+                }],
+            }],
+        }
+    }
+
+    #[test]
+    fn csv_export_import_node() {
+        let mut anim = anim();
+
+        let csv = anim_to_csv(&anim, CsvTracks::Node(0, 0));
+        assert_eq!(
+            "frame,\"a/CustomFloat0/value\",\"a/CustomBoolean0/value\"\n0,0.5,true\n1,1,\n",
+            csv
+        );
+
+        let csv = "frame, \"a/CustomFloat0/value\",a/CustomBoolean0/value\n0,2,0\n1,3,1\n2,4,\n";
+        anim_from_csv(&mut anim, CsvTracks::Node(0, 0), csv).unwrap();
+        let tracks = &anim.groups[0].nodes[0].tracks;
+        assert_eq!(TrackValues::Float(vec![2.0, 3.0, 4.0]), tracks[0].values);
+        assert_eq!(TrackValues::Boolean(vec![false, true]), tracks[1].values);
+        assert_eq!(2.0, anim.final_frame_index);
+    }
+
+    #[test]
+    fn csv_import_invalid_track() {
+        let mut anim = anim();
+
+        assert_eq!(
+            Err(CsvError::ColumnCount {
+                track: "a/CustomFloat0".to_owned(),
+                kind: "Float",
+                expected: 1,
+                found: 4
+            }),
+            anim_from_csv(
+                &mut anim,
+                CsvTracks::Track(0, 0, 0),
+                "frame,x,y,z,w\n0,1,2,3,4"
+            )
+        );
+        assert_eq!(
+            Err(CsvError::InvalidValue {
+                line: 3,
+                column: "value".to_owned(),
+                value: "a".to_owned()
+            }),
+            anim_from_csv(
+                &mut anim,
+                CsvTracks::Track(0, 0, 0),
+                "frame,value\n0,1\n1,a"
+            )
+        );
+        assert_eq!(
+            Err(CsvError::RowLength {
+                line: 2,
+                expected: 2,
+                found: 3
+            }),
+            anim_from_csv(&mut anim, CsvTracks::Track(0, 0, 0), "frame,value\n0,1,2")
+        );
+        assert_eq!(
+            Err(CsvError::MissingFrameColumn {
+                found: "value".to_owned()
+            }),
+            anim_from_csv(&mut anim, CsvTracks::Track(0, 0, 0), "value\n1")
+        );
+        assert_eq!(
+            TrackValues::Float(vec![0.5, 1.0]),
+            anim.groups[0].nodes[0].tracks[0].values
+        );
+    }
+
+    #[test]
+    fn csv_split_quoted_fields() {
+        assert_eq!(
+            vec!["frame", "a,b/value", "c\"d", "1"],
+            split_fields("frame, \"a,b/value\",\"c\"\"d\",1")
+        );
+    }
+}
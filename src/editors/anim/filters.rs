@@ -0,0 +1,554 @@
+use egui::{Button, ComboBox, DragValue, Grid};
+use glam::{EulerRot, Quat, Vec3};
+use ssbh_data::{
+    Vector3, Vector4,
+    anim_data::{TrackValues, Transform, UvTransform},
+    prelude::*,
+};
+
+use crate::app::{CurveFilter, CurveFiltersState};
+
+pub fn filters_window(
+    ctx: &egui::Context,
+    state: &mut CurveFiltersState,
+    anim: &mut AnimData,
+    selected_track: Option<(usize, usize, usize)>,
+) -> bool {
+    let mut changed = false;
+
+    let mut open = state.is_open;
+    egui::Window::new("Curve Filters")
+        .open(&mut open)
+        .resizable(false)
+        .show(ctx, |ui| {
+            Grid::new("anim_curve_filters_grid").show(ui, |ui| {
+                ui.label("Filter");
+                ComboBox::from_id_salt("anim_curve_filter")
+                    .width(200.0)
+                    .selected_text(filter_name(state.filter))
+                    .show_ui(ui, |ui| {
+                        for filter in [
+                            CurveFilter::QuaternionContinuity,
+                            CurveFilter::EulerFilter,
+                            CurveFilter::Gaussian,
+                            CurveFilter::Butterworth,
+                            CurveFilter::KeyframeReduction,
+                        ] {
+                            ui.selectable_value(&mut state.filter, filter, filter_name(filter));
+                        }
+                    });
+                ui.end_row();
+
+                match state.filter {
+                    CurveFilter::QuaternionContinuity | CurveFilter::EulerFilter => (),
+                    CurveFilter::Gaussian => {
+                        ui.label("Sigma (frames)");
+                        ui.add(
+                            DragValue::new(&mut state.sigma)
+                                .range(0.1..=30.0)
+                                .speed(0.1),
+                        );
+                        ui.end_row();
+                    }
+                    CurveFilter::Butterworth => {
+                        ui.label("Cutoff (cycles per frame)");
+                        ui.add(
+                            DragValue::new(&mut state.cutoff)
+                                .range(0.01..=0.49)
+                                .speed(0.005),
+                        );
+                        ui.end_row();
+                    }
+                    CurveFilter::KeyframeReduction => {
+                        ui.label("Tolerance");
+                        ui.add(
+                            DragValue::new(&mut state.tolerance)
+                                .range(0.0..=f32::MAX)
+                                .speed(0.001),
+                        );
+                        ui.end_row();
+                    }
+                }
+
+                ui.label("Tracks");
+                ui.horizontal(|ui| {
+                    ui.radio_value(&mut state.all_tracks, false, "Selected Track");
+                    ui.radio_value(&mut state.all_tracks, true, "All Tracks");
+                });
+                ui.end_row();
+            });
+            ui.label(filter_description(state.filter));
+
+            // Counting keyframes for every track each frame would be too slow.
+            if state.filter == CurveFilter::KeyframeReduction
+                && let Some(values) = track_values(anim, false, selected_track).next()
+            {
+                ui.label(format!(
+                    "Selected Track Keyframes: {} of {}",
+                    keyframe_count(values, state.tolerance),
+                    values.len()
+                ));
+            }
+
+            ui.checkbox(&mut state.preview, "Preview in Graph")
+                .on_hover_text("Show the filtered values for the selected track in the Graph tab.");
+            ui.separator();
+
+            let has_tracks = state.all_tracks || selected_track.is_some();
+            if ui.add_enabled(has_tracks, Button::new("Apply")).clicked() {
+                for values in track_values_mut(anim, state.all_tracks, selected_track) {
+                    apply_filter(values, state);
+                }
+                changed = true;
+            }
+        });
+    state.is_open = open;
+
+    changed
+}
+
+fn filter_name(filter: CurveFilter) -> &'static str {
+    match filter {
+        CurveFilter::QuaternionContinuity => "Quaternion Continuity",
+        CurveFilter::EulerFilter => "Euler Filter",
+        CurveFilter::Gaussian => "Gaussian Smoothing",
+        CurveFilter::Butterworth => "Butterworth Smoothing",
+        CurveFilter::KeyframeReduction => "Keyframe Reduction",
+    }
+}
+
+fn filter_description(filter: CurveFilter) -> &'static str {
+    match filter {
+        CurveFilter::QuaternionContinuity => {
+            "Flip rotations to the same hemisphere as the previous frame to avoid sign flips."
+        }
+        CurveFilter::EulerFilter => {
+            "Unwrap the XYZ Euler angles of rotations to avoid jumps of more than 180 degrees."
+        }
+        CurveFilter::Gaussian => "Average each frame with its neighbors weighted by distance.",
+        CurveFilter::Butterworth => {
+            "Remove frequencies above the cutoff using a zero phase Butterworth low pass filter."
+        }
+        CurveFilter::KeyframeReduction => {
+            "Replace values with linear segments between the fewest keyframes within the tolerance."
+        }
+    }
+}
+
+fn track_values(
+    anim: &AnimData,
+    all_tracks: bool,
+    selected_track: Option<(usize, usize, usize)>,
+) -> impl Iterator<Item = &TrackValues> {
+    anim.groups
+        .iter()
+        .enumerate()
+        .flat_map(|(g, group)| {
+            group
+                .nodes
+                .iter()
+                .enumerate()
+                .map(move |(n, node)| (g, n, node))
+        })
+        .flat_map(|(g, n, node)| {
+            node.tracks
+                .iter()
+                .enumerate()
+                .map(move |(t, track)| ((g, n, t), &track.values))
+        })
+        .filter(move |(index, _)| all_tracks || Some(*index) == selected_track)
+        .map(|(_, values)| values)
+}
+
+fn track_values_mut(
+    anim: &mut AnimData,
+    all_tracks: bool,
+    selected_track: Option<(usize, usize, usize)>,
+) -> impl Iterator<Item = &mut TrackValues> {
+    anim.groups
+        .iter_mut()
+        .enumerate()
+        .flat_map(|(g, group)| {
+            group
+                .nodes
+                .iter_mut()
+                .enumerate()
+                .map(move |(n, node)| (g, n, node))
+        })
+        .flat_map(|(g, n, node)| {
+            node.tracks
+                .iter_mut()
+                .enumerate()
+                .map(move |(t, track)| ((g, n, t), &mut track.values))
+        })
+        .filter(move |(index, _)| all_tracks || Some(*index) == selected_track)
+        .map(|(_, values)| values)
+}
+
+/// Apply the selected filter from `settings` to `values`.
+/// Boolean and pattern index values are not modified.
+pub fn apply_filter(values: &mut TrackValues, settings: &CurveFiltersState) {
+    match settings.filter {
+        CurveFilter::QuaternionContinuity => {
+            if let TrackValues::Transform(values) = values {
+                quaternion_continuity(values);
+            }
+        }
+        CurveFilter::EulerFilter => {
+            if let TrackValues::Transform(values) = values {
+                euler_filter(values);
+            }
+        }
+        CurveFilter::Gaussian => {
+            filter_components(values, |c| gaussian_smooth(c, settings.sigma));
+        }
+        CurveFilter::Butterworth => {
+            filter_components(values, |c| butterworth_smooth(c, settings.cutoff));
+        }
+        CurveFilter::KeyframeReduction => {
+            if let Some(mut components) = components(values) {
+                let keys = linear_keys(&components, settings.tolerance);
+                for c in &mut components {
+                    *c = linear_segments(c, &keys);
+                }
+                set_components(values, &components);
+            }
+        }
+    }
+}
+
+/// The number of keyframes needed to keep `values` within `tolerance` using linear segments.
+pub fn keyframe_count(values: &TrackValues, tolerance: f32) -> usize {
+    match components(values) {
+        Some(components) => linear_keys(&components, tolerance).len(),
+        None => values.len(),
+    }
+}
+
+fn quaternion_continuity(values: &mut [Transform]) {
+    for i in 1..values.len() {
+        let previous = quat(&values[i - 1].rotation);
+        let current = quat(&values[i].rotation);
+        // q and -q represent the same rotation.
+        if previous.dot(current) < 0.0 {
+            values[i].rotation = vector4(-current);
+        }
+    }
+}
+
+fn euler_filter(values: &mut [Transform]) {
+    let mut previous: Option<Vec3> = None;
+    for value in values {
+        let (x, y, z) = quat(&value.rotation).to_euler(EulerRot::XYZ);
+        let euler = Vec3::new(x, y, z);
+
+        let euler = match previous {
+            Some(previous) => {
+                // Both sets of angles produce the same rotation.
+                let flipped = Vec3::new(
+                    x + std::f32::consts::PI,
+                    std::f32::consts::PI - y,
+                    z + std::f32::consts::PI,
+                );
+                let a = unwrap_angles(euler, previous);
+                let b = unwrap_angles(flipped, previous);
+                if a.distance_squared(previous) <= b.distance_squared(previous) {
+                    a
+                } else {
+                    b
+                }
+            }
+            None => euler,
+        };
+
+        value.rotation = vector4(Quat::from_euler(EulerRot::XYZ, euler.x, euler.y, euler.z));
+        previous = Some(euler);
+    }
+}
+
+/// Add multiples of a full turn to each angle to be closest to `previous`.
+fn unwrap_angles(angles: Vec3, previous: Vec3) -> Vec3 {
+    let tau = std::f32::consts::TAU;
+    angles + ((previous - angles) / tau).round() * tau
+}
+
+fn gaussian_smooth(values: &[f32], sigma: f32) -> Vec<f32> {
+    let radius = (sigma * 3.0).ceil() as isize;
+    let weights: Vec<_> = (-radius..=radius)
+        .map(|i| (-(i * i) as f32 / (2.0 * sigma * sigma)).exp())
+        .collect();
+
+    (0..values.len() as isize)
+        .map(|f| {
+            let (sum, total) = (-radius..=radius).zip(&weights).fold(
+                (0.0, 0.0),
+                |(sum, total), (offset, weight)| {
+                    // Repeat the first and last values past the ends.
+                    let i = (f + offset).clamp(0, values.len() as isize - 1) as usize;
+                    (sum + values[i] * weight, total + weight)
+                },
+            );
+            sum / total
+        })
+        .collect()
+}
+
+fn butterworth_smooth(values: &[f32], cutoff: f32) -> Vec<f32> {
+    // Filter forwards and backwards to cancel out the phase shift.
+    let mut filtered = butterworth(values, cutoff);
+    filtered.reverse();
+    let mut filtered = butterworth(&filtered, cutoff);
+    filtered.reverse();
+    filtered
+}
+
+/// A second order low pass Butterworth filter with `cutoff` in cycles per frame.
+fn butterworth(values: &[f32], cutoff: f32) -> Vec<f32> {
+    let Some(first) = values.first().copied() else {
+        return Vec::new();
+    };
+
+    let k = (std::f32::consts::PI * cutoff.clamp(0.001, 0.499)).tan();
+    let sqrt2 = std::f32::consts::SQRT_2;
+    let norm = 1.0 / (1.0 + sqrt2 * k + k * k);
+    let b0 = k * k * norm;
+    let b1 = 2.0 * b0;
+    let b2 = b0;
+    let a1 = 2.0 * (k * k - 1.0) * norm;
+    let a2 = (1.0 - sqrt2 * k + k * k) * norm;
+
+    // Start from a steady state to avoid ringing at the first frame.
+    let (mut x1, mut x2, mut y1, mut y2) = (first, first, first, first);
+    values
+        .iter()
+        .map(|x| {
+            let y = b0 * x + b1 * x1 + b2 * x2 - a1 * y1 - a2 * y2;
+            (x2, x1) = (x1, *x);
+            (y2, y1) = (y1, y);
+            y
+        })
+        .collect()
+}
+
+/// The frames to keep so that all components are within `tolerance` of linear segments between frames.
+fn linear_keys(components: &[Vec<f32>], tolerance: f32) -> Vec<usize> {
+    let count = components.first().map(|c| c.len()).unwrap_or_default();
+    if count <= 2 {
+        return (0..count).collect();
+    }
+
+    let is_linear = |start: usize, end: usize| {
+        components.iter().all(|c| {
+            (start + 1..end).all(|f| {
+                let t = (f - start) as f32 / (end - start) as f32;
+                let value = c[start] + (c[end] - c[start]) * t;
+                (value - c[f]).abs() <= tolerance
+            })
+        })
+    };
+
+    let mut keys = vec![0];
+    let mut start = 0;
+    while start < count - 1 {
+        let mut end = start + 1;
+        while end + 1 < count && is_linear(start, end + 1) {
+            end += 1;
+        }
+        keys.push(end);
+        start = end;
+    }
+    keys
+}
+
+fn linear_segments(values: &[f32], keys: &[usize]) -> Vec<f32> {
+    let mut result = values.to_vec();
+    for segment in keys.windows(2) {
+        let (start, end) = (segment[0], segment[1]);
+        for (f, value) in result.iter_mut().enumerate().take(end).skip(start + 1) {
+            let t = (f - start) as f32 / (end - start) as f32;
+            *value = values[start] + (values[end] - values[start]) * t;
+        }
+    }
+    result
+}
+
+/// Apply `f` to the values over time for each component.
+/// Rotations are made continuous before filtering and normalized after filtering.
+fn filter_components<F: Fn(&[f32]) -> Vec<f32>>(values: &mut TrackValues, f: F) {
+    if let TrackValues::Transform(values) = values {
+        quaternion_continuity(values);
+    }
+
+    if let Some(components) = components(values) {
+        let components: Vec<_> = components.iter().map(|c| f(c)).collect();
+        set_components(values, &components);
+    }
+}
+
+/// The values over time for each component or `None` if the values are not floats.
+fn components(values: &TrackValues) -> Option<Vec<Vec<f32>>> {
+    let frames: Vec<Vec<f32>> = match values {
+        TrackValues::Transform(values) => values
+            .iter()
+            .map(|v| {
+                vec![
+                    v.scale.x,
+                    v.scale.y,
+                    v.scale.z,
+                    v.rotation.x,
+                    v.rotation.y,
+                    v.rotation.z,
+                    v.rotation.w,
+                    v.translation.x,
+                    v.translation.y,
+                    v.translation.z,
+                ]
+            })
+            .collect(),
+        TrackValues::UvTransform(values) => values
+            .iter()
+            .map(|v| {
+                vec![
+                    v.scale_u,
+                    v.scale_v,
+                    v.rotation,
+                    v.translate_u,
+                    v.translate_v,
+                ]
+            })
+            .collect(),
+        TrackValues::Float(values) => values.iter().map(|v| vec![*v]).collect(),
+        TrackValues::Vector4(values) => values.iter().map(|v| vec![v.x, v.y, v.z, v.w]).collect(),
+        TrackValues::PatternIndex(_) | TrackValues::Boolean(_) => return None,
+    };
+
+    let component_count = frames.first().map(|f| f.len()).unwrap_or_default();
+    Some(
+        (0..component_count)
+            .map(|c| frames.iter().map(|f| f[c]).collect())
+            .collect(),
+    )
+}
+
+fn set_components(values: &mut TrackValues, components: &[Vec<f32>]) {
+    let c = |i: usize, f: usize| components[i][f];
+    match values {
+        TrackValues::Transform(values) => {
+            for (f, v) in values.iter_mut().enumerate() {
+                let rotation = Quat::from_xyzw(c(3, f), c(4, f), c(5, f), c(6, f)).normalize();
+                *v = Transform {
+                    scale: Vector3::new(c(0, f), c(1, f), c(2, f)),
+                    rotation: vector4(rotation),
+                    translation: Vector3::new(c(7, f), c(8, f), c(9, f)),
+                };
+            }
+        }
+        TrackValues::UvTransform(values) => {
+            for (f, v) in values.iter_mut().enumerate() {
+                *v = UvTransform {
+                    scale_u: c(0, f),
+                    scale_v: c(1, f),
+                    rotation: c(2, f),
+                    translate_u: c(3, f),
+                    translate_v: c(4, f),
+                };
+            }
+        }
+        TrackValues::Float(values) => {
+            for (f, v) in values.iter_mut().enumerate() {
+                *v = c(0, f);
+            }
+        }
+        TrackValues::Vector4(values) => {
+            for (f, v) in values.iter_mut().enumerate() {
+                *v = Vector4::new(c(0, f), c(1, f), c(2, f), c(3, f));
+            }
+        }
+        TrackValues::PatternIndex(_) | TrackValues::Boolean(_) => (),
+    }
+}
+
+fn quat(v: &Vector4) -> Quat {
+    Quat::from_xyzw(v.x, v.y, v.z, v.w)
+}
+
+fn vector4(q: Quat) -> Vector4 {
+    Vector4::new(q.x, q.y, q.z, q.w)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn transform(rotation: Quat) -> Transform {
+        Transform {
+            scale: Vector3::new(1.0, 1.0, 1.0),
+            rotation: vector4(rotation),
+            translation: Vector3::new(0.0, 0.0, 0.0),
+        }
+    }
+
+    #[test]
+    fn quaternion_continuity_flip_hemisphere() {
+        let mut values = vec![
+            transform(Quat::from_rotation_z(0.1)),
+            transform(-Quat::from_rotation_z(0.2)),
+            transform(Quat::from_rotation_z(0.3)),
+        ];
+        quaternion_continuity(&mut values);
+
+        assert_eq!(vector4(Quat::from_rotation_z(0.2)), values[1].rotation);
+        assert_eq!(vector4(Quat::from_rotation_z(0.3)), values[2].rotation);
+    }
+
+    #[test]
+    fn euler_filter_unwrap() {
+        let mut values = vec![
+            transform(Quat::from_rotation_x(170f32.to_radians())),
+            transform(Quat::from_rotation_x(-170f32.to_radians())),
+        ];
+        euler_filter(&mut values);
+
+        // The rotation is the same but continuous with the previous frame.
+        let previous = quat(&values[0].rotation);
+        let current = quat(&values[1].rotation);
+        assert!(previous.dot(current) > 0.0);
+        assert!(current.abs_diff_eq(-Quat::from_rotation_x(-170f32.to_radians()), 1e-5));
+    }
+
+    #[test]
+    fn smoothing_preserve_constant() {
+        let values = vec![2.0; 10];
+        for v in gaussian_smooth(&values, 2.0) {
+            assert!((v - 2.0).abs() < 1e-5);
+        }
+        for v in butterworth_smooth(&values, 0.1) {
+            assert!((v - 2.0).abs() < 1e-5);
+        }
+
+        // Smoothing should reduce jitter.
+        let values = vec![0.0, 1.0, 0.0, 1.0, 0.0, 1.0, 0.0];
+        let smoothed = gaussian_smooth(&values, 1.0);
+        assert!((smoothed[3] - 0.5).abs() < 0.2);
+    }
+
+    #[test]
+    fn keyframe_reduction_linear_segments() {
+        let mut values = TrackValues::Float(vec![0.0, 1.0, 2.0, 3.0, 2.0, 1.005, 0.0]);
+        assert_eq!(3, keyframe_count(&values, 0.01));
+        assert_eq!(5, keyframe_count(&values, 0.001));
+
+        let settings = CurveFiltersState {
+            filter: CurveFilter::KeyframeReduction,
+            tolerance: 0.01,
+            ..Default::default()
+        };
+        apply_filter(&mut values, &settings);
+        let TrackValues::Float(values) = values else {
+            panic!()
+        };
+        for (v, expected) in values.iter().zip([0.0, 1.0, 2.0, 3.0, 2.0, 1.0, 0.0]) {
+            assert!((v - expected).abs() < 1e-5);
+        }
+    }
+}
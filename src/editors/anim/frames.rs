@@ -0,0 +1,479 @@
+use egui::{Button, DragValue, Grid};
+use log::error;
+use rfd::FileDialog;
+use ssbh_data::{
+    Vector3, Vector4,
+    anim_data::{TrackData, TrackValues, Transform, UvTransform},
+    prelude::*,
+};
+
+use super::nodes::{add_track, find_track};
+use crate::app::FrameToolsState;
+
+pub fn frame_tools_window(
+    ctx: &egui::Context,
+    state: &mut FrameToolsState,
+    anim: &mut AnimData,
+) -> bool {
+    let mut changed = false;
+
+    egui::Window::new("Frame Tools")
+        .open(&mut state.is_open)
+        .resizable(false)
+        .show(ctx, |ui| {
+            let count = frame_count(anim);
+            ui.label(format!("Frame Count: {count}"));
+            ui.separator();
+
+            Grid::new("anim_frame_tools_grid").show(ui, |ui| {
+                ui.label("Start");
+                ui.add(DragValue::new(&mut state.start).range(0..=count - 1));
+                ui.end_row();
+
+                ui.label("End");
+                ui.add(DragValue::new(&mut state.end).range(state.start..=count - 1));
+                ui.end_row();
+
+                ui.label("");
+                if ui
+                    .button("Trim")
+                    .on_hover_text("Remove frames outside the start and end frames.")
+                    .clicked()
+                {
+                    trim_frames(anim, state.start, state.end);
+                    state.start = 0;
+                    state.end = frame_count(anim) - 1;
+                    changed = true;
+                }
+                ui.end_row();
+
+                ui.label("Frame Count");
+                ui.add(DragValue::new(&mut state.frame_count).range(1..=usize::MAX));
+                ui.end_row();
+
+                ui.label("");
+                if ui
+                    .add_enabled(state.frame_count != count, Button::new("Resample"))
+                    .on_hover_text("Interpolate the values to use the new frame count.")
+                    .clicked()
+                {
+                    resample_frames(anim, state.frame_count);
+                    changed = true;
+                }
+                ui.end_row();
+
+                ui.label("Offset");
+                ui.add(DragValue::new(&mut state.offset));
+                ui.end_row();
+
+                ui.label("");
+                if ui
+                    .add_enabled(state.offset != 0, Button::new("Offset"))
+                    .on_hover_text("Shift the values by the offset and wrap around to the start.")
+                    .clicked()
+                {
+                    offset_frames(anim, state.offset);
+                    changed = true;
+                }
+                ui.end_row();
+            });
+            ui.separator();
+
+            ui.horizontal(|ui| {
+                if ui.button("Reverse").clicked() {
+                    reverse_frames(anim);
+                    changed = true;
+                }
+
+                if ui
+                    .button("Loop Pad")
+                    .on_hover_text("Add a copy of the first frame to the end.")
+                    .clicked()
+                {
+                    loop_pad_frames(anim);
+                    changed = true;
+                }
+
+                if ui
+                    .button("Append Anim...")
+                    .on_hover_text("Add the frames from another anim to the end.")
+                    .clicked()
+                    && let Some(file) = FileDialog::new()
+                        .add_filter("Anim", &["nuanmb"])
+                        .pick_file()
+                {
+                    match AnimData::from_file(&file) {
+                        Ok(other) => {
+                            append_anim(anim, &other);
+                            changed = true;
+                        }
+                        Err(e) => error!("Failed to read {file:?}: {e}"),
+                    }
+                }
+            });
+
+            if changed {
+                state.frame_count = frame_count(anim);
+                state.end = state.end.min(state.frame_count - 1);
+                state.start = state.start.min(state.end);
+            }
+        });
+
+    changed
+}
+
+/// The number of frames including the final frame.
+pub fn frame_count(anim: &AnimData) -> usize {
+    anim.final_frame_index.max(0.0) as usize + 1
+}
+
+/// Remove the frames before `start` and after `end`.
+pub fn trim_frames(anim: &mut AnimData, start: usize, end: usize) {
+    let frames: Vec<_> = (start..=end.max(start)).map(|f| f as f32).collect();
+    sample_frames(anim, &frames);
+}
+
+/// Interpolate the values to use `new_frame_count` frames.
+/// Rotations use spherical interpolation.
+pub fn resample_frames(anim: &mut AnimData, new_frame_count: usize) {
+    let final_frame = frame_count(anim) - 1;
+    let frames: Vec<_> = (0..new_frame_count.max(1))
+        .map(|i| {
+            if new_frame_count > 1 {
+                i as f32 * final_frame as f32 / (new_frame_count - 1) as f32
+            } else {
+                0.0
+            }
+        })
+        .collect();
+    sample_frames(anim, &frames);
+}
+
+pub fn reverse_frames(anim: &mut AnimData) {
+    let frames: Vec<_> = (0..frame_count(anim)).rev().map(|f| f as f32).collect();
+    sample_frames(anim, &frames);
+}
+
+/// Shift the values later by `offset` frames and wrap around to the start.
+pub fn offset_frames(anim: &mut AnimData, offset: i64) {
+    let count = frame_count(anim) as i64;
+    let frames: Vec<_> = (0..count)
+        .map(|f| (f - offset).rem_euclid(count) as f32)
+        .collect();
+    sample_frames(anim, &frames);
+}
+
+/// Add a copy of the first frame to the end for smoother looping.
+pub fn loop_pad_frames(anim: &mut AnimData) {
+    let frames: Vec<_> = (0..frame_count(anim))
+        .chain(std::iter::once(0))
+        .map(|f| f as f32)
+        .collect();
+    sample_frames(anim, &frames);
+}
+
+/// Add the frames from `other` after the final frame.
+/// Tracks missing from one of the anims hold their first or last value.
+pub fn append_anim(anim: &mut AnimData, other: &AnimData) {
+    let count = frame_count(anim);
+    let other_count = frame_count(other);
+
+    for group in &mut anim.groups {
+        for node in &mut group.nodes {
+            for track in &mut node.tracks {
+                let other_track = find_track(other, group.group_type, &node.name, &track.name)
+                    .filter(|t| {
+                        std::mem::discriminant(&t.values) == std::mem::discriminant(&track.values)
+                    });
+
+                // Constant tracks only need one value.
+                if track.values.len() == 1 && other_track.is_none_or(|t| t.values == track.values) {
+                    continue;
+                }
+
+                let frames: Vec<_> = (0..count).map(|f| f as f32).collect();
+                let mut values = sample_values(&track.values, &frames);
+                let other_values = match other_track {
+                    Some(t) => {
+                        let frames: Vec<_> = (0..other_count).map(|f| f as f32).collect();
+                        sample_values(&t.values, &frames)
+                    }
+                    None => sample_values(&track.values, &vec![(count - 1) as f32; other_count]),
+                };
+                append_values(&mut values, &other_values);
+                track.values = values;
+            }
+        }
+    }
+
+    for group in &other.groups {
+        for node in &group.nodes {
+            for track in &node.tracks {
+                if find_track(anim, group.group_type, &node.name, &track.name).is_some() {
+                    continue;
+                }
+
+                let values = if track.values.len() == 1 {
+                    track.values.clone()
+                } else {
+                    let frames: Vec<_> = std::iter::repeat_n(0, count)
+                        .chain(0..other_count)
+                        .map(|f| f as f32)
+                        .collect();
+                    sample_values(&track.values, &frames)
+                };
+                add_track(
+                    anim,
+                    group.group_type,
+                    &node.name,
+                    TrackData {
+                        values,
+                        ..track.clone()
+                    },
+                );
+            }
+        }
+    }
+
+    anim.final_frame_index = (count + other_count - 1) as f32;
+}
+
+/// Replace the values for each animated track with the values at `frames`.
+/// Tracks with a single value are constant and left unchanged.
+fn sample_frames(anim: &mut AnimData, frames: &[f32]) {
+    for track in anim
+        .groups
+        .iter_mut()
+        .flat_map(|g| &mut g.nodes)
+        .flat_map(|n| &mut n.tracks)
+    {
+        if track.values.len() > 1 {
+            track.values = sample_values(&track.values, frames);
+        }
+    }
+    anim.final_frame_index = frames.len().saturating_sub(1) as f32;
+}
+
+fn sample_values(values: &TrackValues, frames: &[f32]) -> TrackValues {
+    match values {
+        TrackValues::Transform(v) => TrackValues::Transform(sample(v, frames)),
+        TrackValues::UvTransform(v) => TrackValues::UvTransform(sample(v, frames)),
+        TrackValues::Float(v) => TrackValues::Float(sample(v, frames)),
+        TrackValues::PatternIndex(v) => TrackValues::PatternIndex(sample(v, frames)),
+        TrackValues::Boolean(v) => TrackValues::Boolean(sample(v, frames)),
+        TrackValues::Vector4(v) => TrackValues::Vector4(sample(v, frames)),
+    }
+}
+
+fn append_values(values: &mut TrackValues, other: &TrackValues) {
+    match (values, other) {
+        (TrackValues::Transform(v), TrackValues::Transform(o)) => v.extend_from_slice(o),
+        (TrackValues::UvTransform(v), TrackValues::UvTransform(o)) => v.extend_from_slice(o),
+        (TrackValues::Float(v), TrackValues::Float(o)) => v.extend_from_slice(o),
+        (TrackValues::PatternIndex(v), TrackValues::PatternIndex(o)) => v.extend_from_slice(o),
+        (TrackValues::Boolean(v), TrackValues::Boolean(o)) => v.extend_from_slice(o),
+        (TrackValues::Vector4(v), TrackValues::Vector4(o)) => v.extend_from_slice(o),
+        _ => (),
+    }
+}
+
+fn sample<T: Interpolate>(values: &[T], frames: &[f32]) -> Vec<T> {
+    frames
+        .iter()
+        .filter_map(|frame| {
+            let frame = frame.max(0.0);
+            let i = (frame.floor() as usize).min(values.len().checked_sub(1)?);
+            let factor = frame - i as f32;
+            match values.get(i + 1) {
+                Some(next) if factor > 0.0 => Some(values[i].interpolate(next, factor)),
+                _ => Some(values[i].clone()),
+            }
+        })
+        .collect()
+}
+
+trait Interpolate: Clone {
+    fn interpolate(&self, other: &Self, factor: f32) -> Self;
+}
+
+impl Interpolate for f32 {
+    fn interpolate(&self, other: &Self, factor: f32) -> Self {
+        self + (other - self) * factor
+    }
+}
+
+// Integer and boolean values hold their value until the next frame.
+impl Interpolate for u32 {
+    fn interpolate(&self, _: &Self, _: f32) -> Self {
+        *self
+    }
+}
+
+impl Interpolate for bool {
+    fn interpolate(&self, _: &Self, _: f32) -> Self {
+        *self
+    }
+}
+
+impl Interpolate for Vector3 {
+    fn interpolate(&self, other: &Self, factor: f32) -> Self {
+        Vector3::new(
+            self.x.interpolate(&other.x, factor),
+            self.y.interpolate(&other.y, factor),
+            self.z.interpolate(&other.z, factor),
+        )
+    }
+}
+
+impl Interpolate for Vector4 {
+    fn interpolate(&self, other: &Self, factor: f32) -> Self {
+        Vector4::new(
+            self.x.interpolate(&other.x, factor),
+            self.y.interpolate(&other.y, factor),
+            self.z.interpolate(&other.z, factor),
+            self.w.interpolate(&other.w, factor),
+        )
+    }
+}
+
+impl Interpolate for UvTransform {
+    fn interpolate(&self, other: &Self, factor: f32) -> Self {
+        UvTransform {
+            scale_u: self.scale_u.interpolate(&other.scale_u, factor),
+            scale_v: self.scale_v.interpolate(&other.scale_v, factor),
+            rotation: self.rotation.interpolate(&other.rotation, factor),
+            translate_u: self.translate_u.interpolate(&other.translate_u, factor),
+            translate_v: self.translate_v.interpolate(&other.translate_v, factor),
+        }
+    }
+}
+
+impl Interpolate for Transform {
+    fn interpolate(&self, other: &Self, factor: f32) -> Self {
+        let quat = |r: &Vector4| glam::Quat::from_xyzw(r.x, r.y, r.z, r.w);
+        let rotation = quat(&self.rotation).slerp(quat(&other.rotation), factor);
+        Transform {
+            scale: self.scale.interpolate(&other.scale, factor),
+            rotation: Vector4::new(rotation.x, rotation.y, rotation.z, rotation.w),
+            translation: self.translation.interpolate(&other.translation, factor),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ssbh_data::anim_data::{GroupData, NodeData, TransformFlags};
+
+    fn float_anim(values: Vec<f32>) -> AnimData {
+        AnimData {
+            major_version: 2,
+            minor_version: 0,
+            final_frame_index: values.len() as f32 - 1.0,
+            groups: vec![GroupData {
+                group_type: GroupType::Material,
+                nodes: vec![NodeData {
+                    name: "a".to_owned(),
+                    tracks: vec![
+                        TrackData {
+                            name: "CustomFloat0".to_owned(),
+                            compensate_scale: false,
+                            transform_flags: TransformFlags::default(),
+                            values: TrackValues::Float(values),
+                        },
+                        TrackData {
+                            name: "CustomFloat1".to_owned(),
+                            compensate_scale: false,
+                            transform_flags: TransformFlags::default(),
+                            values: TrackValues::Float(vec![5.0]),
+                        },
+                    ],
+                }],
+            }],
+        }
+    }
+
+    fn values(anim: &AnimData, track: usize) -> &TrackValues {
+        &anim.groups[0].nodes[0].tracks[track].values
+    }
+
+    #[test]
+    fn trim_reverse_offset_pad() {
+        let mut anim = float_anim(vec![0.0, 1.0, 2.0, 3.0]);
+
+        trim_frames(&mut anim, 1, 3);
+        assert_eq!(&TrackValues::Float(vec![1.0, 2.0, 3.0]), values(&anim, 0));
+        assert_eq!(2.0, anim.final_frame_index);
+
+        reverse_frames(&mut anim);
+        assert_eq!(&TrackValues::Float(vec![3.0, 2.0, 1.0]), values(&anim, 0));
+
+        offset_frames(&mut anim, 1);
+        assert_eq!(&TrackValues::Float(vec![1.0, 3.0, 2.0]), values(&anim, 0));
+
+        loop_pad_frames(&mut anim);
+        assert_eq!(
+            &TrackValues::Float(vec![1.0, 3.0, 2.0, 1.0]),
+            values(&anim, 0)
+        );
+        assert_eq!(3.0, anim.final_frame_index);
+
+        // Constant tracks are unchanged.
+        assert_eq!(&TrackValues::Float(vec![5.0]), values(&anim, 1));
+    }
+
+    #[test]
+    fn resample_interpolate() {
+        let mut anim = float_anim(vec![0.0, 2.0, 4.0]);
+
+        resample_frames(&mut anim, 5);
+        assert_eq!(
+            &TrackValues::Float(vec![0.0, 1.0, 2.0, 3.0, 4.0]),
+            values(&anim, 0)
+        );
+        assert_eq!(4.0, anim.final_frame_index);
+    }
+
+    #[test]
+    fn resample_slerp_rotation() {
+        let half_sqrt2 = 0.5f32.sqrt();
+        let a = Transform {
+            scale: Vector3::new(1.0, 1.0, 1.0),
+            rotation: Vector4::new(0.0, 0.0, 0.0, 1.0),
+            translation: Vector3::new(0.0, 0.0, 0.0),
+        };
+        let b = Transform {
+            scale: Vector3::new(1.0, 1.0, 1.0),
+            rotation: Vector4::new(0.0, 0.0, half_sqrt2, half_sqrt2),
+            translation: Vector3::new(2.0, 0.0, 0.0),
+        };
+
+        let c = a.interpolate(&b, 0.5);
+        let expected = (std::f32::consts::PI / 8.0).sin();
+        assert!((c.rotation.z - expected).abs() < 1e-6);
+        assert_eq!(1.0, c.translation.x);
+    }
+
+    #[test]
+    fn append_missing_tracks() {
+        let mut anim = float_anim(vec![0.0, 1.0]);
+        let mut other = float_anim(vec![2.0, 3.0, 4.0]);
+        other.groups[0].nodes[0].name = "b".to_owned();
+
+        append_anim(&mut anim, &other);
+
+        assert_eq!(4.0, anim.final_frame_index);
+        let node_a = &anim.groups[0].nodes[0];
+        let node_b = &anim.groups[0].nodes[1];
+        assert_eq!(
+            TrackValues::Float(vec![0.0, 1.0, 1.0, 1.0, 1.0]),
+            node_a.tracks[0].values
+        );
+        assert_eq!(TrackValues::Float(vec![5.0]), node_a.tracks[1].values);
+        assert_eq!(
+            TrackValues::Float(vec![2.0, 2.0, 2.0, 3.0, 4.0]),
+            node_b.tracks[0].values
+        );
+        assert_eq!(TrackValues::Float(vec![5.0]), node_b.tracks[1].values);
+    }
+}
@@ -0,0 +1,671 @@
+use std::{collections::BTreeSet, path::Path};
+
+use egui::{Button, CentralPanel, Color32, Id, Pos2, ecolor::Hsva};
+use egui_plot::{
+    Legend, Line, LineStyle, MarkerShape, Plot, PlotMemory, PlotPoint, PlotUi, Points, Polygon,
+};
+use ssbh_data::{Vector4, anim_data::TrackValues, prelude::*};
+
+use super::{filters::apply_filter, select_track_panel, selected_track};
+use crate::app::{AnimEditorState, AnimGraphState, GraphDrag};
+
+// The maximum distance in points for clicking or dragging a value.
+const HIT_RADIUS: f32 = 8.0;
+
+pub fn graph_view(
+    ui: &mut egui::Ui,
+    folder_name: &Path,
+    anim: &mut AnimData,
+    state: &mut AnimEditorState,
+) -> bool {
+    let mut changed = select_track_panel(ui, folder_name, anim, state);
+
+    CentralPanel::default().show_inside(ui, |ui| {
+        // Selected keys are only valid for the track that was selected.
+        let track_indices = (
+            state.selected_group_index,
+            state.selected_node_index,
+            state.selected_track_index,
+        );
+        if state.graph.track != track_indices {
+            state.graph = AnimGraphState {
+                track: track_indices,
+                ..Default::default()
+            };
+        }
+
+        if selected_track(&mut anim.groups, state).is_none() {
+            return;
+        }
+
+        // Insert and delete frames for every track to keep the frame count consistent.
+        let count = frame_count(anim);
+        ui.horizontal(|ui| {
+            if ui
+                .button("Insert Frame")
+                .on_hover_text("Insert a copy of the last selected frame after that frame for all tracks.")
+                .clicked()
+            {
+                let frame = state
+                    .graph
+                    .selected_keys
+                    .iter()
+                    .map(|(_, f)| *f)
+                    .max()
+                    .unwrap_or(count - 1);
+                insert_anim_frame(anim, frame);
+                state.graph.selected_keys.clear();
+                changed = true;
+            }
+
+            let frames: BTreeSet<_> = state.graph.selected_keys.iter().map(|(_, f)| *f).collect();
+            // Animations should always have at least one frame.
+            if ui
+                .add_enabled(
+                    !frames.is_empty() && frames.len() < count,
+                    Button::new("Delete Frames"),
+                )
+                .on_hover_text("Delete all frames with a selected value for all tracks.")
+                .clicked()
+            {
+                delete_anim_frames(anim, &frames);
+                state.graph.selected_keys.clear();
+                changed = true;
+            }
+        });
+
+        let Some(track) = selected_track(&mut anim.groups, state) else {
+            return;
+        };
+        let graph = &mut state.graph;
+
+        // Compare the filtered values with the current values before applying the filter.
+        let filtered_values = (state.filters.is_open && state.filters.preview).then(|| {
+            let mut values = track.values.clone();
+            apply_filter(&mut values, &state.filters);
+            values
+        });
+
+        ui.weak("Drag values to move them. Hold Ctrl while dragging to scale them. Drag empty space to box select. Hold Shift to add to the selection.");
+
+        let label_fmt = |name: &str, value: &PlotPoint| {
+            if name.is_empty() {
+                // Don't show values when not hovering near a line.
+                String::new()
+            } else {
+                format!("{name}\nframe = {}\nvalue = {}", value.x, value.y)
+            }
+        };
+
+        let plot_id = ui.make_persistent_id("anim_plot");
+
+        // Don't edit channels hidden using the legend.
+        let names = channel_names(&track.values);
+        let hidden_items = PlotMemory::load(ui.ctx(), plot_id)
+            .map(|m| m.hidden_items)
+            .unwrap_or_default();
+        let visible: Vec<_> = names
+            .iter()
+            .map(|n| !hidden_items.contains(&Id::new(n)))
+            .collect();
+
+        // Add a legend for labels and visibility toggles
+        let plot = Plot::new("anim_plot")
+            .id(plot_id)
+            .allow_drag(false)
+            .allow_zoom(false)
+            .allow_scroll(false)
+            .label_formatter(label_fmt)
+            .legend(Legend::default().follow_insertion_order(true));
+
+        plot.show(ui, |plot_ui| {
+            changed |= edit_values(plot_ui, &mut track.values, &visible, graph);
+
+            for (c, name) in names.iter().enumerate() {
+                let values = channel_values(&track.values, c);
+                let color = channel_color(c);
+
+                let points: Vec<_> = values
+                    .iter()
+                    .enumerate()
+                    .map(|(f, v)| [f as f64, *v])
+                    .collect();
+
+                let line_points = if let TrackValues::Boolean(_) = track.values {
+                    // Each value lasts until the next frame.
+                    step_points(&values)
+                } else {
+                    points.clone()
+                };
+
+                // Use the same name to toggle the line and points together.
+                plot_ui.line(Line::new(*name, line_points).color(color));
+                plot_ui.points(Points::new(*name, points).color(color).radius(2.5));
+
+                if let Some(filtered_values) = &filtered_values {
+                    let values = channel_values(filtered_values, c);
+                    let points: Vec<_> = values
+                        .iter()
+                        .enumerate()
+                        .map(|(f, v)| [f as f64, *v])
+                        .collect();
+                    plot_ui.line(
+                        Line::new(format!("{name} (filtered)"), points)
+                            .color(color)
+                            .style(LineStyle::dashed_dense()),
+                    );
+                }
+            }
+
+            let selected_points: Vec<_> = graph
+                .selected_keys
+                .iter()
+                .filter(|(c, _)| visible.get(*c).copied().unwrap_or_default())
+                .filter_map(|(c, f)| Some([*f as f64, channel_value(&track.values, *c, *f)?]))
+                .collect();
+            plot_ui.points(
+                Points::new("", selected_points)
+                    .shape(MarkerShape::Diamond)
+                    .color(Color32::WHITE)
+                    .radius(5.0),
+            );
+
+            if let Some(GraphDrag::BoxSelect { start }) = &graph.drag
+                && let Some(end) = plot_ui.pointer_coordinate()
+            {
+                plot_ui.polygon(Polygon::new(
+                    "",
+                    vec![
+                        [start[0], start[1]],
+                        [end.x, start[1]],
+                        [end.x, end.y],
+                        [start[0], end.y],
+                    ],
+                ));
+            }
+        });
+    });
+
+    changed
+}
+
+fn edit_values(
+    plot_ui: &mut PlotUi,
+    values: &mut TrackValues,
+    visible: &[bool],
+    graph: &mut AnimGraphState,
+) -> bool {
+    let mut changed = false;
+
+    let response = plot_ui.response().clone();
+    let (modifiers, press_origin) = plot_ui
+        .ctx()
+        .input(|i| (i.modifiers, i.pointer.press_origin()));
+
+    if response.clicked() {
+        if !modifiers.shift {
+            graph.selected_keys.clear();
+        }
+        if let Some(pos) = response.interact_pointer_pos()
+            && let Some(key) = closest_key(plot_ui, values, visible, pos)
+        {
+            graph.selected_keys.insert(key);
+        }
+    }
+
+    if response.drag_started_by(egui::PointerButton::Primary)
+        && let Some(pos) = press_origin
+    {
+        match closest_key(plot_ui, values, visible, pos) {
+            Some(key) => {
+                if !graph.selected_keys.contains(&key) {
+                    if !modifiers.shift {
+                        graph.selected_keys.clear();
+                    }
+                    graph.selected_keys.insert(key);
+                }
+
+                let start_values: Vec<_> = graph
+                    .selected_keys
+                    .iter()
+                    .filter_map(|(c, f)| Some(((*c, *f), channel_value(values, *c, *f)?)))
+                    .collect();
+
+                graph.drag = Some(if modifiers.ctrl {
+                    let pivot = start_values.iter().map(|(_, v)| v).sum::<f64>()
+                        / start_values.len() as f64;
+                    GraphDrag::Scale {
+                        start_values,
+                        pivot,
+                        factor: 1.0,
+                    }
+                } else {
+                    GraphDrag::Move {
+                        start_values,
+                        offset: 0.0,
+                    }
+                });
+            }
+            None => {
+                let start = plot_ui.plot_from_screen(pos);
+                graph.drag = Some(GraphDrag::BoxSelect {
+                    start: [start.x, start.y],
+                });
+            }
+        }
+    }
+
+    if response.dragged_by(egui::PointerButton::Primary) {
+        match &mut graph.drag {
+            Some(GraphDrag::Move {
+                start_values,
+                offset,
+            }) => {
+                // Keep the plot from rescaling while moving values.
+                plot_ui.set_auto_bounds(false);
+
+                // Accumulate the offset to allow moving integer or boolean values.
+                *offset += plot_ui.pointer_coordinate_drag_delta().y as f64;
+                for ((c, f), v) in start_values.iter() {
+                    set_channel_value(values, *c, *f, v + *offset);
+                }
+                changed = true;
+            }
+            Some(GraphDrag::Scale {
+                start_values,
+                pivot,
+                factor,
+            }) => {
+                plot_ui.set_auto_bounds(false);
+
+                // Dragging up scales up and dragging down scales down.
+                *factor *= (-response.drag_delta().y as f64 * 0.01).exp();
+                for ((c, f), v) in start_values.iter() {
+                    set_channel_value(values, *c, *f, *pivot + (v - *pivot) * *factor);
+                }
+                changed = true;
+            }
+            Some(GraphDrag::BoxSelect { .. }) | None => (),
+        }
+    }
+
+    if response.drag_stopped() {
+        if let Some(GraphDrag::BoxSelect { start }) = &graph.drag
+            && let Some(end) = plot_ui.pointer_coordinate()
+        {
+            if !modifiers.shift {
+                graph.selected_keys.clear();
+            }
+            let (min_x, max_x) = (start[0].min(end.x), start[0].max(end.x));
+            let (min_y, max_y) = (start[1].min(end.y), start[1].max(end.y));
+            for c in (0..visible.len()).filter(|c| visible[*c]) {
+                for (f, v) in channel_values(values, c).into_iter().enumerate() {
+                    let x = f as f64;
+                    if (min_x..=max_x).contains(&x) && (min_y..=max_y).contains(&v) {
+                        graph.selected_keys.insert((c, f));
+                    }
+                }
+            }
+        }
+        graph.drag = None;
+        plot_ui.set_auto_bounds(true);
+    }
+
+    changed
+}
+
+fn closest_key(
+    plot_ui: &PlotUi,
+    values: &TrackValues,
+    visible: &[bool],
+    pos: Pos2,
+) -> Option<(usize, usize)> {
+    let mut closest = None;
+    let mut min_distance = HIT_RADIUS;
+    for c in (0..visible.len()).filter(|c| visible[*c]) {
+        for (f, v) in channel_values(values, c).into_iter().enumerate() {
+            let distance = plot_ui
+                .screen_from_plot(PlotPoint::new(f as f64, v))
+                .distance(pos);
+            if distance < min_distance {
+                min_distance = distance;
+                closest = Some((c, f));
+            }
+        }
+    }
+    closest
+}
+
+fn channel_color(channel: usize) -> Color32 {
+    // Match the automatic line colors used by egui_plot.
+    let golden_ratio = (5.0_f32.sqrt() - 1.0) / 2.0;
+    Hsva::new(channel as f32 * golden_ratio, 0.85, 0.5, 1.0).into()
+}
+
+fn step_points(values: &[f64]) -> Vec<[f64; 2]> {
+    let mut points = Vec::new();
+    for (i, v) in values.iter().enumerate() {
+        points.push([i as f64, *v]);
+        if i < values.len() - 1 {
+            points.push([(i + 1) as f64, *v]);
+        }
+    }
+    points
+}
+
+/// The names of the values for each frame that can be edited in the graph.
+/// Rotations use Euler angles since quaternion components are hard to read.
+fn channel_names(values: &TrackValues) -> &'static [&'static str] {
+    match values {
+        TrackValues::Transform(_) => &[
+            "translation.x",
+            "translation.y",
+            "translation.z",
+            "rotation.x (degrees)",
+            "rotation.y (degrees)",
+            "rotation.z (degrees)",
+            "scale.x",
+            "scale.y",
+            "scale.z",
+        ],
+        TrackValues::UvTransform(_) => &[
+            "scale_u",
+            "scale_v",
+            "rotation",
+            "translate_u",
+            "translate_v",
+        ],
+        TrackValues::Float(_) | TrackValues::PatternIndex(_) | TrackValues::Boolean(_) => {
+            &["value"]
+        }
+        TrackValues::Vector4(_) => &["x", "y", "z", "w"],
+    }
+}
+
+fn channel_values(values: &TrackValues, channel: usize) -> Vec<f64> {
+    (0..values.len())
+        .filter_map(|f| channel_value(values, channel, f))
+        .collect()
+}
+
+fn channel_value(values: &TrackValues, channel: usize, frame: usize) -> Option<f64> {
+    let value = match values {
+        TrackValues::Transform(values) => {
+            let t = values.get(frame)?;
+            match channel {
+                0 => t.translation.x,
+                1 => t.translation.y,
+                2 => t.translation.z,
+                3..=5 => euler_degrees(&t.rotation)[channel - 3],
+                6 => t.scale.x,
+                7 => t.scale.y,
+                8 => t.scale.z,
+                _ => return None,
+            }
+        }
+        TrackValues::UvTransform(values) => {
+            let v = values.get(frame)?;
+            match channel {
+                0 => v.scale_u,
+                1 => v.scale_v,
+                2 => v.rotation,
+                3 => v.translate_u,
+                4 => v.translate_v,
+                _ => return None,
+            }
+        }
+        TrackValues::Float(values) => *values.get(frame)?,
+        TrackValues::PatternIndex(values) => *values.get(frame)? as f32,
+        TrackValues::Boolean(values) => {
+            if *values.get(frame)? {
+                1.0
+            } else {
+                0.0
+            }
+        }
+        TrackValues::Vector4(values) => {
+            let v = values.get(frame)?;
+            match channel {
+                0 => v.x,
+                1 => v.y,
+                2 => v.z,
+                3 => v.w,
+                _ => return None,
+            }
+        }
+    };
+    Some(value as f64)
+}
+
+fn set_channel_value(values: &mut TrackValues, channel: usize, frame: usize, value: f64) {
+    let value = value as f32;
+    match values {
+        TrackValues::Transform(values) => {
+            if let Some(t) = values.get_mut(frame) {
+                match channel {
+                    0 => t.translation.x = value,
+                    1 => t.translation.y = value,
+                    2 => t.translation.z = value,
+                    3..=5 => set_euler_degrees(&mut t.rotation, channel - 3, value),
+                    6 => t.scale.x = value,
+                    7 => t.scale.y = value,
+                    8 => t.scale.z = value,
+                    _ => (),
+                }
+            }
+        }
+        TrackValues::UvTransform(values) => {
+            if let Some(v) = values.get_mut(frame) {
+                match channel {
+                    0 => v.scale_u = value,
+                    1 => v.scale_v = value,
+                    2 => v.rotation = value,
+                    3 => v.translate_u = value,
+                    4 => v.translate_v = value,
+                    _ => (),
+                }
+            }
+        }
+        TrackValues::Float(values) => {
+            if let Some(v) = values.get_mut(frame) {
+                *v = value;
+            }
+        }
+        TrackValues::PatternIndex(values) => {
+            if let Some(v) = values.get_mut(frame) {
+                *v = value.round().max(0.0) as u32;
+            }
+        }
+        TrackValues::Boolean(values) => {
+            if let Some(v) = values.get_mut(frame) {
+                *v = value >= 0.5;
+            }
+        }
+        TrackValues::Vector4(values) => {
+            if let Some(v) = values.get_mut(frame) {
+                match channel {
+                    0 => v.x = value,
+                    1 => v.y = value,
+                    2 => v.z = value,
+                    3 => v.w = value,
+                    _ => (),
+                }
+            }
+        }
+    }
+}
+
+fn euler_degrees(rotation: &Vector4) -> [f32; 3] {
+    let quat = glam::Quat::from_xyzw(rotation.x, rotation.y, rotation.z, rotation.w);
+    let (x, y, z) = quat.to_euler(glam::EulerRot::XYZ);
+    [x.to_degrees(), y.to_degrees(), z.to_degrees()]
+}
+
+fn set_euler_degrees(rotation: &mut Vector4, axis: usize, value: f32) {
+    let mut euler = euler_degrees(rotation);
+    euler[axis] = value;
+
+    let old = glam::Quat::from_xyzw(rotation.x, rotation.y, rotation.z, rotation.w);
+    let mut quat = glam::Quat::from_euler(
+        glam::EulerRot::XYZ,
+        euler[0].to_radians(),
+        euler[1].to_radians(),
+        euler[2].to_radians(),
+    );
+    // Keep the same sign to avoid flipping interpolation between frames.
+    if quat.dot(old) < 0.0 {
+        quat = -quat;
+    }
+    *rotation = Vector4::new(quat.x, quat.y, quat.z, quat.w);
+}
+
+fn frame_count(anim: &AnimData) -> usize {
+    anim.final_frame_index as usize + 1
+}
+
+fn insert_anim_frame(anim: &mut AnimData, frame: usize) {
+    let count = frame_count(anim);
+    for track in anim
+        .groups
+        .iter_mut()
+        .flat_map(|g| g.nodes.iter_mut())
+        .flat_map(|n| n.tracks.iter_mut())
+    {
+        // Constant tracks apply to every frame and don't need a new value.
+        if track.values.len() > 1 {
+            insert_frame(&mut track.values, frame.min(track.values.len() - 1));
+        }
+    }
+    anim.final_frame_index = count as f32;
+}
+
+fn delete_anim_frames(anim: &mut AnimData, frames: &BTreeSet<usize>) {
+    let count = frame_count(anim);
+    for track in anim
+        .groups
+        .iter_mut()
+        .flat_map(|g| g.nodes.iter_mut())
+        .flat_map(|n| n.tracks.iter_mut())
+    {
+        if track.values.len() > 1 {
+            remove_frames(&mut track.values, frames);
+        }
+    }
+    let removed = frames.iter().filter(|f| **f < count).count();
+    anim.final_frame_index = count.saturating_sub(removed).max(1) as f32 - 1.0;
+}
+
+fn insert_frame(values: &mut TrackValues, frame: usize) {
+    match values {
+        TrackValues::Transform(values) => duplicate_value(values, frame),
+        TrackValues::UvTransform(values) => duplicate_value(values, frame),
+        TrackValues::Float(values) => duplicate_value(values, frame),
+        TrackValues::PatternIndex(values) => duplicate_value(values, frame),
+        TrackValues::Boolean(values) => duplicate_value(values, frame),
+        TrackValues::Vector4(values) => duplicate_value(values, frame),
+    }
+}
+
+fn duplicate_value<T: Clone>(values: &mut Vec<T>, index: usize) {
+    if let Some(value) = values.get(index).cloned() {
+        values.insert(index + 1, value);
+    }
+}
+
+fn remove_frames(values: &mut TrackValues, frames: &BTreeSet<usize>) {
+    match values {
+        TrackValues::Transform(values) => remove_values(values, frames),
+        TrackValues::UvTransform(values) => remove_values(values, frames),
+        TrackValues::Float(values) => remove_values(values, frames),
+        TrackValues::PatternIndex(values) => remove_values(values, frames),
+        TrackValues::Boolean(values) => remove_values(values, frames),
+        TrackValues::Vector4(values) => remove_values(values, frames),
+    }
+}
+
+fn remove_values<T>(values: &mut Vec<T>, indices: &BTreeSet<usize>) {
+    let mut i = 0;
+    values.retain(|_| {
+        let keep = !indices.contains(&i);
+        i += 1;
+        keep
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ssbh_data::{
+        Vector3,
+        anim_data::{GroupData, GroupType, NodeData, TrackData, Transform, TransformFlags},
+    };
+
+    #[test]
+    fn set_rotation_euler_degrees() {
+        let mut values = TrackValues::Transform(vec![Transform {
+            scale: Vector3::new(1.0, 1.0, 1.0),
+            rotation: Vector4::new(0.0, 0.0, 0.0, 1.0),
+            translation: Vector3::new(0.0, 0.0, 0.0),
+        }]);
+
+        set_channel_value(&mut values, 5, 0, 90.0);
+        assert!((channel_value(&values, 5, 0).unwrap() - 90.0).abs() < 1e-4);
+        assert!(channel_value(&values, 3, 0).unwrap().abs() < 1e-4);
+
+        let TrackValues::Transform(transforms) = values else {
+            panic!()
+        };
+        let r = &transforms[0].rotation;
+        let half_sqrt2 = 0.5f32.sqrt();
+        assert!((r.z - half_sqrt2).abs() < 1e-6);
+        assert!((r.w - half_sqrt2).abs() < 1e-6);
+    }
+
+    #[test]
+    fn insert_delete_anim_frames() {
+        let mut anim = AnimData {
+            major_version: 2,
+            minor_version: 0,
+            final_frame_index: 2.0,
+            groups: vec![GroupData {
+                group_type: GroupType::Material,
+                nodes: vec![NodeData {
+                    name: "a".to_owned(),
+                    tracks: vec![
+                        TrackData {
+                            name: "CustomFloat0".to_owned(),
+                            compensate_scale: false,
+                            transform_flags: TransformFlags::default(),
+                            values: TrackValues::Float(vec![0.0, 1.0, 2.0]),
+                        },
+                        TrackData {
+                            name: "CustomFloat1".to_owned(),
+                            compensate_scale: false,
+                            transform_flags: TransformFlags::default(),
+                            values: TrackValues::Float(vec![5.0]),
+                        },
+                    ],
+                }],
+            }],
+        };
+
+        insert_anim_frame(&mut anim, 1);
+        assert_eq!(3.0, anim.final_frame_index);
+        let tracks = &anim.groups[0].nodes[0].tracks;
+        assert_eq!(
+            TrackValues::Float(vec![0.0, 1.0, 1.0, 2.0]),
+            tracks[0].values
+        );
+        assert_eq!(TrackValues::Float(vec![5.0]), tracks[1].values);
+
+        delete_anim_frames(&mut anim, &[0, 3].into());
+        assert_eq!(1.0, anim.final_frame_index);
+        let tracks = &anim.groups[0].nodes[0].tracks;
+        assert_eq!(TrackValues::Float(vec![1.0, 1.0]), tracks[0].values);
+        assert_eq!(TrackValues::Float(vec![5.0]), tracks[1].values);
+
+        let mut values = tracks[0].values.clone();
+        set_channel_value(&mut values, 0, 1, 0.5);
+        assert_eq!(TrackValues::Float(vec![1.0, 0.5]), values);
+    }
+}
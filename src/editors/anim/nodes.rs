@@ -0,0 +1,303 @@
+use egui::{Button, ComboBox, Grid, ScrollArea};
+use ssbh_data::{
+    Vector3, Vector4,
+    anim_data::{
+        GroupData, GroupType, NodeData, TrackData, TrackValues, Transform, TransformFlags,
+    },
+    matl_data::{MatlEntryData, ParamId},
+    prelude::*,
+};
+
+use crate::app::AddNodeState;
+
+pub fn add_node_window(
+    ctx: &egui::Context,
+    state: &mut AddNodeState,
+    anim: &mut AnimData,
+    skel: Option<&SkelData>,
+    mesh: Option<&MeshData>,
+    matl: Option<&MatlData>,
+) -> bool {
+    let mut changed = false;
+
+    egui::Window::new("Add Node")
+        .open(&mut state.is_open)
+        .resizable(false)
+        .show(ctx, |ui| {
+            Grid::new("anim_add_node_grid").show(ui, |ui| {
+                ui.label("Group");
+                ComboBox::from_id_salt("anim_add_node_group")
+                    .selected_text(state.group_type.to_string())
+                    .show_ui(ui, |ui| {
+                        for group_type in [
+                            GroupType::Transform,
+                            GroupType::Visibility,
+                            GroupType::Material,
+                        ] {
+                            ui.selectable_value(
+                                &mut state.group_type,
+                                group_type,
+                                group_type.to_string(),
+                            );
+                        }
+                    });
+                ui.end_row();
+
+                let names = node_names(state.group_type, skel, mesh, matl);
+                ui.label("Node");
+                name_combo_box(ui, "anim_add_node_name", &mut state.node_name, &names);
+                ui.end_row();
+
+                if state.group_type == GroupType::Material {
+                    let entry = matl.and_then(|m| {
+                        m.entries
+                            .iter()
+                            .find(|e| e.material_label == state.node_name)
+                    });
+                    ui.label("Track");
+                    name_combo_box(
+                        ui,
+                        "anim_add_node_track",
+                        &mut state.track_name,
+                        &entry.map(material_track_names).unwrap_or_default(),
+                    );
+                    ui.end_row();
+                }
+            });
+
+            // Use the current file values as the initial value for the track.
+            let track = match state.group_type {
+                GroupType::Transform => skel
+                    .and_then(|s| s.bones.iter().find(|b| b.name == state.node_name))
+                    .map(|b| transform_track(&b.transform)),
+                GroupType::Visibility => Some(visibility_track()),
+                GroupType::Material => matl
+                    .and_then(|m| {
+                        m.entries
+                            .iter()
+                            .find(|e| e.material_label == state.node_name)
+                    })
+                    .and_then(|e| material_track(e, &state.track_name)),
+                GroupType::Camera => None,
+            };
+
+            let is_valid = !state.node_name.is_empty()
+                && track.as_ref().is_some_and(|t| {
+                    find_track(anim, state.group_type, &state.node_name, &t.name).is_none()
+                });
+            if ui.add_enabled(is_valid, Button::new("Add")).clicked()
+                && let Some(track) = track
+            {
+                add_track(anim, state.group_type, &state.node_name, track);
+                changed = true;
+            }
+        });
+
+    changed
+}
+
+fn name_combo_box(ui: &mut egui::Ui, id_salt: &str, name: &mut String, names: &[String]) {
+    ComboBox::from_id_salt(id_salt)
+        .width(300.0)
+        .selected_text(name.as_str())
+        .show_ui(ui, |ui| {
+            ScrollArea::vertical().max_height(300.0).show(ui, |ui| {
+                for n in names {
+                    ui.selectable_value(name, n.clone(), n);
+                }
+            });
+        });
+}
+
+/// Names from the folder's files that can be used for nodes in groups of type `group_type`.
+pub fn node_names(
+    group_type: GroupType,
+    skel: Option<&SkelData>,
+    mesh: Option<&MeshData>,
+    matl: Option<&MatlData>,
+) -> Vec<String> {
+    match group_type {
+        GroupType::Transform => skel
+            .map(|s| s.bones.iter().map(|b| b.name.clone()).collect())
+            .unwrap_or_default(),
+        GroupType::Visibility => {
+            // Visibility nodes apply to all subindices of a mesh object.
+            let mut names: Vec<_> = mesh
+                .map(|m| m.objects.iter().map(|o| o.name.clone()).collect())
+                .unwrap_or_default();
+            names.sort();
+            names.dedup();
+            names
+        }
+        GroupType::Material => matl
+            .map(|m| m.entries.iter().map(|e| e.material_label.clone()).collect())
+            .unwrap_or_default(),
+        GroupType::Camera => Vec::new(),
+    }
+}
+
+fn material_track_names(entry: &MatlEntryData) -> Vec<String> {
+    entry
+        .vectors
+        .iter()
+        .map(|p| p.param_id.to_string())
+        .chain(entry.floats.iter().map(|p| p.param_id.to_string()))
+        .chain(entry.booleans.iter().map(|p| p.param_id.to_string()))
+        .collect()
+}
+
+fn material_track(entry: &MatlEntryData, name: &str) -> Option<TrackData> {
+    let find = |id: ParamId| id.to_string() == name;
+
+    let values = if let Some(p) = entry.vectors.iter().find(|p| find(p.param_id)) {
+        TrackValues::Vector4(vec![p.data])
+    } else if let Some(p) = entry.floats.iter().find(|p| find(p.param_id)) {
+        TrackValues::Float(vec![p.data])
+    } else if let Some(p) = entry.booleans.iter().find(|p| find(p.param_id)) {
+        TrackValues::Boolean(vec![p.data])
+    } else {
+        return None;
+    };
+
+    Some(TrackData {
+        name: name.to_owned(),
+        compensate_scale: false,
+        transform_flags: TransformFlags::default(),
+        values,
+    })
+}
+
+pub fn visibility_track() -> TrackData {
+    TrackData {
+        name: "Visibility".to_owned(),
+        compensate_scale: false,
+        transform_flags: TransformFlags::default(),
+        values: TrackValues::Boolean(vec![true]),
+    }
+}
+
+fn transform_track(transform: &[[f32; 4]; 4]) -> TrackData {
+    let (s, r, t) = glam::Mat4::from_cols_array_2d(transform).to_scale_rotation_translation();
+    TrackData {
+        name: "Transform".to_owned(),
+        compensate_scale: false,
+        transform_flags: TransformFlags::default(),
+        values: TrackValues::Transform(vec![Transform {
+            scale: Vector3::new(s.x, s.y, s.z),
+            rotation: Vector4::new(r.x, r.y, r.z, r.w),
+            translation: Vector3::new(t.x, t.y, t.z),
+        }]),
+    }
+}
+
+pub fn find_track<'a>(
+    anim: &'a AnimData,
+    group_type: GroupType,
+    node_name: &str,
+    track_name: &str,
+) -> Option<&'a TrackData> {
+    anim.groups
+        .iter()
+        .filter(|g| g.group_type == group_type)
+        .flat_map(|g| &g.nodes)
+        .filter(|n| n.name == node_name)
+        .flat_map(|n| &n.tracks)
+        .find(|t| t.name == track_name)
+}
+
+/// Add `track` to the node, creating the group and node if needed.
+pub fn add_track(anim: &mut AnimData, group_type: GroupType, node_name: &str, track: TrackData) {
+    let group = match anim.groups.iter().position(|g| g.group_type == group_type) {
+        Some(i) => &mut anim.groups[i],
+        None => {
+            anim.groups.push(GroupData {
+                group_type,
+                nodes: Vec::new(),
+            });
+            anim.groups.last_mut().unwrap()
+        }
+    };
+
+    match group.nodes.iter_mut().find(|n| n.name == node_name) {
+        Some(node) => node.tracks.push(track),
+        None => group.nodes.push(NodeData {
+            name: node_name.to_owned(),
+            tracks: vec![track],
+        }),
+    }
+}
+
+/// Copy the tracks for the node at `node_index` to the node named `name`.
+/// Any existing tracks for `name` are replaced.
+pub fn copy_node_tracks(group: &mut GroupData, node_index: usize, name: &str) {
+    let Some(tracks) = group.nodes.get(node_index).map(|n| n.tracks.clone()) else {
+        return;
+    };
+
+    match group.nodes.iter_mut().find(|n| n.name == name) {
+        Some(node) => node.tracks = tracks,
+        None => group.nodes.push(NodeData {
+            name: name.to_owned(),
+            tracks,
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn anim(groups: Vec<GroupData>) -> AnimData {
+        AnimData {
+            major_version: 2,
+            minor_version: 0,
+            final_frame_index: 0.0,
+            groups,
+        }
+    }
+
+    #[test]
+    fn add_track_new_group_and_node() {
+        let mut anim = anim(Vec::new());
+
+        add_track(&mut anim, GroupType::Visibility, "a", visibility_track());
+        add_track(&mut anim, GroupType::Visibility, "b", visibility_track());
+
+        assert_eq!(1, anim.groups.len());
+        assert_eq!(GroupType::Visibility, anim.groups[0].group_type);
+        assert_eq!(
+            vec!["a", "b"],
+            anim.groups[0]
+                .nodes
+                .iter()
+                .map(|n| n.name.as_str())
+                .collect::<Vec<_>>()
+        );
+        assert!(find_track(&anim, GroupType::Visibility, "a", "Visibility").is_some());
+        assert!(find_track(&anim, GroupType::Material, "a", "Visibility").is_none());
+    }
+
+    #[test]
+    fn copy_tracks_replace_existing() {
+        let mut group = GroupData {
+            group_type: GroupType::Transform,
+            nodes: vec![
+                NodeData {
+                    name: "a".to_owned(),
+                    tracks: vec![transform_track(&glam::Mat4::IDENTITY.to_cols_array_2d())],
+                },
+                NodeData {
+                    name: "b".to_owned(),
+                    tracks: Vec::new(),
+                },
+            ],
+        };
+
+        copy_node_tracks(&mut group, 0, "b");
+        copy_node_tracks(&mut group, 0, "c");
+
+        assert_eq!(3, group.nodes.len());
+        assert_eq!(group.nodes[0].tracks, group.nodes[1].tracks);
+        assert_eq!(group.nodes[0].tracks, group.nodes[2].tracks);
+    }
+}
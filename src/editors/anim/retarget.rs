@@ -0,0 +1,362 @@
+use std::{collections::BTreeMap, path::Path};
+
+use egui::{Button, ComboBox, Grid, ScrollArea};
+use log::error;
+use rfd::FileDialog;
+use ssbh_data::{
+    Vector3, Vector4,
+    anim_data::{GroupData, GroupType, NodeData, TrackData, TrackValues, Transform},
+    prelude::*,
+};
+
+use super::nodes::find_track;
+use crate::{app::RetargetState, export_json, import_json, save_file_as};
+
+pub fn retarget_window(
+    ctx: &egui::Context,
+    state: &mut RetargetState,
+    skel: Option<&SkelData>,
+    folder_name: &Path,
+) {
+    egui::Window::new("Retarget Anim")
+        .open(&mut state.is_open)
+        .resizable(true)
+        .show(ctx, |ui| {
+            let Some(skel) = skel else {
+                ui.label("The folder does not contain a model.nusktb.");
+                return;
+            };
+
+            Grid::new("anim_retarget_files").show(ui, |ui| {
+                ui.label("Source Anim");
+                ui.horizontal(|ui| {
+                    file_label(ui, state.source_anim.as_ref().map(|(p, _)| p.as_path()));
+                    if ui.button("Open...").clicked()
+                        && let Some(file) = FileDialog::new()
+                            .add_filter("Anim", &["nuanmb"])
+                            .pick_file()
+                    {
+                        match AnimData::from_file(&file) {
+                            Ok(anim) => state.source_anim = Some((file, anim)),
+                            Err(e) => error!("Failed to read {file:?}: {e}"),
+                        }
+                    }
+                });
+                ui.end_row();
+
+                ui.label("Source Skel");
+                ui.horizontal(|ui| {
+                    file_label(ui, state.source_skel.as_ref().map(|(p, _)| p.as_path()));
+                    if ui.button("Open...").clicked()
+                        && let Some(file) = FileDialog::new()
+                            .add_filter("Skel", &["nusktb"])
+                            .pick_file()
+                    {
+                        match SkelData::from_file(&file) {
+                            Ok(source_skel) => {
+                                state.bone_map = auto_bone_map(&source_skel, skel);
+                                state.source_skel = Some((file, source_skel));
+                            }
+                            Err(e) => error!("Failed to read {file:?}: {e}"),
+                        }
+                    }
+                });
+                ui.end_row();
+            });
+            ui.separator();
+
+            let Some((_, source_skel)) = &state.source_skel else {
+                return;
+            };
+
+            ui.horizontal(|ui| {
+                if ui.button("Auto Match").clicked() {
+                    state.bone_map = auto_bone_map(source_skel, skel);
+                }
+
+                if ui.button("Load Map...").clicked() {
+                    import_json(&mut state.bone_map, folder_name);
+                }
+
+                if ui.button("Save Map...").clicked() {
+                    export_json(&state.bone_map, folder_name, "bone_map");
+                }
+            });
+
+            ui.heading("Bone Map");
+            ScrollArea::vertical().max_height(400.0).show(ui, |ui| {
+                Grid::new("anim_retarget_bone_map")
+                    .striped(true)
+                    .show(ui, |ui| {
+                        ui.strong("Target Bone");
+                        ui.strong("Source Bone");
+                        ui.end_row();
+
+                        for bone in &skel.bones {
+                            ui.label(&bone.name);
+
+                            let mut source_name = state.bone_map.get(&bone.name).cloned();
+                            ComboBox::from_id_salt(("anim_retarget_source", &bone.name))
+                                .width(250.0)
+                                .selected_text(source_name.as_deref().unwrap_or("None"))
+                                .show_ui(ui, |ui| {
+                                    ui.selectable_value(&mut source_name, None, "None");
+                                    for source_bone in &source_skel.bones {
+                                        ui.selectable_value(
+                                            &mut source_name,
+                                            Some(source_bone.name.clone()),
+                                            &source_bone.name,
+                                        );
+                                    }
+                                });
+                            match source_name {
+                                Some(name) => {
+                                    state.bone_map.insert(bone.name.clone(), name);
+                                }
+                                None => {
+                                    state.bone_map.remove(&bone.name);
+                                }
+                            }
+                            ui.end_row();
+                        }
+                    });
+            });
+            ui.separator();
+
+            if ui
+                .add_enabled(state.source_anim.is_some(), Button::new("Retarget"))
+                .on_hover_text("Save the retargeted source anim to a new file.")
+                .clicked()
+                && let Some((source_path, source_anim)) = &state.source_anim
+            {
+                let retargeted = retarget_anim(source_anim, source_skel, skel, &state.bone_map);
+                let stem = source_path
+                    .file_stem()
+                    .map(|s| s.to_string_lossy().to_string())
+                    .unwrap_or_default();
+                save_file_as(
+                    &retargeted,
+                    folder_name,
+                    &format!("{stem}_retargeted.nuanmb"),
+                    "Anim",
+                    "nuanmb",
+                );
+            }
+        });
+}
+
+fn file_label(ui: &mut egui::Ui, path: Option<&Path>) {
+    match path {
+        Some(path) => {
+            ui.label(
+                path.file_name()
+                    .map(|f| f.to_string_lossy().to_string())
+                    .unwrap_or_default(),
+            )
+            .on_hover_text(path.to_string_lossy());
+        }
+        None => {
+            ui.weak("None");
+        }
+    }
+}
+
+/// Map each target bone to the source bone with the same name ignoring case.
+pub fn auto_bone_map(source: &SkelData, target: &SkelData) -> BTreeMap<String, String> {
+    target
+        .bones
+        .iter()
+        .filter_map(|t| {
+            let source = source.bones.iter().find(|s| s.name == t.name).or_else(|| {
+                source
+                    .bones
+                    .iter()
+                    .find(|s| s.name.eq_ignore_ascii_case(&t.name))
+            })?;
+            Some((t.name.clone(), source.name.clone()))
+        })
+        .collect()
+}
+
+/// Transfer the transform tracks of `source_anim` to the target bones in `bone_map`.
+///
+/// Rotations are applied relative to each skeleton's rest pose.
+/// Translation offsets from the source rest pose are scaled by the ratio of the bone lengths
+/// and applied to the target rest pose.
+/// Groups other than transforms are copied unchanged.
+pub fn retarget_anim(
+    source_anim: &AnimData,
+    source_skel: &SkelData,
+    target_skel: &SkelData,
+    bone_map: &BTreeMap<String, String>,
+) -> AnimData {
+    let nodes = target_skel
+        .bones
+        .iter()
+        .filter_map(|target_bone| {
+            let source_name = bone_map.get(&target_bone.name)?;
+            let source_bone = source_skel.bones.iter().find(|b| &b.name == source_name)?;
+            let track = find_track(source_anim, GroupType::Transform, source_name, "Transform")?;
+            let TrackValues::Transform(values) = &track.values else {
+                return None;
+            };
+
+            let (_, source_rotation, source_translation) =
+                glam::Mat4::from_cols_array_2d(&source_bone.transform)
+                    .to_scale_rotation_translation();
+            let (_, target_rotation, target_translation) =
+                glam::Mat4::from_cols_array_2d(&target_bone.transform)
+                    .to_scale_rotation_translation();
+
+            // Root bones often have no length, so preserve their translation.
+            let source_length = source_translation.length();
+            let length_ratio = if source_length > 1e-6 {
+                target_translation.length() / source_length
+            } else {
+                1.0
+            };
+
+            let values = values
+                .iter()
+                .map(|v| {
+                    let rotation = glam::Quat::from_xyzw(
+                        v.rotation.x,
+                        v.rotation.y,
+                        v.rotation.z,
+                        v.rotation.w,
+                    );
+                    // Apply the rotation from the source rest pose to the target rest pose.
+                    let rest_rotation = target_rotation * source_rotation.inverse();
+                    let rotation = rest_rotation * rotation;
+
+                    let translation = glam::vec3(v.translation.x, v.translation.y, v.translation.z);
+                    let translation = target_translation
+                        + rest_rotation * (translation - source_translation) * length_ratio;
+                    Transform {
+                        scale: v.scale,
+                        rotation: Vector4::new(rotation.x, rotation.y, rotation.z, rotation.w),
+                        translation: Vector3::new(translation.x, translation.y, translation.z),
+                    }
+                })
+                .collect();
+
+            Some(NodeData {
+                name: target_bone.name.clone(),
+                tracks: vec![TrackData {
+                    values: TrackValues::Transform(values),
+                    ..track.clone()
+                }],
+            })
+        })
+        .collect();
+
+    AnimData {
+        major_version: source_anim.major_version,
+        minor_version: source_anim.minor_version,
+        final_frame_index: source_anim.final_frame_index,
+        groups: std::iter::once(GroupData {
+            group_type: GroupType::Transform,
+            nodes,
+        })
+        .chain(
+            source_anim
+                .groups
+                .iter()
+                .filter(|g| g.group_type != GroupType::Transform)
+                .cloned(),
+        )
+        .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{bone, skel};
+    use ssbh_data::anim_data::TransformFlags;
+
+    #[test]
+    fn auto_bone_map_ignore_case() {
+        let source = skel(vec![
+            bone("Hip", None, glam::Mat4::IDENTITY),
+            bone("ArmL", None, glam::Mat4::IDENTITY),
+        ]);
+        let target = skel(vec![
+            bone("Hip", None, glam::Mat4::IDENTITY),
+            bone("arml", None, glam::Mat4::IDENTITY),
+            bone("Tail", None, glam::Mat4::IDENTITY),
+        ]);
+
+        assert_eq!(
+            BTreeMap::from([
+                ("Hip".to_owned(), "Hip".to_owned()),
+                ("arml".to_owned(), "ArmL".to_owned()),
+            ]),
+            auto_bone_map(&source, &target)
+        );
+    }
+
+    #[test]
+    fn retarget_rotation_and_translation_offset() {
+        let source_skel = skel(vec![bone(
+            "ArmL",
+            None,
+            glam::Mat4::from_translation(glam::vec3(2.0, 0.0, 0.0)),
+        )]);
+        let target_skel = skel(vec![bone(
+            "L_Arm",
+            None,
+            glam::Mat4::from_rotation_translation(
+                glam::Quat::from_rotation_z(1.0),
+                glam::vec3(0.0, 3.0, 0.0),
+            ),
+        )]);
+
+        let rotation = glam::Quat::from_rotation_x(0.5);
+        let source_anim = AnimData {
+            major_version: 2,
+            minor_version: 0,
+            final_frame_index: 0.0,
+            groups: vec![GroupData {
+                group_type: GroupType::Transform,
+                nodes: vec![NodeData {
+                    name: "ArmL".to_owned(),
+                    tracks: vec![TrackData {
+                        name: "Transform".to_owned(),
+                        compensate_scale: false,
+                        transform_flags: TransformFlags::default(),
+                        values: TrackValues::Transform(vec![Transform {
+                            scale: Vector3::new(1.0, 1.0, 1.0),
+                            rotation: Vector4::new(rotation.x, rotation.y, rotation.z, rotation.w),
+                            translation: Vector3::new(3.0, 0.0, 0.0),
+                        }]),
+                    }],
+                }],
+            }],
+        };
+
+        let bone_map = BTreeMap::from([("L_Arm".to_owned(), "ArmL".to_owned())]);
+        let anim = retarget_anim(&source_anim, &source_skel, &target_skel, &bone_map);
+
+        let node = &anim.groups[0].nodes[0];
+        assert_eq!("L_Arm", node.name);
+        let TrackValues::Transform(values) = &node.tracks[0].values else {
+            panic!()
+        };
+
+        let expected = glam::Quat::from_rotation_z(1.0) * rotation;
+        let r = &values[0].rotation;
+        assert!((r.x - expected.x).abs() < 1e-6);
+        assert!((r.y - expected.y).abs() < 1e-6);
+        assert!((r.z - expected.z).abs() < 1e-6);
+        assert!((r.w - expected.w).abs() < 1e-6);
+
+        // The offset of 1.0 from the source rest pose is scaled by 3.0 / 2.0.
+        // The offset is rotated to match the rotated target rest pose.
+        let offset = glam::Quat::from_rotation_z(1.0) * glam::vec3(1.5, 0.0, 0.0);
+        let t = &values[0].translation;
+        assert!((t.x - offset.x).abs() < 1e-6);
+        assert!((t.y - (3.0 + offset.y)).abs() < 1e-6);
+        assert!((t.z - offset.z).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,336 @@
+use egui::{
+    Align2, Button, FontId, Label, PointerButton, Pos2, Rect, ScrollArea, Sense, Stroke,
+    StrokeKind, Vec2,
+};
+use ssbh_data::{
+    anim_data::{GroupType, TrackValues},
+    prelude::*,
+};
+
+use super::{
+    frames::frame_count,
+    nodes::{add_track, node_names, visibility_track},
+};
+use crate::app::VisibilityTimelineState;
+
+const NAME_WIDTH: f32 = 200.0;
+const ROW_HEIGHT: f32 = 18.0;
+const FRAME_WIDTH: f32 = 10.0;
+
+pub fn timeline_view(
+    ui: &mut egui::Ui,
+    anim: &mut AnimData,
+    state: &mut VisibilityTimelineState,
+    mesh: Option<&MeshData>,
+) -> bool {
+    let mut changed = false;
+
+    // Mesh objects with the same name share a visibility node.
+    let existing_names: Vec<_> = visibility_rows(anim)
+        .into_iter()
+        .map(|(_, name, _)| name)
+        .collect();
+    let missing_names: Vec<_> = node_names(GroupType::Visibility, None, mesh, None)
+        .into_iter()
+        .filter(|name| !existing_names.contains(name))
+        .collect();
+
+    ui.horizontal(|ui| {
+        ui.add_enabled_ui(!missing_names.is_empty(), |ui| {
+            ui.menu_button("Add Mesh Object", |ui| {
+                if ui.button("All Missing Objects").clicked() {
+                    for name in &missing_names {
+                        add_track(anim, GroupType::Visibility, name, visibility_track());
+                    }
+                    changed = true;
+                }
+                ui.separator();
+
+                ScrollArea::vertical().max_height(300.0).show(ui, |ui| {
+                    for name in &missing_names {
+                        if ui.button(name).clicked() {
+                            add_track(anim, GroupType::Visibility, name, visibility_track());
+                            changed = true;
+                        }
+                    }
+                });
+            })
+            .response
+            .on_disabled_hover_text("All mesh objects in the model.numshb have visibility tracks.");
+        });
+    });
+    ui.weak("Drag to paint visible or hidden frames. Hold Shift while dragging to select frames to copy. Right click a row to copy or paste.");
+    ui.separator();
+
+    let frame_count = visibility_rows(anim)
+        .iter()
+        .map(|(_, _, values)| values.len())
+        .max()
+        .unwrap_or_default()
+        .max(frame_count(anim));
+
+    let rows: Vec<_> = visibility_rows(anim)
+        .into_iter()
+        .map(|(index, name, _)| (index, name))
+        .collect();
+
+    ScrollArea::both().auto_shrink([false; 2]).show(ui, |ui| {
+        frame_header(ui, frame_count);
+
+        for ((g, n, t), name) in rows {
+            let Some(TrackValues::Boolean(values)) = anim
+                .groups
+                .get_mut(g)
+                .and_then(|group| group.nodes.get_mut(n))
+                .and_then(|node| node.tracks.get_mut(t))
+                .map(|track| &mut track.values)
+            else {
+                continue;
+            };
+
+            ui.horizontal(|ui| {
+                ui.add_sized([NAME_WIDTH, ROW_HEIGHT], Label::new(&name).truncate());
+                changed |= timeline_row(ui, (g, n), values, frame_count, state);
+            });
+        }
+    });
+
+    changed
+}
+
+/// The indices, node name, and values for each visibility track.
+fn visibility_rows(anim: &AnimData) -> Vec<((usize, usize, usize), String, &[bool])> {
+    anim.groups
+        .iter()
+        .enumerate()
+        .filter(|(_, group)| group.group_type == GroupType::Visibility)
+        .flat_map(|(g, group)| {
+            group.nodes.iter().enumerate().filter_map(move |(n, node)| {
+                node.tracks
+                    .iter()
+                    .enumerate()
+                    .find_map(|(t, track)| match &track.values {
+                        TrackValues::Boolean(values) => {
+                            Some(((g, n, t), node.name.clone(), values.as_slice()))
+                        }
+                        _ => None,
+                    })
+            })
+        })
+        .collect()
+}
+
+fn frame_header(ui: &mut egui::Ui, frame_count: usize) {
+    ui.horizontal(|ui| {
+        ui.add_space(NAME_WIDTH + ui.spacing().item_spacing.x);
+        let (rect, _) = ui.allocate_exact_size(
+            Vec2::new(frame_count as f32 * FRAME_WIDTH, ROW_HEIGHT),
+            Sense::hover(),
+        );
+        for frame in (0..frame_count).step_by(10) {
+            ui.painter().text(
+                Pos2::new(rect.left() + frame as f32 * FRAME_WIDTH, rect.center().y),
+                Align2::LEFT_CENTER,
+                frame.to_string(),
+                FontId::monospace(10.0),
+                ui.visuals().text_color(),
+            );
+        }
+    });
+}
+
+fn timeline_row(
+    ui: &mut egui::Ui,
+    node: (usize, usize),
+    values: &mut Vec<bool>,
+    frame_count: usize,
+    state: &mut VisibilityTimelineState,
+) -> bool {
+    let mut changed = false;
+
+    let (rect, response) = ui.allocate_exact_size(
+        Vec2::new(frame_count as f32 * FRAME_WIDTH, ROW_HEIGHT),
+        Sense::click_and_drag(),
+    );
+    let frame_at = |pos: Pos2| {
+        let frame = ((pos.x - rect.left()) / FRAME_WIDTH).floor().max(0.0) as usize;
+        frame.min(frame_count.saturating_sub(1))
+    };
+
+    if response.drag_started_by(PointerButton::Primary)
+        && let Some(pos) = response.interact_pointer_pos()
+    {
+        let frame = frame_at(pos);
+        if ui.input(|i| i.modifiers.shift) {
+            state.selection = Some((node.0, node.1, frame, frame));
+            state.paint = None;
+        } else {
+            // Paint the opposite of the value under the cursor.
+            state.paint = Some((!is_visible(values, frame), frame));
+        }
+    }
+
+    if response.dragged_by(PointerButton::Primary)
+        && let Some(pos) = response.interact_pointer_pos()
+    {
+        let frame = frame_at(pos);
+        if let Some((visible, previous)) = state.paint {
+            // Fill any frames skipped by moving the cursor quickly.
+            for f in frame.min(previous)..=frame.max(previous) {
+                set_visible(values, f, visible, frame_count);
+            }
+            state.paint = Some((visible, frame));
+            changed = true;
+        } else if let Some((g, n, _, end)) = &mut state.selection
+            && (*g, *n) == node
+        {
+            *end = frame;
+        }
+    }
+
+    if response.drag_stopped() {
+        state.paint = None;
+    }
+
+    if response.clicked()
+        && let Some(pos) = response.interact_pointer_pos()
+    {
+        let frame = frame_at(pos);
+        if ui.input(|i| i.modifiers.shift) {
+            state.selection = Some((node.0, node.1, frame, frame));
+        } else {
+            let visible = is_visible(values, frame);
+            set_visible(values, frame, !visible, frame_count);
+            changed = true;
+        }
+    }
+
+    let selection = state
+        .selection
+        .filter(|(g, n, _, _)| (*g, *n) == node)
+        .map(|(_, _, start, end)| (start.min(end), start.max(end)));
+
+    response.context_menu(|ui| {
+        let (start, end) = selection.unwrap_or((0, frame_count.saturating_sub(1)));
+        let label = if selection.is_some() {
+            "Copy Selected Frames"
+        } else {
+            "Copy All Frames"
+        };
+        if ui.button(label).clicked() {
+            state.copied = (start..=end).map(|f| is_visible(values, f)).collect();
+        }
+
+        if ui
+            .add_enabled(!state.copied.is_empty(), Button::new("Paste"))
+            .on_hover_text("Paste starting at the first selected frame or frame 0.")
+            .clicked()
+        {
+            for (i, visible) in state.copied.iter().enumerate() {
+                if start + i < frame_count {
+                    set_visible(values, start + i, *visible, frame_count);
+                }
+            }
+            changed = true;
+        }
+    });
+
+    // Draw spans of visible frames.
+    let painter = ui.painter_at(rect);
+    painter.rect_filled(rect, 0.0, ui.visuals().extreme_bg_color);
+    for (start, end) in visible_spans(values, frame_count) {
+        painter.rect_filled(
+            Rect::from_min_max(
+                Pos2::new(rect.left() + start as f32 * FRAME_WIDTH, rect.top() + 1.0),
+                Pos2::new(
+                    rect.left() + (end + 1) as f32 * FRAME_WIDTH,
+                    rect.bottom() - 1.0,
+                ),
+            ),
+            2.0,
+            ui.visuals().selection.bg_fill,
+        );
+    }
+    if let Some((start, end)) = selection {
+        painter.rect_stroke(
+            Rect::from_min_max(
+                Pos2::new(rect.left() + start as f32 * FRAME_WIDTH, rect.top()),
+                Pos2::new(rect.left() + (end + 1) as f32 * FRAME_WIDTH, rect.bottom()),
+            ),
+            0.0,
+            Stroke::new(2.0, ui.visuals().strong_text_color()),
+            StrokeKind::Inside,
+        );
+    }
+
+    response.on_hover_ui_at_pointer(|ui| {
+        if let Some(pos) = ui.ctx().pointer_hover_pos() {
+            let frame = frame_at(pos);
+            let visible = if is_visible(values, frame) {
+                "visible"
+            } else {
+                "hidden"
+            };
+            ui.label(format!("frame {frame}: {visible}"));
+        }
+    });
+
+    changed
+}
+
+/// Values past the end of the track use the last value like in game.
+fn is_visible(values: &[bool], frame: usize) -> bool {
+    values.get(frame).or(values.last()).copied().unwrap_or(true)
+}
+
+fn set_visible(values: &mut Vec<bool>, frame: usize, visible: bool, frame_count: usize) {
+    // Constant tracks need a value for each frame before they can be edited.
+    if values.len() < frame_count {
+        let last = values.last().copied().unwrap_or(true);
+        values.resize(frame_count, last);
+    }
+    if let Some(value) = values.get_mut(frame) {
+        *value = visible;
+    }
+}
+
+/// The inclusive start and end frame for each span of visible frames.
+fn visible_spans(values: &[bool], frame_count: usize) -> Vec<(usize, usize)> {
+    let mut spans = Vec::new();
+    let mut start = None;
+    for frame in 0..frame_count {
+        match (is_visible(values, frame), start) {
+            (true, None) => start = Some(frame),
+            (false, Some(s)) => {
+                spans.push((s, frame - 1));
+                start = None;
+            }
+            _ => (),
+        }
+    }
+    if let Some(s) = start {
+        spans.push((s, frame_count - 1));
+    }
+    spans
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn visible_spans_extend_last_value() {
+        assert_eq!(
+            vec![(0, 1), (3, 5)],
+            visible_spans(&[true, true, false, true], 6)
+        );
+        assert!(visible_spans(&[false], 4).is_empty());
+    }
+
+    #[test]
+    fn set_visible_constant_track() {
+        let mut values = vec![true];
+        set_visible(&mut values, 2, false, 4);
+        assert_eq!(vec![true, true, false, true], values);
+    }
+}
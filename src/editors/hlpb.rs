@@ -3,7 +3,7 @@ use std::path::Path;
 use crate::{
     EditorResponse,
     app::{HlpbEditorState, HlpbEditorTab},
-    horizontal_separator_empty,
+    export_json, horizontal_separator_empty, import_json,
     path::folder_editor_title,
     save_file, save_file_as,
     widgets::{DragSlider, bone_combo_box},
@@ -163,6 +163,15 @@ fn menu_bar(
             if ui.button("Save As...").clicked() {
                 saved |= save_file_as(hlpb, folder_name, file_name, "Hlpb", "nuhlpb");
             }
+
+            ui.separator();
+            if ui.button("Import JSON...").clicked() {
+                changed |= import_json(hlpb, folder_name);
+            }
+
+            if ui.button("Export JSON...").clicked() {
+                export_json(hlpb, folder_name, file_name);
+            }
         });
 
         ui.menu_button("Constraint", |ui| {
@@ -1,10 +1,10 @@
 use crate::{
-    EditorResponse,
+    EditorMessage, EditorResponse,
     app::{
-        MatlEditorState, PresetMode, UiState, display_validation_errors, draggable_icon,
-        warning_icon, warning_icon_text,
+        MatlCompareSource, MatlEditorState, PresetMode, UiState, display_validation_errors,
+        draggable_icon, warning_icon, warning_icon_text,
     },
-    horizontal_separator_empty,
+    export_json, horizontal_separator_empty, import_json,
     material::*,
     path::{folder_editor_title, presets_file},
     presets::{load_json_presets, load_xml_presets},
@@ -26,6 +26,10 @@ use ssbh_wgpu::{ShaderDatabase, ShaderProgram};
 use std::path::Path;
 use strum::IntoEnumIterator;
 
+mod compare;
+use compare::copy_param;
+pub use compare::matl_entry_diff;
+
 const UNUSED_PARAM: &str =
     "This parameter is not required by the shader and will be ignored in game.";
 
@@ -36,7 +40,7 @@ pub fn matl_editor(
     file_name: &str,
     state: &mut MatlEditorState,
     matl: &mut MatlData,
-    modl: Option<&mut ModlData>,
+    modl: Option<&ModlData>,
     validation_errors: &[MatlValidationError],
     folder_thumbnails: &[Thumbnail],
     default_thumbnails: &[Thumbnail],
@@ -50,6 +54,7 @@ pub fn matl_editor(
     let mut open = true;
     let mut changed = false;
     let mut saved = false;
+    let mut message = None;
 
     let title = folder_editor_title(folder_name, file_name);
     Window::new(format!("Matl Editor ({title})"))
@@ -61,7 +66,7 @@ pub fn matl_editor(
                 let (menu_changed, menu_saved) = menu_bar(
                     ui,
                     matl,
-                    &modl,
+                    modl,
                     state,
                     material_presets,
                     folder_name,
@@ -83,6 +88,7 @@ pub fn matl_editor(
                                 dark_mode,
                                 validation_errors,
                                 state,
+                                &mut message,
                             );
                         });
                 });
@@ -103,6 +109,8 @@ pub fn matl_editor(
                 }
                 changed |= preset_changed;
 
+                changed |= compare_window(ctx, state, matl, material_presets, default_presets);
+
                 ScrollArea::vertical()
                     .auto_shrink([false; 2])
                     .show(ui, |ui| {
@@ -111,7 +119,7 @@ pub fn matl_editor(
                                 ctx,
                                 ui,
                                 entry,
-                                modl,
+                                Some(&mut message),
                                 validation_errors,
                                 folder_thumbnails,
                                 default_thumbnails,
@@ -129,7 +137,7 @@ pub fn matl_editor(
         open,
         changed,
         saved,
-        message: None,
+        message,
     }
 }
 
@@ -139,6 +147,7 @@ fn select_material_dnd(
     dark_mode: bool,
     validation_errors: &[MatlValidationError],
     state: &mut MatlEditorState,
+    message: &mut Option<EditorMessage>,
 ) -> bool {
     let mut changed = false;
 
@@ -187,6 +196,21 @@ fn select_material_dnd(
             }
 
             response.context_menu(|ui| {
+                if ui
+                    .button("Rename...")
+                    .on_hover_text("Rename the material and update the numdlb, anims, and presets.")
+                    .clicked()
+                {
+                    *message = Some(EditorMessage::RenameMaterial {
+                        material_label: entry.material_label.clone(),
+                    });
+                }
+
+                if ui.button("Compare With...").clicked() {
+                    state.selected_material_index = *item_index;
+                    state.compare.is_open = true;
+                }
+
                 // TODO: Also add a menu option?
                 if ui.button("Delete").clicked() {
                     index_to_delete = Some(*item_index);
@@ -416,27 +440,11 @@ fn load_presets_from_file<F: Fn(&[u8]) -> anyhow::Result<Vec<MatlEntryData>>>(
     }
 }
 
-fn save_material_presets(presets: &[MatlEntryData], file: std::path::PathBuf) {
-    // TODO: Give a visual indication that the file saved?
-    match serde_json::to_string_pretty(&MatlData {
-        major_version: 1,
-        minor_version: 6,
-        entries: presets.to_vec(),
-    }) {
-        Ok(presets_json) => {
-            if let Err(e) = std::fs::write(file, presets_json) {
-                error!("Failed to save material presets JSON: {e}");
-            }
-        }
-        Err(e) => error!("Failed to convert material presets to JSON: {e}"),
-    }
-}
-
 fn edit_matl_entry(
     ctx: &Context,
     ui: &mut Ui,
     entry: &mut MatlEntryData,
-    modl: Option<&mut ModlData>,
+    message: Option<&mut Option<EditorMessage>>,
     validation_errors: &[MatlValidationError],
     folder_thumbnails: &[Thumbnail],
     default_thumbnails: &[Thumbnail],
@@ -455,16 +463,7 @@ fn edit_matl_entry(
 
     ui.horizontal(|ui| {
         ui.label("Material Label");
-        let mut modl_entries: Vec<_> = modl
-            .map(|modl| {
-                modl.entries
-                    .iter_mut()
-                    .filter(|e| e.material_label == entry.material_label)
-                    .collect()
-            })
-            .unwrap_or_default();
-
-        changed |= edit_material_label(entry, ui, &mut modl_entries);
+        changed |= edit_material_label(entry, ui, message);
     });
 
     changed |= edit_matl_entry_inner(
@@ -542,6 +541,190 @@ fn preset_window(
     (open, changed)
 }
 
+fn compare_window(
+    ctx: &egui::Context,
+    state: &mut MatlEditorState,
+    matl: &mut MatlData,
+    material_presets: &[MatlEntryData],
+    default_presets: &[MatlEntryData],
+) -> bool {
+    let mut changed = false;
+
+    Window::new("Compare Materials")
+        .open(&mut state.compare.is_open)
+        .resizable(true)
+        .default_width(600.0)
+        .show(ctx, |ui| {
+            let mut reference = None;
+            Grid::new("matl_compare_select").show(ui, |ui| {
+                ui.label("Material");
+                material_combo_box(
+                    ui,
+                    "matl_compare_material",
+                    &mut state.selected_material_index,
+                    &matl.entries,
+                );
+                ui.end_row();
+
+                ui.label("Reference Source");
+                ui.horizontal(|ui| {
+                    let source = &mut state.compare.source;
+                    let old_source = *source;
+                    ui.radio_value(source, MatlCompareSource::Matl, "This File");
+                    ui.radio_value(source, MatlCompareSource::File, "Other File");
+                    ui.radio_value(source, MatlCompareSource::UserPresets, "User Presets");
+                    ui.radio_value(source, MatlCompareSource::DefaultPresets, "Default Presets");
+                    if *source != old_source {
+                        state.compare.reference_index = 0;
+                    }
+                });
+                ui.end_row();
+
+                if state.compare.source == MatlCompareSource::File {
+                    ui.label("Reference File");
+                    ui.horizontal(|ui| {
+                        if let Some((path, _)) = &state.compare.reference_file {
+                            ui.label(
+                                path.file_name()
+                                    .map(|f| f.to_string_lossy().to_string())
+                                    .unwrap_or_default(),
+                            )
+                            .on_hover_text(path.to_string_lossy());
+                        }
+
+                        if ui.button("Open...").clicked()
+                            && let Some(file) = FileDialog::new()
+                                .add_filter("Matl", &["numatb"])
+                                .pick_file()
+                        {
+                            match MatlData::from_file(&file) {
+                                Ok(reference) => {
+                                    state.compare.reference_file = Some((file, reference));
+                                    state.compare.reference_index = 0;
+                                }
+                                Err(e) => error!("Failed to read {file:?}: {e}"),
+                            }
+                        }
+                    });
+                    ui.end_row();
+                }
+
+                let reference_entries = match state.compare.source {
+                    MatlCompareSource::Matl => &matl.entries[..],
+                    MatlCompareSource::File => state
+                        .compare
+                        .reference_file
+                        .as_ref()
+                        .map(|(_, m)| &m.entries[..])
+                        .unwrap_or_default(),
+                    MatlCompareSource::UserPresets => material_presets,
+                    MatlCompareSource::DefaultPresets => default_presets,
+                };
+                ui.label("Reference");
+                material_combo_box(
+                    ui,
+                    "matl_compare_reference",
+                    &mut state.compare.reference_index,
+                    reference_entries,
+                );
+                ui.end_row();
+
+                // Clone the reference since it may be in the same file as the material.
+                reference = reference_entries
+                    .get(state.compare.reference_index)
+                    .cloned();
+            });
+            ui.separator();
+
+            let (Some(entry), Some(reference)) = (
+                matl.entries.get_mut(state.selected_material_index),
+                reference,
+            ) else {
+                ui.label("Select a material and a reference material to compare.");
+                return;
+            };
+
+            let diffs = matl_entry_diff(entry, &reference);
+            if diffs.is_empty() && entry.shader_label == reference.shader_label {
+                ui.label("No differences found.");
+                return;
+            }
+
+            ScrollArea::vertical()
+                .auto_shrink([false; 2])
+                .max_height(500.0)
+                .show(ui, |ui| {
+                    Grid::new("matl_compare_grid").striped(true).show(ui, |ui| {
+                        ui.heading("Parameter");
+                        ui.heading("Material");
+                        ui.heading("Reference");
+                        ui.end_row();
+
+                        let missing = || RichText::new("Missing").weak();
+
+                        if entry.shader_label != reference.shader_label {
+                            ui.label("Shader Label");
+                            ui.label(&entry.shader_label);
+                            ui.label(&reference.shader_label);
+                            if ui.button("Copy").clicked() {
+                                entry.shader_label = reference.shader_label.clone();
+                                changed = true;
+                            }
+                            ui.end_row();
+                        }
+
+                        for diff in diffs {
+                            ui.label(param_label(diff.param_id));
+                            match diff.value {
+                                Some(value) => ui.label(value),
+                                None => ui.label(missing()),
+                            };
+
+                            // Parameters missing from the reference can only be removed.
+                            let text = match diff.reference_value {
+                                Some(value) => {
+                                    ui.label(value);
+                                    "Copy"
+                                }
+                                None => {
+                                    ui.label(missing());
+                                    "Remove"
+                                }
+                            };
+                            if ui.button(text).clicked() {
+                                copy_param(entry, &reference, diff.param_id);
+                                changed = true;
+                            }
+                            ui.end_row();
+                        }
+                    });
+                });
+        });
+
+    changed
+}
+
+fn material_combo_box(
+    ui: &mut Ui,
+    id_salt: &str,
+    selected_index: &mut usize,
+    entries: &[MatlEntryData],
+) {
+    ComboBox::from_id_salt(id_salt)
+        .width(300.0)
+        .selected_text(
+            entries
+                .get(*selected_index)
+                .map(|e| e.material_label.as_str())
+                .unwrap_or_default(),
+        )
+        .show_ui(ui, |ui| {
+            for (i, entry) in entries.iter().enumerate() {
+                ui.selectable_value(selected_index, i, &entry.material_label);
+            }
+        });
+}
+
 fn list_presets(
     ui: &mut Ui,
     material_presets: &[MatlEntryData],
@@ -585,7 +768,7 @@ fn program_attributes(program: &ShaderProgram) -> String {
 fn menu_bar(
     ui: &mut Ui,
     matl: &mut MatlData,
-    modl: &Option<&mut ModlData>,
+    modl: Option<&ModlData>,
     state: &mut MatlEditorState,
     material_presets: &mut Vec<MatlEntryData>,
     folder_name: &Path,
@@ -603,6 +786,15 @@ fn menu_bar(
             if ui.button("Save As...").clicked() {
                 saved |= save_file_as(matl, folder_name, file_name, "Matl", "numatb");
             }
+
+            ui.separator();
+            if ui.button("Import JSON...").clicked() {
+                changed |= import_json(matl, folder_name);
+            }
+
+            if ui.button("Export JSON...").clicked() {
+                export_json(matl, folder_name, file_name);
+            }
         });
 
         ui.menu_button("Material", |ui| {
@@ -642,6 +834,10 @@ fn menu_bar(
                 state.matl_preset_window_open = true;
                 changed = true;
             }
+
+            if button(ui, "Compare With...").clicked() {
+                state.compare.is_open = true;
+            }
             ui.separator();
 
             if ui.button("Remove Duplicates").clicked() {
@@ -724,25 +920,32 @@ fn help_menu(ui: &mut Ui) {
 fn edit_material_label(
     entry: &mut MatlEntryData,
     ui: &mut Ui,
-    modl_entries: &mut [&mut ModlEntryData],
+    message: Option<&mut Option<EditorMessage>>,
 ) -> bool {
-    // TODO: Get this to work with lost_focus for efficiency.
-    // TODO: Show errors if these checks fail?
-    let response = ui.add_sized(
-        egui::Vec2::new(ui.available_width(), 20.0),
-        egui::TextEdit::singleline(&mut entry.material_label),
-    );
-
-    let changed = response.changed();
-    if changed {
-        // Rename any effected modl entries if the material label changes.
-        // Keep track of modl entries since materials may be renamed.
-        for modl_entry in modl_entries {
-            modl_entry.material_label.clone_from(&entry.material_label);
+    match message {
+        Some(message) => {
+            // Renaming updates references in other files, so don't edit the label directly.
+            ui.label(&entry.material_label);
+            if ui
+                .button("Rename...")
+                .on_hover_text("Rename the material and update the numdlb, anims, and presets.")
+                .clicked()
+            {
+                *message = Some(EditorMessage::RenameMaterial {
+                    material_label: entry.material_label.clone(),
+                });
+            }
+            false
+        }
+        None => {
+            // Presets aren't referenced by other files.
+            ui.add_sized(
+                egui::Vec2::new(ui.available_width(), 20.0),
+                egui::TextEdit::singleline(&mut entry.material_label),
+            )
+            .changed()
         }
     }
-
-    changed
 }
 
 fn edit_shader_label(
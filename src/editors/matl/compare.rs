@@ -0,0 +1,173 @@
+use std::fmt::Debug;
+
+use ssbh_data::matl_data::*;
+
+/// A parameter with a different value in a material and a reference material.
+#[derive(Debug, PartialEq)]
+pub struct ParamDiff {
+    pub param_id: ParamId,
+    /// The value in the material or `None` if the parameter is missing.
+    pub value: Option<String>,
+    /// The value in the reference or `None` if the parameter is extra.
+    pub reference_value: Option<String>,
+}
+
+/// Find the parameters that differ between `entry` and `reference` matched by [ParamId].
+pub fn matl_entry_diff(entry: &MatlEntryData, reference: &MatlEntryData) -> Vec<ParamDiff> {
+    let mut diffs = Vec::new();
+    param_diffs(&mut diffs, &entry.booleans, &reference.booleans);
+    param_diffs(&mut diffs, &entry.floats, &reference.floats);
+    param_diffs(&mut diffs, &entry.vectors, &reference.vectors);
+    param_diffs(&mut diffs, &entry.textures, &reference.textures);
+    param_diffs(&mut diffs, &entry.samplers, &reference.samplers);
+    param_diffs(&mut diffs, &entry.blend_states, &reference.blend_states);
+    param_diffs(
+        &mut diffs,
+        &entry.rasterizer_states,
+        &reference.rasterizer_states,
+    );
+    param_diffs(&mut diffs, &entry.uv_transforms, &reference.uv_transforms);
+    diffs
+}
+
+fn param_diffs<T: PartialEq + Debug>(
+    diffs: &mut Vec<ParamDiff>,
+    params: &[ParamData<T>],
+    reference_params: &[ParamData<T>],
+) {
+    for p in params {
+        let reference = reference_params.iter().find(|r| r.param_id == p.param_id);
+        if reference.map(|r| &r.data) != Some(&p.data) {
+            diffs.push(ParamDiff {
+                param_id: p.param_id,
+                value: Some(format!("{:?}", p.data)),
+                reference_value: reference.map(|r| format!("{:?}", r.data)),
+            });
+        }
+    }
+
+    for r in reference_params {
+        if !params.iter().any(|p| p.param_id == r.param_id) {
+            diffs.push(ParamDiff {
+                param_id: r.param_id,
+                value: None,
+                reference_value: Some(format!("{:?}", r.data)),
+            });
+        }
+    }
+}
+
+/// Set the value for `param_id` to the value from `reference`.
+/// Parameters not present in `reference` are removed.
+pub fn copy_param(entry: &mut MatlEntryData, reference: &MatlEntryData, param_id: ParamId) {
+    copy_param_data(&mut entry.booleans, &reference.booleans, param_id);
+    copy_param_data(&mut entry.floats, &reference.floats, param_id);
+    copy_param_data(&mut entry.vectors, &reference.vectors, param_id);
+    copy_param_data(&mut entry.textures, &reference.textures, param_id);
+    copy_param_data(&mut entry.samplers, &reference.samplers, param_id);
+    copy_param_data(&mut entry.blend_states, &reference.blend_states, param_id);
+    copy_param_data(
+        &mut entry.rasterizer_states,
+        &reference.rasterizer_states,
+        param_id,
+    );
+    copy_param_data(&mut entry.uv_transforms, &reference.uv_transforms, param_id);
+}
+
+fn copy_param_data<T: Clone>(
+    params: &mut Vec<ParamData<T>>,
+    reference_params: &[ParamData<T>],
+    param_id: ParamId,
+) {
+    match reference_params.iter().find(|r| r.param_id == param_id) {
+        Some(r) => match params.iter_mut().find(|p| p.param_id == param_id) {
+            Some(p) => p.data = r.data.clone(),
+            None => {
+                params.push(r.clone());
+                // Sort the parameters to match Smash Ultimate's conventions.
+                params.sort_by_key(|p| p.param_id as u64);
+            }
+        },
+        None => params.retain(|p| p.param_id != param_id),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(floats: Vec<FloatParam>) -> MatlEntryData {
+        MatlEntryData {
+            material_label: "a".to_owned(),
+            shader_label: "SFX_PBS_0100000008008269_opaque".to_owned(),
+            blend_states: Vec::new(),
+            floats,
+            booleans: Vec::new(),
+            vectors: Vec::new(),
+            rasterizer_states: Vec::new(),
+            samplers: Vec::new(),
+            textures: Vec::new(),
+            uv_transforms: Vec::new(),
+        }
+    }
+
+    fn float(param_id: ParamId, data: f32) -> FloatParam {
+        FloatParam { param_id, data }
+    }
+
+    #[test]
+    fn diff_changed_missing_extra() {
+        let entry = entry(vec![
+            float(ParamId::CustomFloat0, 1.0),
+            float(ParamId::CustomFloat8, 0.5),
+        ]);
+        let reference = self::entry(vec![
+            float(ParamId::CustomFloat0, 1.0),
+            float(ParamId::CustomFloat1, 2.0),
+            float(ParamId::CustomFloat8, 0.7),
+        ]);
+
+        assert_eq!(
+            vec![
+                ParamDiff {
+                    param_id: ParamId::CustomFloat8,
+                    value: Some("0.5".to_owned()),
+                    reference_value: Some("0.7".to_owned()),
+                },
+                ParamDiff {
+                    param_id: ParamId::CustomFloat1,
+                    value: None,
+                    reference_value: Some("2.0".to_owned()),
+                },
+            ],
+            matl_entry_diff(&entry, &reference)
+        );
+        assert!(matl_entry_diff(&reference, &reference).is_empty());
+    }
+
+    #[test]
+    fn copy_changed_missing_extra() {
+        let mut entry = entry(vec![
+            float(ParamId::CustomFloat0, 1.0),
+            float(ParamId::CustomFloat8, 0.5),
+        ]);
+        let reference = self::entry(vec![
+            float(ParamId::CustomFloat1, 2.0),
+            float(ParamId::CustomFloat8, 0.7),
+        ]);
+
+        copy_param(&mut entry, &reference, ParamId::CustomFloat8);
+        copy_param(&mut entry, &reference, ParamId::CustomFloat1);
+        assert_eq!(
+            vec![
+                float(ParamId::CustomFloat0, 1.0),
+                float(ParamId::CustomFloat1, 2.0),
+                float(ParamId::CustomFloat8, 0.7),
+            ],
+            entry.floats
+        );
+
+        copy_param(&mut entry, &reference, ParamId::CustomFloat0);
+        assert_eq!(reference, entry);
+    }
+}
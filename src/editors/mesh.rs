@@ -1,34 +1,51 @@
 use crate::{
     EditorMessage, EditorResponse,
-    app::{MeshEditorState, display_validation_errors, draggable_icon, warning_icon_text},
-    horizontal_separator_empty,
+    app::{
+        ERROR_COLOR, MeshEditorState, MeshStatisticsColumn, MeshStatisticsState,
+        MeshTransformState, UvToolsState, WARNING_COLOR, WeightTransferMethod, WeightTransferState,
+        display_validation_errors, draggable_icon, warning_icon_text,
+    },
+    export_json, horizontal_separator_empty, import_json,
     path::folder_editor_title,
     save_file, save_file_as,
     validation::{MeshValidationError, MeshValidationErrorKind},
     widgets::bone_combo_box,
 };
 use egui::{
-    Button, CentralPanel, ComboBox, Grid, RichText, ScrollArea, SidePanel, TextEdit, TextWrapMode,
+    Button, CentralPanel, ComboBox, DragValue, Grid, RichText, ScrollArea, SidePanel, TextWrapMode,
     Ui, special_emojis::GITHUB,
 };
 use egui_dnd::dnd;
+use egui_extras::{Column, TableBuilder, TableRow};
 use log::error;
 use rfd::FileDialog;
 use ssbh_data::{
-    mesh_data::{
-        AttributeData, BoneInfluence, MeshObjectData, VectorData, VertexWeight, transform_points,
-        transform_vectors,
-    },
+    mesh_data::{AttributeData, BoneInfluence, MeshObjectData, VectorData, VertexWeight},
+    modl_data::ModlEntryData,
     prelude::*,
 };
 use std::path::Path;
 
+mod objects;
+mod stats;
+mod transform;
+mod uv;
+mod weights;
+use objects::{has_same_layout, merge_mesh_objects, split_mesh_object};
+use stats::{MeshObjectStats, mesh_object_stats, sort_stats, stats_csv, total_stats};
+use transform::transform_mesh_object;
+use uv::{flip_v, is_outside_unit_square, overlapping_triangles, scale_offset_uvs, uv_triangles};
+pub use weights::reassign_bone_influences;
+use weights::transfer_bone_influences;
+
 pub fn mesh_editor(
     ctx: &egui::Context,
     folder_name: &Path,
     file_name: &str,
     mesh: &mut MeshData,
     skel: Option<&SkelData>,
+    mut modl: Option<&mut ModlData>,
+    modl_changed: &mut bool,
     validation_errors: &[MeshValidationError],
     dark_mode: bool,
     state: &mut MeshEditorState,
@@ -53,6 +70,15 @@ pub fn mesh_editor(
                     if ui.button("Save As...").clicked() {
                         saved |= save_file_as(mesh, folder_name, file_name, "Mesh", "numshb");
                     }
+
+                    ui.separator();
+                    if ui.button("Import JSON...").clicked() {
+                        changed |= import_json(mesh, folder_name);
+                    }
+
+                    if ui.button("Export JSON...").clicked() {
+                        export_json(mesh, folder_name, file_name);
+                    }
                 });
 
                 ui.menu_button("Mesh", |ui| {
@@ -71,6 +97,23 @@ pub fn mesh_editor(
                             Err(e) => error!("Failed to read {file:?}: {e}"),
                         }
                     }
+
+                    if ui
+                        .add(
+                            Button::new("Transfer Weights From...").wrap_mode(TextWrapMode::Extend),
+                        )
+                        .clicked()
+                    {
+                        state.weight_transfer.is_open = true;
+                    }
+
+                    if ui.button("Transform...").clicked() {
+                        state.transform.is_open = true;
+                    }
+
+                    if ui.button("Statistics").clicked() {
+                        state.statistics.is_open = true;
+                    }
                 });
 
                 ui.menu_button("Help", |ui| {
@@ -93,6 +136,8 @@ pub fn mesh_editor(
                             changed |= select_mesh_object_dnd(
                                 ui,
                                 mesh,
+                                modl.as_deref_mut(),
+                                modl_changed,
                                 validation_errors,
                                 dark_mode,
                                 &mut message,
@@ -117,12 +162,23 @@ pub fn mesh_editor(
                                 skel,
                                 state.selected_index,
                                 &errors,
+                                &mut state.uv_tools,
+                                &mut message,
                             );
                         }
                     });
             });
         });
 
+    changed |= weight_transfer_window(ctx, mesh, state.selected_index, &mut state.weight_transfer);
+    changed |= transform_window(ctx, mesh, state.selected_index, &mut state.transform);
+    statistics_window(ctx, folder_name, file_name, mesh, &mut state.statistics);
+    uv_layout_window(
+        ctx,
+        mesh.objects.get(state.selected_index),
+        &mut state.uv_tools,
+    );
+
     EditorResponse {
         open,
         changed,
@@ -131,9 +187,337 @@ pub fn mesh_editor(
     }
 }
 
+fn weight_transfer_window(
+    ctx: &egui::Context,
+    mesh: &mut MeshData,
+    selected_index: usize,
+    state: &mut WeightTransferState,
+) -> bool {
+    let mut changed = false;
+
+    egui::Window::new("Transfer Weights")
+        .open(&mut state.is_open)
+        .resizable(false)
+        .show(ctx, |ui| {
+            let Some(target) = mesh.objects.get(selected_index) else {
+                ui.label("Select a mesh object to transfer weights to.");
+                return;
+            };
+
+            Grid::new("weight_transfer_grid").show(ui, |ui| {
+                ui.label("Target");
+                ui.label(&target.name);
+                ui.end_row();
+
+                ui.label("Reference Mesh");
+                ui.horizontal(|ui| {
+                    match &state.reference {
+                        Some((path, _)) => ui.label(
+                            path.file_name()
+                                .map(|f| f.to_string_lossy().to_string())
+                                .unwrap_or_default(),
+                        ),
+                        None => ui.label("Current Mesh"),
+                    };
+
+                    if ui.button("Open...").clicked()
+                        && let Some(file) = FileDialog::new()
+                            .add_filter("Mesh", &["numshb"])
+                            .pick_file()
+                    {
+                        match MeshData::from_file(&file) {
+                            Ok(reference) => {
+                                state.reference = Some((file, reference));
+                                state.source_index = None;
+                            }
+                            Err(e) => error!("Failed to read {file:?}: {e}"),
+                        }
+                    }
+
+                    if state.reference.is_some() && ui.button("Clear").clicked() {
+                        state.reference = None;
+                        state.source_index = None;
+                    }
+                });
+                ui.end_row();
+
+                let source_objects = state
+                    .reference
+                    .as_ref()
+                    .map(|(_, m)| &m.objects)
+                    .unwrap_or(&mesh.objects);
+
+                ui.label("Source Object");
+                ComboBox::from_id_salt("weight_transfer_source")
+                    .selected_text(
+                        state
+                            .source_index
+                            .and_then(|i| source_objects.get(i))
+                            .map(|o| o.name.as_str())
+                            .unwrap_or("All Objects"),
+                    )
+                    .show_ui(ui, |ui| {
+                        ui.selectable_value(&mut state.source_index, None, "All Objects");
+                        for (i, o) in source_objects.iter().enumerate() {
+                            ui.selectable_value(&mut state.source_index, Some(i), &o.name);
+                        }
+                    });
+                ui.end_row();
+
+                ui.label("Method");
+                ui.horizontal(|ui| {
+                    ui.radio_value(
+                        &mut state.method,
+                        WeightTransferMethod::NearestSurface,
+                        "Nearest Surface",
+                    )
+                    .on_hover_text("Blend the weights of the closest point on the reference faces.");
+                    ui.radio_value(
+                        &mut state.method,
+                        WeightTransferMethod::NearestVertex,
+                        "Nearest Vertex",
+                    )
+                    .on_hover_text("Copy the weights of the closest reference vertex.");
+                });
+                ui.end_row();
+            });
+
+            ui.checkbox(&mut state.match_names, "Only use objects with matching names")
+                .on_hover_text("Ignore reference mesh objects with a different name than the target.");
+            horizontal_separator_empty(ui);
+
+            if ui.button("Transfer").clicked() {
+                let source_objects = state
+                    .reference
+                    .as_ref()
+                    .map(|(_, m)| &m.objects)
+                    .unwrap_or(&mesh.objects);
+
+                // Objects in the current mesh shouldn't transfer weights to themselves.
+                let sources: Vec<_> = source_objects
+                    .iter()
+                    .enumerate()
+                    .filter(|(i, _)| state.reference.is_some() || *i != selected_index)
+                    .filter(|(i, _)| state.source_index.is_none_or(|s| s == *i))
+                    .filter(|(_, o)| !state.match_names || o.name == target.name)
+                    .filter(|(_, o)| !o.bone_influences.is_empty())
+                    .map(|(_, o)| o)
+                    .collect();
+
+                match transfer_bone_influences(target, &sources, state.method) {
+                    Some(bone_influences) => {
+                        let target = &mut mesh.objects[selected_index];
+                        target.bone_influences = bone_influences;
+                        // Meshes should have influences or a parent bone but not both.
+                        target.parent_bone_name = String::new();
+                        changed = true;
+                    }
+                    None => error!(
+                        "Failed to transfer weights to {:?}. No reference mesh objects have bone influences.",
+                        target.name
+                    ),
+                }
+            }
+        });
+
+    changed
+}
+
+fn transform_window(
+    ctx: &egui::Context,
+    mesh: &mut MeshData,
+    selected_index: usize,
+    state: &mut MeshTransformState,
+) -> bool {
+    let mut changed = false;
+
+    egui::Window::new("Transform Mesh")
+        .open(&mut state.is_open)
+        .resizable(false)
+        .show(ctx, |ui| {
+            Grid::new("mesh_transform_grid").show(ui, |ui| {
+                ui.label("Translation");
+                for v in &mut state.translation {
+                    ui.add(DragValue::new(v).speed(0.1));
+                }
+                ui.end_row();
+
+                ui.label("Rotation (Degrees)");
+                for v in &mut state.rotation_degrees {
+                    ui.add(DragValue::new(v).speed(1.0));
+                }
+                ui.end_row();
+
+                ui.label("Scale");
+                for v in &mut state.scale {
+                    ui.add(DragValue::new(v).speed(0.01));
+                }
+                ui.end_row();
+
+                ui.label("Mirror");
+                for (mirror, axis) in state.mirror.iter_mut().zip(["X", "Y", "Z"]) {
+                    ui.checkbox(mirror, axis);
+                }
+                ui.end_row();
+            });
+
+            ui.checkbox(&mut state.apply_to_all, "Apply to all mesh objects");
+            horizontal_separator_empty(ui);
+
+            ui.horizontal(|ui| {
+                if ui.button("Apply").clicked() {
+                    let transform = mesh_transform(state);
+                    for (i, mesh_object) in mesh.objects.iter_mut().enumerate() {
+                        if state.apply_to_all || i == selected_index {
+                            // Bounding information is recalculated from the positions when saving.
+                            transform_mesh_object(mesh_object, transform);
+                            changed = true;
+                        }
+                    }
+                }
+
+                if ui.button("Reset").clicked() {
+                    *state = MeshTransformState {
+                        is_open: true,
+                        ..Default::default()
+                    };
+                }
+            });
+        });
+
+    changed
+}
+
+fn statistics_window(
+    ctx: &egui::Context,
+    folder_name: &Path,
+    file_name: &str,
+    mesh: &MeshData,
+    state: &mut MeshStatisticsState,
+) {
+    let title = folder_editor_title(folder_name, file_name);
+    egui::Window::new(format!("Mesh Statistics ({title})"))
+        .open(&mut state.is_open)
+        .default_size(egui::Vec2::new(800.0, 400.0))
+        .resizable(true)
+        .show(ctx, |ui| {
+            // TODO: Cache this if it's too slow for large meshes.
+            let mut stats: Vec<_> = mesh.objects.iter().map(mesh_object_stats).collect();
+            sort_stats(&mut stats, state.sort_column, state.sort_descending);
+            let total = total_stats(&stats);
+
+            if ui.button("Export CSV...").clicked()
+                && let Some(file) = FileDialog::new().add_filter("CSV", &["csv"]).save_file()
+            {
+                let mut rows = stats.clone();
+                rows.push(total.clone());
+                if let Err(e) = std::fs::write(&file, stats_csv(&rows)) {
+                    error!("Failed to save {file:?}: {e}");
+                }
+            }
+            horizontal_separator_empty(ui);
+
+            let mut sort_header = |ui: &mut Ui, column: MeshStatisticsColumn, name: &str| {
+                let text = if state.sort_column == column {
+                    if state.sort_descending {
+                        format!("{name} ⏷")
+                    } else {
+                        format!("{name} ⏶")
+                    }
+                } else {
+                    name.to_string()
+                };
+                if ui.button(RichText::new(text).heading()).clicked() {
+                    if state.sort_column == column {
+                        state.sort_descending = !state.sort_descending;
+                    } else {
+                        state.sort_column = column;
+                        state.sort_descending = false;
+                    }
+                }
+            };
+
+            TableBuilder::new(ui)
+                .striped(true)
+                .column(Column::auto().at_least(150.0).resizable(true))
+                .columns(Column::auto().resizable(true), 2)
+                .column(Column::initial(250.0).clip(true).resizable(true))
+                .columns(Column::auto().resizable(true), 3)
+                .header(24.0, |mut header| {
+                    header.col(|ui| sort_header(ui, MeshStatisticsColumn::Name, "Name"));
+                    header.col(|ui| sort_header(ui, MeshStatisticsColumn::Vertices, "Vertices"));
+                    header.col(|ui| sort_header(ui, MeshStatisticsColumn::Triangles, "Triangles"));
+                    header.col(|ui| {
+                        ui.heading("Attributes");
+                    });
+                    header
+                        .col(|ui| sort_header(ui, MeshStatisticsColumn::Influences, "Influences"));
+                    header.col(|ui| {
+                        sort_header(ui, MeshStatisticsColumn::MaxInfluences, "Max Influences")
+                    });
+                    header.col(|ui| sort_header(ui, MeshStatisticsColumn::BufferSize, "Size"));
+                })
+                .body(|body| {
+                    body.rows(20.0, stats.len() + 1, |mut row| {
+                        // Always show the total in the last row.
+                        match stats.get(row.index()) {
+                            Some(s) => {
+                                statistics_row(&mut row, s, &format!("{} ({})", s.name, s.subindex))
+                            }
+                            None => statistics_row(&mut row, &total, "Total"),
+                        }
+                    });
+                });
+        });
+}
+
+fn statistics_row(row: &mut TableRow, s: &MeshObjectStats, name: &str) {
+    row.col(|ui| {
+        ui.label(name);
+    });
+    row.col(|ui| {
+        ui.label(s.vertex_count.to_string());
+    });
+    row.col(|ui| {
+        ui.label(s.triangle_count.to_string());
+    });
+    row.col(|ui| {
+        ui.label(s.attributes.join(", "))
+            .on_hover_text(s.attributes.join("\n"));
+    });
+    row.col(|ui| {
+        ui.label(s.influence_count.to_string());
+    });
+    row.col(|ui| {
+        ui.label(s.max_influences_per_vertex.to_string());
+    });
+    row.col(|ui| {
+        ui.label(format!("{:.1} KB", s.buffer_size as f32 / 1024.0))
+            .on_hover_text("Estimated size of the vertex, index, and skin weight buffers.");
+    });
+}
+
+fn mesh_transform(state: &MeshTransformState) -> glam::Mat4 {
+    let [rx, ry, rz] = state.rotation_degrees.map(f32::to_radians);
+    let scale: [f32; 3] = std::array::from_fn(|i| {
+        if state.mirror[i] {
+            -state.scale[i]
+        } else {
+            state.scale[i]
+        }
+    });
+    glam::Mat4::from_scale_rotation_translation(
+        glam::Vec3::from(scale),
+        glam::Quat::from_euler(glam::EulerRot::XYZ, rx, ry, rz),
+        glam::Vec3::from(state.translation),
+    )
+}
+
 fn select_mesh_object_dnd(
     ui: &mut Ui,
     mesh: &mut MeshData,
+    mut modl: Option<&mut ModlData>,
+    modl_changed: &mut bool,
     validation_errors: &[MeshValidationError],
     dark_mode: bool,
     message: &mut Option<EditorMessage>,
@@ -143,6 +527,9 @@ fn select_mesh_object_dnd(
 
     let mut mesh_to_duplicate = None;
     let mut mesh_to_remove = None;
+    let mut meshes_to_merge = None;
+    let mut mesh_to_split = None;
+    let mut mesh_to_rename = None;
 
     // TODO: Avoid allocating here.
     let mut item_indices: Vec<_> = (0..mesh.objects.len()).collect();
@@ -167,7 +554,20 @@ fn select_mesh_object_dnd(
                 RichText::new(&mesh_object.name)
             };
 
-            let header_response = ui.selectable_value(&mut state.selected_index, *item_index, text);
+            let is_selected =
+                state.selected_index == *item_index || state.merge_selection.contains(item_index);
+            let header_response = ui.selectable_label(is_selected, text);
+            if header_response.clicked() {
+                if ui.input(|i| i.modifiers.command) {
+                    // Ctrl+click selects additional objects to merge with the selected object.
+                    if !state.merge_selection.remove(item_index) {
+                        state.merge_selection.insert(*item_index);
+                    }
+                } else {
+                    state.selected_index = *item_index;
+                    state.merge_selection.clear();
+                }
+            }
 
             header_response.context_menu(|ui| {
                 if ui.button("Duplicate").clicked() {
@@ -175,10 +575,68 @@ fn select_mesh_object_dnd(
                     changed = true;
                 }
 
+                if ui.button("Rename...").clicked() {
+                    mesh_to_rename = Some(*item_index);
+                }
+
                 if ui.button("Delete").clicked() {
                     mesh_to_remove = Some(*item_index);
                     changed = true;
                 }
+
+                ui.menu_button("Merge With", |ui| {
+                    let target = &mesh.objects[*item_index];
+                    let selection: Vec<_> = state
+                        .merge_selection
+                        .iter()
+                        .copied()
+                        .filter(|i| {
+                            *i != *item_index
+                                && mesh
+                                    .objects
+                                    .get(*i)
+                                    .is_some_and(|o| has_same_layout(target, o))
+                        })
+                        .collect();
+                    if ui
+                        .add_enabled(!selection.is_empty(), Button::new("Selected Objects"))
+                        .on_disabled_hover_text("Ctrl+click objects in the list to select them.")
+                        .clicked()
+                    {
+                        meshes_to_merge = Some((*item_index, selection));
+                    }
+                    ui.separator();
+
+                    for (i, o) in mesh.objects.iter().enumerate() {
+                        if i != *item_index
+                            && has_same_layout(target, o)
+                            && ui.button(format!("{} ({})", o.name, o.subindex)).clicked()
+                        {
+                            meshes_to_merge = Some((*item_index, vec![i]));
+                        }
+                    }
+                })
+                .response
+                .on_hover_text("Append the vertices of objects with the same vertex attributes.");
+
+                ui.menu_button("Split By", |ui| {
+                    if ui.button("Connected Components").clicked() {
+                        mesh_to_split = Some((*item_index, None));
+                        changed = true;
+                    }
+
+                    let mesh_object = &mesh.objects[*item_index];
+                    for a in mesh_object
+                        .texture_coordinates
+                        .iter()
+                        .chain(&mesh_object.color_sets)
+                    {
+                        if ui.button(format!("{} Islands", a.name)).clicked() {
+                            mesh_to_split = Some((*item_index, Some(a.name.clone())));
+                            changed = true;
+                        }
+                    }
+                });
             });
 
             // Outline the selected mesh in the viewport.
@@ -199,19 +657,74 @@ fn select_mesh_object_dnd(
 
     if let Some(i) = mesh_to_duplicate {
         let mut duplicated_mesh = mesh.objects[i].clone();
-        duplicated_mesh.subindex = mesh
-            .objects
-            .iter()
-            .filter(|o| o.name == duplicated_mesh.name)
-            .map(|o| o.subindex)
-            .max()
-            .unwrap_or_default()
-            + 1;
+        duplicated_mesh.subindex = new_subindex(mesh, &duplicated_mesh.name);
+        if let Some(modl) = modl.as_deref_mut() {
+            *modl_changed |= add_modl_entry(modl, &mesh.objects[i], &duplicated_mesh);
+        }
         mesh.objects.insert(i + 1, duplicated_mesh);
+        state.merge_selection.clear();
     }
 
     if let Some(i) = mesh_to_remove {
-        mesh.objects.remove(i);
+        let removed = mesh.objects.remove(i);
+        if let Some(modl) = modl.as_deref_mut() {
+            *modl_changed |= remove_modl_entries(modl, &removed);
+        }
+        state.merge_selection.clear();
+    }
+
+    if let Some(i) = mesh_to_rename {
+        *message = Some(EditorMessage::RenameMeshObject {
+            mesh_object_name: mesh.objects[i].name.clone(),
+            mesh_object_subindex: mesh.objects[i].subindex,
+        });
+    }
+
+    if let Some((i, mut others)) = meshes_to_merge {
+        let merged = others
+            .iter()
+            .try_fold(mesh.objects[i].clone(), |merged, j| {
+                merge_mesh_objects(&merged, &mesh.objects[*j])
+            });
+        match merged {
+            Some(merged) => {
+                mesh.objects[i] = merged;
+
+                // Remove objects in reverse order to keep the remaining indices valid.
+                others.sort_unstable();
+                for j in others.iter().rev() {
+                    let removed = mesh.objects.remove(*j);
+                    if let Some(modl) = modl.as_deref_mut() {
+                        *modl_changed |= remove_modl_entries(modl, &removed);
+                    }
+                }
+                state.selected_index = i - others.iter().filter(|j| **j < i).count();
+                state.merge_selection.clear();
+                changed = true;
+            }
+            None => error!(
+                "Failed to merge {:?}. Mesh objects must have the same vertex attributes.",
+                mesh.objects[i].name
+            ),
+        }
+    }
+
+    if let Some((i, attribute_name)) = mesh_to_split {
+        let objects = split_mesh_object(&mesh.objects[i], attribute_name.as_deref());
+        if objects.len() > 1 {
+            // The first object keeps the original subindex to preserve existing assignments.
+            let original = mesh.objects.remove(i);
+            for (j, mut object) in objects.into_iter().enumerate() {
+                if j > 0 {
+                    object.subindex = new_subindex(mesh, &object.name);
+                    if let Some(modl) = modl.as_deref_mut() {
+                        *modl_changed |= add_modl_entry(modl, &original, &object);
+                    }
+                }
+                mesh.objects.insert(i + j, object);
+            }
+            state.merge_selection.clear();
+        }
     }
 
     if let Some(response) = response.final_update() {
@@ -220,18 +733,57 @@ fn select_mesh_object_dnd(
             .iter()
             .position(|i| *i == state.selected_index)
             .unwrap_or_default();
+        state.merge_selection.clear();
         changed = true;
     }
 
     changed
 }
 
+fn new_subindex(mesh: &MeshData, name: &str) -> u64 {
+    mesh.objects
+        .iter()
+        .filter(|o| o.name == name)
+        .map(|o| o.subindex)
+        .max()
+        .unwrap_or_default()
+        + 1
+}
+
+/// Returns `true` if an entry was added.
+fn add_modl_entry(modl: &mut ModlData, original: &MeshObjectData, new: &MeshObjectData) -> bool {
+    // Use the same material as the original object.
+    if let Some(entry) = modl.entries.iter().find(|e| {
+        e.mesh_object_name == original.name && e.mesh_object_subindex == original.subindex
+    }) {
+        modl.entries.push(ModlEntryData {
+            mesh_object_name: new.name.clone(),
+            mesh_object_subindex: new.subindex,
+            material_label: entry.material_label.clone(),
+        });
+        true
+    } else {
+        false
+    }
+}
+
+/// Returns `true` if any entries were removed.
+fn remove_modl_entries(modl: &mut ModlData, removed: &MeshObjectData) -> bool {
+    let count = modl.entries.len();
+    modl.entries.retain(|e| {
+        e.mesh_object_name != removed.name || e.mesh_object_subindex != removed.subindex
+    });
+    modl.entries.len() != count
+}
+
 fn edit_mesh_object(
     ui: &mut Ui,
     mesh_object: &mut MeshObjectData,
     skel: Option<&SkelData>,
     i: usize,
     errors: &[&MeshValidationError],
+    uv_tools: &mut UvToolsState,
+    message: &mut Option<EditorMessage>,
 ) -> bool {
     let mut changed = false;
 
@@ -240,25 +792,29 @@ fn edit_mesh_object(
     // TODO: Reorder mesh objects?
     // TODO: Show errors on the appropriate field?
     Grid::new("mesh_grid").show(ui, |ui| {
-        // TODO: Link name edits with the numdlb and numshexb.
-        // This will need to check for duplicate names.
+        // Renaming updates references in other files, so don't edit the name directly.
         ui.label("Name");
-        changed |= ui
-            .add(TextEdit::singleline(&mut mesh_object.name).clip_text(false))
-            .changed();
+        ui.horizontal(|ui| {
+            ui.label(&mesh_object.name);
+            if ui
+                .button("Rename...")
+                .on_hover_text("Rename the mesh object and update the numdlb, numshexb, and anims.")
+                .clicked()
+            {
+                *message = Some(EditorMessage::RenameMeshObject {
+                    mesh_object_name: mesh_object.name.clone(),
+                    mesh_object_subindex: mesh_object.subindex,
+                });
+            }
+        });
         ui.end_row();
 
-        // TODO: Is it possible to edit the subindex without messing up influence assignments?
         ui.label("Subindex");
-        changed |= ui
-            .add(egui::DragValue::new(&mut mesh_object.subindex))
-            .changed();
+        ui.label(mesh_object.subindex.to_string());
         ui.end_row();
 
         ui.label("Sort Bias");
-        changed |= ui
-            .add(egui::DragValue::new(&mut mesh_object.sort_bias))
-            .changed();
+        changed |= ui.add(DragValue::new(&mut mesh_object.sort_bias)).changed();
         ui.end_row();
     });
 
@@ -310,7 +866,7 @@ fn edit_mesh_object(
         .next()
         .unwrap_or_default();
 
-    changed |= edit_mesh_attributes(ui, mesh_object, missing_attributes);
+    changed |= edit_mesh_attributes(ui, mesh_object, missing_attributes, uv_tools);
     horizontal_separator_empty(ui);
 
     let has_influence_errors = errors.iter().any(|e| {
@@ -404,18 +960,10 @@ fn convert_parent_bone_to_influences(mesh_object: &mut MeshObjectData, skel: Opt
             .find(|b| b.name == mesh_object.parent_bone_name)
             .and_then(|b| s.calculate_world_transform(b).ok())
     }) {
-        for attribute in &mut mesh_object.positions {
-            attribute.data = transform_points(&attribute.data, &parent_transform);
-        }
-        for attribute in &mut mesh_object.normals {
-            attribute.data = transform_vectors(&attribute.data, &parent_transform);
-        }
-        for attribute in &mut mesh_object.tangents {
-            attribute.data = transform_vectors(&attribute.data, &parent_transform);
-        }
-        for attribute in &mut mesh_object.binormals {
-            attribute.data = transform_vectors(&attribute.data, &parent_transform);
-        }
+        transform_mesh_object(
+            mesh_object,
+            glam::Mat4::from_cols_array_2d(&parent_transform),
+        );
     } else {
         error!(
             "Failed to apply transform for {:?}",
@@ -427,6 +975,17 @@ fn convert_parent_bone_to_influences(mesh_object: &mut MeshObjectData, skel: Opt
     mesh_object.parent_bone_name = String::new();
 }
 
+fn vector3_data(data: &VectorData) -> Vec<glam::Vec3> {
+    match data {
+        VectorData::Vector2(v) => v.iter().map(|[x, y]| glam::vec3(*x, *y, 0.0)).collect(),
+        VectorData::Vector3(v) => v.iter().map(|v| glam::Vec3::from(*v)).collect(),
+        VectorData::Vector4(v) => v
+            .iter()
+            .map(|[x, y, z, _]| glam::vec3(*x, *y, *z))
+            .collect(),
+    }
+}
+
 fn match_mesh_order(mesh: &mut MeshData, reference: &MeshData) {
     mesh.objects.sort_by_key(|o| {
         // The sort is stable, so unmatched objects will be placed at the end in the same order.
@@ -468,6 +1027,7 @@ fn edit_mesh_attributes(
     ui: &mut Ui,
     mesh_object: &mut MeshObjectData,
     missing_attributes: &[String],
+    uv_tools: &mut UvToolsState,
 ) -> bool {
     let mut changed = false;
 
@@ -484,6 +1044,8 @@ fn edit_mesh_attributes(
         changed = true;
     }
 
+    let mut uv_to_copy = None;
+
     egui::Grid::new("vertex_attributes_grid").show(ui, |ui| {
         // TODO: Create a size between heading and label?
         ui.label(RichText::new("Name").size(16.0));
@@ -519,6 +1081,11 @@ fn edit_mesh_attributes(
         }
 
         // Vertex buffer 1.
+        let uv_names: Vec<_> = mesh_object
+            .texture_coordinates
+            .iter()
+            .map(|a| a.name.clone())
+            .collect();
         for (i, a) in mesh_object.texture_coordinates.iter_mut().enumerate() {
             edit_attribute_name(
                 ui,
@@ -528,6 +1095,9 @@ fn edit_mesh_attributes(
             );
             ui.label("Texture Coordinate (UV)");
             ui.label(a.data.len().to_string());
+            ui.menu_button("UV Tools", |ui| {
+                changed |= edit_uvs(ui, a, i, &uv_names, &mut uv_to_copy, uv_tools);
+            });
             ui.end_row();
         }
         for (i, a) in mesh_object.color_sets.iter_mut().enumerate() {
@@ -554,9 +1124,172 @@ fn edit_mesh_attributes(
         }
     });
 
+    if let Some((from, to)) = uv_to_copy {
+        mesh_object.texture_coordinates[to].data =
+            mesh_object.texture_coordinates[from].data.clone();
+        changed = true;
+    }
+
     changed
 }
 
+fn edit_uvs(
+    ui: &mut Ui,
+    attribute: &mut AttributeData,
+    i: usize,
+    uv_names: &[String],
+    uv_to_copy: &mut Option<(usize, usize)>,
+    state: &mut UvToolsState,
+) -> bool {
+    let mut changed = false;
+
+    if ui.button("Flip V").clicked() {
+        flip_v(&mut attribute.data);
+        changed = true;
+    }
+
+    ui.menu_button("Copy To", |ui| {
+        for (j, name) in uv_names.iter().enumerate() {
+            if j != i && ui.button(name).clicked() {
+                *uv_to_copy = Some((i, j));
+            }
+        }
+    });
+
+    if ui.button("Show Layout").clicked() {
+        state.layout_open = true;
+        state.layout_attribute_index = i;
+    }
+    ui.separator();
+
+    Grid::new("uv_tools_grid").show(ui, |ui| {
+        ui.label("Scale");
+        ui.add(DragValue::new(&mut state.scale[0]).speed(0.01));
+        ui.add(DragValue::new(&mut state.scale[1]).speed(0.01));
+        ui.end_row();
+
+        ui.label("Offset");
+        ui.add(DragValue::new(&mut state.offset[0]).speed(0.01));
+        ui.add(DragValue::new(&mut state.offset[1]).speed(0.01));
+        ui.end_row();
+    });
+    if ui.button("Apply Scale and Offset").clicked() {
+        scale_offset_uvs(&mut attribute.data, state.scale, state.offset);
+        changed = true;
+    }
+
+    changed
+}
+
+fn uv_layout_window(
+    ctx: &egui::Context,
+    mesh_object: Option<&MeshObjectData>,
+    state: &mut UvToolsState,
+) {
+    egui::Window::new("UV Layout")
+        .open(&mut state.layout_open)
+        .default_size(egui::Vec2::new(400.0, 400.0))
+        .resizable(true)
+        .show(ctx, |ui| {
+            let Some(mesh_object) = mesh_object else {
+                return;
+            };
+
+            ComboBox::from_id_salt("uv_layout_attribute")
+                .selected_text(
+                    mesh_object
+                        .texture_coordinates
+                        .get(state.layout_attribute_index)
+                        .map(|a| a.name.as_str())
+                        .unwrap_or_default(),
+                )
+                .show_ui(ui, |ui| {
+                    for (i, a) in mesh_object.texture_coordinates.iter().enumerate() {
+                        ui.selectable_value(&mut state.layout_attribute_index, i, &a.name);
+                    }
+                });
+
+            let Some(attribute) = mesh_object
+                .texture_coordinates
+                .get(state.layout_attribute_index)
+            else {
+                return;
+            };
+            let triangles = uv_triangles(&attribute.data, &mesh_object.vertex_indices);
+            let outside_count = triangles
+                .iter()
+                .filter(|t| is_outside_unit_square(t))
+                .count();
+            if outside_count > 0 {
+                ui.label(warning_icon_text(&format!(
+                    "{outside_count} triangles are outside the 0 to 1 range"
+                )))
+                .on_hover_text(
+                    "Textures with clamped wrap modes will appear stretched on these faces.",
+                );
+            }
+
+            let overlapping = overlapping_triangles(&triangles);
+            let overlapping_count = overlapping.iter().filter(|o| **o).count();
+            if overlapping_count > 0 {
+                ui.label(warning_icon_text(&format!(
+                    "{overlapping_count} triangles overlap other triangles"
+                )))
+                .on_hover_text("Overlapping faces share the same texture pixels.");
+            }
+
+            let size = ui.available_width().min(ui.available_height()).max(100.0);
+            let (response, painter) =
+                ui.allocate_painter(egui::Vec2::splat(size), egui::Sense::hover());
+
+            // Fit the full layout including the 0 to 1 range.
+            let (min, max) = triangles.iter().flatten().fold(
+                ([0.0f32, 0.0f32], [1.0f32, 1.0f32]),
+                |(min, max), [u, v]| {
+                    (
+                        [min[0].min(*u), min[1].min(*v)],
+                        [max[0].max(*u), max[1].max(*v)],
+                    )
+                },
+            );
+            let extent = (max[0] - min[0]).max(max[1] - min[1]);
+            let rect = response.rect;
+            let to_screen = |[u, v]: [f32; 2]| {
+                rect.min + egui::vec2(u - min[0], v - min[1]) * rect.width() / extent
+            };
+
+            painter.rect_stroke(
+                egui::Rect::from_two_pos(to_screen([0.0, 0.0]), to_screen([1.0, 1.0])),
+                0.0,
+                ui.visuals().widgets.noninteractive.bg_stroke,
+                egui::StrokeKind::Middle,
+            );
+
+            let overlapping_fill = WARNING_COLOR.gamma_multiply(0.3);
+            for (t, _) in triangles.iter().zip(&overlapping).filter(|(_, o)| **o) {
+                painter.add(egui::Shape::convex_polygon(
+                    t.map(to_screen).to_vec(),
+                    overlapping_fill,
+                    egui::Stroke::NONE,
+                ));
+            }
+
+            let stroke = egui::Stroke::new(1.0, ui.visuals().text_color());
+            let outside_stroke = egui::Stroke::new(1.0, ERROR_COLOR);
+            for t in &triangles {
+                let stroke = if is_outside_unit_square(t) {
+                    outside_stroke
+                } else {
+                    stroke
+                };
+                let [a, b, c] = t.map(to_screen);
+                painter.line_segment([a, b], stroke);
+                painter.line_segment([b, c], stroke);
+                painter.line_segment([c, a], stroke);
+            }
+        });
+}
+
 fn add_uv(mesh_object: &mut MeshObjectData, name: &str, count: usize) {
     mesh_object.texture_coordinates.push(AttributeData {
         name: name.to_owned(),
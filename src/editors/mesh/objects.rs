@@ -0,0 +1,390 @@
+use std::collections::HashMap;
+
+use ssbh_data::mesh_data::{
+    AttributeData, BoneInfluence, MeshObjectData, VectorData, VertexWeight,
+};
+
+/// Returns `true` if `a` and `b` have the same attribute names and types and the same skinning.
+pub fn has_same_layout(a: &MeshObjectData, b: &MeshObjectData) -> bool {
+    same_attributes(&a.positions, &b.positions)
+        && same_attributes(&a.normals, &b.normals)
+        && same_attributes(&a.binormals, &b.binormals)
+        && same_attributes(&a.tangents, &b.tangents)
+        && same_attributes(&a.texture_coordinates, &b.texture_coordinates)
+        && same_attributes(&a.color_sets, &b.color_sets)
+        && a.bone_influences.is_empty() == b.bone_influences.is_empty()
+        && a.parent_bone_name == b.parent_bone_name
+}
+
+fn same_attributes(a: &[AttributeData], b: &[AttributeData]) -> bool {
+    a.len() == b.len()
+        && a.iter().zip(b).all(|(a, b)| {
+            a.name == b.name && std::mem::discriminant(&a.data) == std::mem::discriminant(&b.data)
+        })
+}
+
+/// Append the vertices and faces of `b` to `a`.
+/// Returns `None` if the objects don't have the same layout.
+pub fn merge_mesh_objects(a: &MeshObjectData, b: &MeshObjectData) -> Option<MeshObjectData> {
+    if !has_same_layout(a, b) {
+        return None;
+    }
+    let offset = a.vertex_count().ok()? as u32;
+    b.vertex_count().ok()?;
+
+    let mut merged = a.clone();
+    append_attributes(&mut merged.positions, &b.positions);
+    append_attributes(&mut merged.normals, &b.normals);
+    append_attributes(&mut merged.binormals, &b.binormals);
+    append_attributes(&mut merged.tangents, &b.tangents);
+    append_attributes(&mut merged.texture_coordinates, &b.texture_coordinates);
+    append_attributes(&mut merged.color_sets, &b.color_sets);
+
+    merged
+        .vertex_indices
+        .extend(b.vertex_indices.iter().map(|i| i + offset));
+
+    for influence in &b.bone_influences {
+        let weights = influence.vertex_weights.iter().map(|w| VertexWeight {
+            vertex_index: w.vertex_index + offset,
+            vertex_weight: w.vertex_weight,
+        });
+        match merged
+            .bone_influences
+            .iter_mut()
+            .find(|i| i.bone_name == influence.bone_name)
+        {
+            Some(i) => i.vertex_weights.extend(weights),
+            None => merged.bone_influences.push(BoneInfluence {
+                bone_name: influence.bone_name.clone(),
+                vertex_weights: weights.collect(),
+            }),
+        }
+    }
+
+    Some(merged)
+}
+
+fn append_attributes(a: &mut [AttributeData], b: &[AttributeData]) {
+    for (a, b) in a.iter_mut().zip(b) {
+        match (&mut a.data, &b.data) {
+            (VectorData::Vector2(a), VectorData::Vector2(b)) => a.extend_from_slice(b),
+            (VectorData::Vector3(a), VectorData::Vector3(b)) => a.extend_from_slice(b),
+            (VectorData::Vector4(a), VectorData::Vector4(b)) => a.extend_from_slice(b),
+            _ => (),
+        }
+    }
+}
+
+/// Split `object` into separate objects for each group of connected faces.
+/// Vertices with identical positions are considered connected.
+/// If `attribute_name` is a UV or color set, vertices must also have identical values for that attribute.
+pub fn split_mesh_object(
+    object: &MeshObjectData,
+    attribute_name: Option<&str>,
+) -> Vec<MeshObjectData> {
+    let Some(positions) = object.positions.first().map(|a| &a.data) else {
+        return Vec::new();
+    };
+    let attribute = attribute_name.and_then(|name| {
+        object
+            .texture_coordinates
+            .iter()
+            .chain(&object.color_sets)
+            .find(|a| a.name == name)
+            .map(|a| &a.data)
+    });
+
+    let vertex_count = positions.len();
+    let mut parents: Vec<_> = (0..vertex_count).collect();
+
+    let triangles: Vec<_> = object
+        .vertex_indices
+        .chunks_exact(3)
+        .filter(|t| t.iter().all(|i| (*i as usize) < vertex_count))
+        .collect();
+    for t in &triangles {
+        union(&mut parents, t[0] as usize, t[1] as usize);
+        union(&mut parents, t[1] as usize, t[2] as usize);
+    }
+
+    // Faces are connected across seams if the welded vertices match.
+    let mut welded = HashMap::new();
+    for i in 0..vertex_count {
+        let mut key = vertex_bits(positions, i).unwrap_or_default();
+        // Skip the attribute for malformed meshes with fewer values than positions.
+        if let Some(bits) = attribute.and_then(|a| vertex_bits(a, i)) {
+            key.extend(bits);
+        }
+        match welded.get(&key) {
+            Some(j) => union(&mut parents, *j, i),
+            None => {
+                welded.insert(key, i);
+            }
+        }
+    }
+
+    // Preserve the original face order within each component.
+    let mut roots = Vec::new();
+    let mut components: Vec<Vec<u32>> = Vec::new();
+    for t in triangles {
+        let root = find(&mut parents, t[0] as usize);
+        match roots.iter().position(|r| *r == root) {
+            Some(i) => components[i].extend_from_slice(t),
+            None => {
+                roots.push(root);
+                components.push(t.to_vec());
+            }
+        }
+    }
+
+    components
+        .into_iter()
+        .map(|vertex_indices| mesh_object_subset(object, vertex_count, &vertex_indices))
+        .collect()
+}
+
+fn mesh_object_subset(
+    object: &MeshObjectData,
+    vertex_count: usize,
+    vertex_indices: &[u32],
+) -> MeshObjectData {
+    // Only include vertices used by the faces.
+    let mut new_indices = vec![None; vertex_count];
+    let mut vertices = Vec::new();
+    let vertex_indices = vertex_indices
+        .iter()
+        .map(|i| {
+            *new_indices[*i as usize].get_or_insert_with(|| {
+                vertices.push(*i as usize);
+                vertices.len() as u32 - 1
+            })
+        })
+        .collect();
+
+    let attributes = |attributes: &[AttributeData]| {
+        attributes
+            .iter()
+            .map(|a| AttributeData {
+                name: a.name.clone(),
+                data: select_vertices(&a.data, &vertices),
+            })
+            .collect()
+    };
+
+    MeshObjectData {
+        name: object.name.clone(),
+        subindex: object.subindex,
+        parent_bone_name: object.parent_bone_name.clone(),
+        sort_bias: object.sort_bias,
+        disable_depth_write: object.disable_depth_write,
+        disable_depth_test: object.disable_depth_test,
+        vertex_indices,
+        positions: attributes(&object.positions),
+        normals: attributes(&object.normals),
+        binormals: attributes(&object.binormals),
+        tangents: attributes(&object.tangents),
+        texture_coordinates: attributes(&object.texture_coordinates),
+        color_sets: attributes(&object.color_sets),
+        bone_influences: object
+            .bone_influences
+            .iter()
+            .map(|i| BoneInfluence {
+                bone_name: i.bone_name.clone(),
+                vertex_weights: i
+                    .vertex_weights
+                    .iter()
+                    .filter_map(|w| {
+                        Some(VertexWeight {
+                            vertex_index: (*new_indices.get(w.vertex_index as usize)?)?,
+                            vertex_weight: w.vertex_weight,
+                        })
+                    })
+                    .collect(),
+            })
+            .filter(|i| !i.vertex_weights.is_empty())
+            .collect(),
+        ..Default::default()
+    }
+}
+
+fn select_vertices(data: &VectorData, vertices: &[usize]) -> VectorData {
+    // Skip missing values instead of panicking for malformed meshes.
+    match data {
+        VectorData::Vector2(v) => {
+            VectorData::Vector2(vertices.iter().filter_map(|i| v.get(*i).copied()).collect())
+        }
+        VectorData::Vector3(v) => {
+            VectorData::Vector3(vertices.iter().filter_map(|i| v.get(*i).copied()).collect())
+        }
+        VectorData::Vector4(v) => {
+            VectorData::Vector4(vertices.iter().filter_map(|i| v.get(*i).copied()).collect())
+        }
+    }
+}
+
+fn vertex_bits(data: &VectorData, i: usize) -> Option<Vec<u32>> {
+    match data {
+        VectorData::Vector2(v) => v.get(i).map(|v| v.iter().map(|f| f.to_bits()).collect()),
+        VectorData::Vector3(v) => v.get(i).map(|v| v.iter().map(|f| f.to_bits()).collect()),
+        VectorData::Vector4(v) => v.get(i).map(|v| v.iter().map(|f| f.to_bits()).collect()),
+    }
+}
+
+fn find(parents: &mut [usize], i: usize) -> usize {
+    let mut root = i;
+    while parents[root] != root {
+        root = parents[root];
+    }
+    parents[i] = root;
+    root
+}
+
+fn union(parents: &mut [usize], a: usize, b: usize) {
+    let a = find(parents, a);
+    let b = find(parents, b);
+    // Use the smaller index to keep the component order predictable.
+    parents[a.max(b)] = a.min(b);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn positions(positions: Vec<[f32; 3]>) -> Vec<AttributeData> {
+        vec![AttributeData {
+            name: "Position0".to_owned(),
+            data: VectorData::Vector3(positions),
+        }]
+    }
+
+    #[test]
+    fn merge_different_layouts() {
+        let a = MeshObjectData {
+            positions: positions(vec![[0.0; 3]]),
+            ..Default::default()
+        };
+        let b = MeshObjectData {
+            positions: positions(vec![[0.0; 3]]),
+            parent_bone_name: "a".to_owned(),
+            ..Default::default()
+        };
+        assert_eq!(None, merge_mesh_objects(&a, &b));
+    }
+
+    #[test]
+    fn merge_offset_indices_and_weights() {
+        let a = MeshObjectData {
+            name: "a".to_owned(),
+            vertex_indices: vec![0, 1, 2],
+            positions: positions(vec![[0.0; 3], [1.0; 3], [2.0; 3]]),
+            bone_influences: vec![BoneInfluence {
+                bone_name: "bone".to_owned(),
+                vertex_weights: vec![VertexWeight {
+                    vertex_index: 0,
+                    vertex_weight: 1.0,
+                }],
+            }],
+            ..Default::default()
+        };
+        let b = MeshObjectData {
+            name: "b".to_owned(),
+            vertex_indices: vec![2, 1, 0],
+            positions: positions(vec![[3.0; 3], [4.0; 3], [5.0; 3]]),
+            bone_influences: vec![BoneInfluence {
+                bone_name: "bone".to_owned(),
+                vertex_weights: vec![VertexWeight {
+                    vertex_index: 1,
+                    vertex_weight: 0.5,
+                }],
+            }],
+            ..Default::default()
+        };
+
+        let merged = merge_mesh_objects(&a, &b).unwrap();
+        assert_eq!("a", merged.name);
+        assert_eq!(vec![0, 1, 2, 5, 4, 3], merged.vertex_indices);
+        assert_eq!(
+            positions(vec![
+                [0.0; 3], [1.0; 3], [2.0; 3], [3.0; 3], [4.0; 3], [5.0; 3]
+            ]),
+            merged.positions
+        );
+        assert_eq!(
+            vec![
+                VertexWeight {
+                    vertex_index: 0,
+                    vertex_weight: 1.0,
+                },
+                VertexWeight {
+                    vertex_index: 4,
+                    vertex_weight: 0.5,
+                }
+            ],
+            merged.bone_influences[0].vertex_weights
+        );
+    }
+
+    #[test]
+    fn split_connected_components() {
+        // The first two faces share a welded vertex at the same position.
+        let object = MeshObjectData {
+            vertex_indices: vec![0, 1, 2, 3, 4, 5, 6, 7, 8],
+            positions: positions(vec![
+                [0.0, 0.0, 0.0],
+                [1.0, 0.0, 0.0],
+                [0.0, 1.0, 0.0],
+                [1.0, 0.0, 0.0],
+                [1.0, 1.0, 0.0],
+                [0.0, 1.0, 1.0],
+                [5.0, 0.0, 0.0],
+                [6.0, 0.0, 0.0],
+                [5.0, 1.0, 0.0],
+            ]),
+            bone_influences: vec![BoneInfluence {
+                bone_name: "bone".to_owned(),
+                vertex_weights: vec![VertexWeight {
+                    vertex_index: 7,
+                    vertex_weight: 1.0,
+                }],
+            }],
+            ..Default::default()
+        };
+
+        let objects = split_mesh_object(&object, None);
+        assert_eq!(2, objects.len());
+        assert_eq!(vec![0, 1, 2, 3, 4, 5], objects[0].vertex_indices);
+        assert!(objects[0].bone_influences.is_empty());
+
+        assert_eq!(vec![0, 1, 2], objects[1].vertex_indices);
+        assert_eq!(
+            positions(vec![[5.0, 0.0, 0.0], [6.0, 0.0, 0.0], [5.0, 1.0, 0.0]]),
+            objects[1].positions
+        );
+        assert_eq!(
+            vec![VertexWeight {
+                vertex_index: 1,
+                vertex_weight: 1.0,
+            }],
+            objects[1].bone_influences[0].vertex_weights
+        );
+    }
+
+    #[test]
+    fn split_short_attribute() {
+        // Malformed meshes may have fewer UVs than positions.
+        let object = MeshObjectData {
+            vertex_indices: vec![0, 1, 2],
+            positions: positions(vec![[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]]),
+            texture_coordinates: vec![AttributeData {
+                name: "map1".to_owned(),
+                data: VectorData::Vector2(vec![[0.0, 0.0]]),
+            }],
+            ..Default::default()
+        };
+
+        let objects = split_mesh_object(&object, Some("map1"));
+        assert_eq!(1, objects.len());
+        assert_eq!(vec![0, 1, 2], objects[0].vertex_indices);
+    }
+}
@@ -0,0 +1,258 @@
+use ssbh_data::mesh_data::{AttributeData, MeshObjectData, VectorData};
+
+use crate::app::MeshStatisticsColumn;
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct MeshObjectStats {
+    pub name: String,
+    pub subindex: u64,
+    pub vertex_count: usize,
+    pub triangle_count: usize,
+    pub attributes: Vec<String>,
+    pub influence_count: usize,
+    pub max_influences_per_vertex: usize,
+    pub buffer_size: usize,
+}
+
+pub fn mesh_object_stats(mesh_object: &MeshObjectData) -> MeshObjectStats {
+    let vertex_count = mesh_object.vertex_count().unwrap_or_default();
+
+    // Estimate sizes using the component types used when saving.
+    let attributes: Vec<_> = [
+        (&mesh_object.positions, ComponentType::Float),
+        (&mesh_object.normals, ComponentType::HalfFloat),
+        (&mesh_object.binormals, ComponentType::Float),
+        (&mesh_object.tangents, ComponentType::HalfFloat),
+        (&mesh_object.texture_coordinates, ComponentType::Float),
+        (&mesh_object.color_sets, ComponentType::Byte),
+    ]
+    .into_iter()
+    .flat_map(|(attributes, component_type)| attributes.iter().map(move |a| (a, component_type)))
+    .collect();
+    let vertex_stride: usize = attributes
+        .iter()
+        .map(|(a, component_type)| attribute_size(a, *component_type))
+        .sum();
+
+    let mut influences_per_vertex = vec![0; vertex_count];
+    for influence in &mesh_object.bone_influences {
+        for w in &influence.vertex_weights {
+            if let Some(count) = influences_per_vertex.get_mut(w.vertex_index as usize) {
+                *count += 1;
+            }
+        }
+    }
+    let weight_count: usize = influences_per_vertex.iter().sum();
+
+    // Indices use u16 when possible.
+    let index_size = if vertex_count <= u16::MAX as usize {
+        2
+    } else {
+        4
+    };
+
+    // Skin weights store a u16 vertex index and f32 weight.
+    let buffer_size = vertex_count * vertex_stride
+        + mesh_object.vertex_indices.len() * index_size
+        + weight_count * 6;
+
+    MeshObjectStats {
+        name: mesh_object.name.clone(),
+        subindex: mesh_object.subindex,
+        vertex_count,
+        triangle_count: mesh_object.vertex_indices.len() / 3,
+        attributes: attributes
+            .iter()
+            .map(|(a, component_type)| attribute_description(a, *component_type))
+            .collect(),
+        influence_count: mesh_object.bone_influences.len(),
+        max_influences_per_vertex: influences_per_vertex.into_iter().max().unwrap_or_default(),
+        buffer_size,
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum ComponentType {
+    Float,
+    HalfFloat,
+    Byte,
+}
+
+impl ComponentType {
+    fn size(self) -> usize {
+        match self {
+            ComponentType::Float => 4,
+            ComponentType::HalfFloat => 2,
+            ComponentType::Byte => 1,
+        }
+    }
+}
+
+fn component_count(data: &VectorData) -> usize {
+    match data {
+        VectorData::Vector2(_) => 2,
+        VectorData::Vector3(_) => 3,
+        VectorData::Vector4(_) => 4,
+    }
+}
+
+fn attribute_size(attribute: &AttributeData, component_type: ComponentType) -> usize {
+    component_count(&attribute.data) * component_type.size()
+}
+
+fn attribute_description(attribute: &AttributeData, component_type: ComponentType) -> String {
+    format!(
+        "{} ({component_type:?}{})",
+        attribute.name,
+        component_count(&attribute.data)
+    )
+}
+
+/// Calculate the sum of counts and sizes for `stats`.
+pub fn total_stats(stats: &[MeshObjectStats]) -> MeshObjectStats {
+    MeshObjectStats {
+        name: "Total".to_owned(),
+        subindex: 0,
+        vertex_count: stats.iter().map(|s| s.vertex_count).sum(),
+        triangle_count: stats.iter().map(|s| s.triangle_count).sum(),
+        attributes: Vec::new(),
+        influence_count: stats.iter().map(|s| s.influence_count).sum(),
+        max_influences_per_vertex: stats
+            .iter()
+            .map(|s| s.max_influences_per_vertex)
+            .max()
+            .unwrap_or_default(),
+        buffer_size: stats.iter().map(|s| s.buffer_size).sum(),
+    }
+}
+
+pub fn sort_stats(stats: &mut [MeshObjectStats], column: MeshStatisticsColumn, descending: bool) {
+    stats.sort_by(|a, b| {
+        let ordering = match column {
+            MeshStatisticsColumn::Name => a.name.cmp(&b.name).then(a.subindex.cmp(&b.subindex)),
+            MeshStatisticsColumn::Vertices => a.vertex_count.cmp(&b.vertex_count),
+            MeshStatisticsColumn::Triangles => a.triangle_count.cmp(&b.triangle_count),
+            MeshStatisticsColumn::Influences => a.influence_count.cmp(&b.influence_count),
+            MeshStatisticsColumn::MaxInfluences => a
+                .max_influences_per_vertex
+                .cmp(&b.max_influences_per_vertex),
+            MeshStatisticsColumn::BufferSize => a.buffer_size.cmp(&b.buffer_size),
+        };
+        if descending {
+            ordering.reverse()
+        } else {
+            ordering
+        }
+    });
+}
+
+pub fn stats_csv(stats: &[MeshObjectStats]) -> String {
+    let mut csv = String::from(
+        "name,subindex,vertices,triangles,attributes,influences,max influences per vertex,estimated size (bytes)\n",
+    );
+    for s in stats {
+        csv.push_str(&format!(
+            "\"{}\",{},{},{},\"{}\",{},{},{}\n",
+            s.name.replace('"', "\"\""),
+            s.subindex,
+            s.vertex_count,
+            s.triangle_count,
+            s.attributes.join(";"),
+            s.influence_count,
+            s.max_influences_per_vertex,
+            s.buffer_size
+        ));
+    }
+    csv
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ssbh_data::mesh_data::{BoneInfluence, VertexWeight};
+
+    #[test]
+    fn stats_skinned_mesh_object() {
+        let mesh_object = MeshObjectData {
+            name: "a".to_owned(),
+            subindex: 1,
+            vertex_indices: vec![0, 1, 2],
+            positions: vec![AttributeData {
+                name: "Position0".to_owned(),
+                data: VectorData::Vector3(vec![[0.0; 3]; 3]),
+            }],
+            normals: vec![AttributeData {
+                name: "Normal0".to_owned(),
+                data: VectorData::Vector4(vec![[0.0; 4]; 3]),
+            }],
+            texture_coordinates: vec![AttributeData {
+                name: "map1".to_owned(),
+                data: VectorData::Vector2(vec![[0.0; 2]; 3]),
+            }],
+            color_sets: vec![AttributeData {
+                name: "colorSet1".to_owned(),
+                data: VectorData::Vector4(vec![[0.0; 4]; 3]),
+            }],
+            bone_influences: vec![
+                BoneInfluence {
+                    bone_name: "a".to_owned(),
+                    vertex_weights: vec![
+                        VertexWeight {
+                            vertex_index: 0,
+                            vertex_weight: 0.5,
+                        },
+                        VertexWeight {
+                            vertex_index: 1,
+                            vertex_weight: 1.0,
+                        },
+                    ],
+                },
+                BoneInfluence {
+                    bone_name: "b".to_owned(),
+                    vertex_weights: vec![VertexWeight {
+                        vertex_index: 0,
+                        vertex_weight: 0.5,
+                    }],
+                },
+            ],
+            ..Default::default()
+        };
+
+        assert_eq!(
+            MeshObjectStats {
+                name: "a".to_owned(),
+                subindex: 1,
+                vertex_count: 3,
+                triangle_count: 1,
+                attributes: vec![
+                    "Position0 (Float3)".to_owned(),
+                    "Normal0 (HalfFloat4)".to_owned(),
+                    "map1 (Float2)".to_owned(),
+                    "colorSet1 (Byte4)".to_owned()
+                ],
+                influence_count: 2,
+                max_influences_per_vertex: 2,
+                buffer_size: 3 * (12 + 8 + 8 + 4) + 3 * 2 + 3 * 6,
+            },
+            mesh_object_stats(&mesh_object)
+        );
+    }
+
+    #[test]
+    fn stats_csv_quoted_names() {
+        let stats = vec![MeshObjectStats {
+            name: "a\"b".to_owned(),
+            subindex: 0,
+            vertex_count: 3,
+            triangle_count: 1,
+            attributes: vec!["Position0 (Float3)".to_owned(), "map1 (Float2)".to_owned()],
+            influence_count: 0,
+            max_influences_per_vertex: 0,
+            buffer_size: 42,
+        }];
+        assert_eq!(
+            "name,subindex,vertices,triangles,attributes,influences,max influences per vertex,estimated size (bytes)\n\"a\"\"b\",0,3,1,\"Position0 (Float3);map1 (Float2)\",0,0,42\n",
+            stats_csv(&stats)
+        );
+    }
+}
@@ -0,0 +1,128 @@
+use glam::{Mat3, Mat4, Vec3};
+use ssbh_data::mesh_data::{MeshObjectData, VectorData};
+
+/// Apply `transform` to the vertex data of `mesh_object`.
+/// Mirrored transforms also reverse the face winding and flip the tangent sign.
+pub fn transform_mesh_object(mesh_object: &mut MeshObjectData, transform: Mat4) {
+    let vector_transform = Mat3::from_mat4(transform);
+    // Normals need the inverse transpose to stay perpendicular with non uniform scale.
+    let normal_transform = vector_transform.inverse().transpose();
+    let is_mirrored = vector_transform.determinant() < 0.0;
+
+    for attribute in &mut mesh_object.positions {
+        map_vectors(&mut attribute.data, |v| transform.transform_point3(v));
+    }
+    for attribute in &mut mesh_object.normals {
+        map_vectors(&mut attribute.data, |v| {
+            (normal_transform * v).normalize_or_zero()
+        });
+    }
+    for attribute in &mut mesh_object.binormals {
+        map_vectors(&mut attribute.data, |v| {
+            (vector_transform * v).normalize_or_zero()
+        });
+    }
+    for attribute in &mut mesh_object.tangents {
+        map_vectors(&mut attribute.data, |v| {
+            (vector_transform * v).normalize_or_zero()
+        });
+        if is_mirrored && let VectorData::Vector4(values) = &mut attribute.data {
+            // The W component stores the bitangent sign.
+            for v in values {
+                v[3] = -v[3];
+            }
+        }
+    }
+
+    if is_mirrored {
+        // Keep the front faces pointing outward.
+        for face in mesh_object.vertex_indices.chunks_exact_mut(3) {
+            face.swap(1, 2);
+        }
+    }
+}
+
+fn map_vectors(data: &mut VectorData, f: impl Fn(Vec3) -> Vec3) {
+    match data {
+        VectorData::Vector2(_) => (),
+        VectorData::Vector3(values) => {
+            for v in values {
+                *v = f(Vec3::from(*v)).to_array();
+            }
+        }
+        VectorData::Vector4(values) => {
+            for v in values {
+                let [x, y, z] = f(Vec3::new(v[0], v[1], v[2])).to_array();
+                *v = [x, y, z, v[3]];
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ssbh_data::mesh_data::AttributeData;
+
+    fn attribute(name: &str, data: VectorData) -> Vec<AttributeData> {
+        vec![AttributeData {
+            name: name.to_owned(),
+            data,
+        }]
+    }
+
+    #[test]
+    fn transform_translation() {
+        let mut mesh_object = MeshObjectData {
+            vertex_indices: vec![0, 1, 2],
+            positions: attribute("Position0", VectorData::Vector3(vec![[1.0, 2.0, 3.0]])),
+            normals: attribute("Normal0", VectorData::Vector4(vec![[0.0, 1.0, 0.0, 0.0]])),
+            ..Default::default()
+        };
+
+        transform_mesh_object(
+            &mut mesh_object,
+            Mat4::from_translation(Vec3::new(1.0, 1.0, 1.0)),
+        );
+
+        assert_eq!(vec![0, 1, 2], mesh_object.vertex_indices);
+        assert_eq!(
+            attribute("Position0", VectorData::Vector3(vec![[2.0, 3.0, 4.0]])),
+            mesh_object.positions
+        );
+        assert_eq!(
+            attribute("Normal0", VectorData::Vector4(vec![[0.0, 1.0, 0.0, 0.0]])),
+            mesh_object.normals
+        );
+    }
+
+    #[test]
+    fn transform_mirror_x() {
+        let mut mesh_object = MeshObjectData {
+            vertex_indices: vec![0, 1, 2],
+            positions: attribute("Position0", VectorData::Vector3(vec![[1.0, 2.0, 3.0]])),
+            normals: attribute("Normal0", VectorData::Vector4(vec![[1.0, 0.0, 0.0, 0.0]])),
+            tangents: attribute("Tangent0", VectorData::Vector4(vec![[0.0, 0.0, 1.0, 1.0]])),
+            ..Default::default()
+        };
+
+        transform_mesh_object(
+            &mut mesh_object,
+            Mat4::from_scale(Vec3::new(-1.0, 1.0, 1.0)),
+        );
+
+        assert_eq!(vec![0, 2, 1], mesh_object.vertex_indices);
+        assert_eq!(
+            attribute("Position0", VectorData::Vector3(vec![[-1.0, 2.0, 3.0]])),
+            mesh_object.positions
+        );
+        assert_eq!(
+            attribute("Normal0", VectorData::Vector4(vec![[-1.0, 0.0, 0.0, 0.0]])),
+            mesh_object.normals
+        );
+        assert_eq!(
+            attribute("Tangent0", VectorData::Vector4(vec![[0.0, 0.0, 1.0, -1.0]])),
+            mesh_object.tangents
+        );
+    }
+}
@@ -0,0 +1,208 @@
+use ssbh_data::mesh_data::VectorData;
+
+/// Flip the V coordinate to convert between top left and bottom left UV origins.
+pub fn flip_v(data: &mut VectorData) {
+    transform_uvs(data, |[u, v]| [u, 1.0 - v]);
+}
+
+/// Scale and then offset the UV coordinates.
+pub fn scale_offset_uvs(data: &mut VectorData, scale: [f32; 2], offset: [f32; 2]) {
+    transform_uvs(data, |[u, v]| {
+        [u * scale[0] + offset[0], v * scale[1] + offset[1]]
+    });
+}
+
+fn transform_uvs(data: &mut VectorData, f: impl Fn([f32; 2]) -> [f32; 2]) {
+    match data {
+        VectorData::Vector2(values) => {
+            for v in values {
+                *v = f(*v);
+            }
+        }
+        VectorData::Vector3(values) => {
+            for v in values {
+                let [x, y] = f([v[0], v[1]]);
+                *v = [x, y, v[2]];
+            }
+        }
+        VectorData::Vector4(values) => {
+            for v in values {
+                let [x, y] = f([v[0], v[1]]);
+                *v = [x, y, v[2], v[3]];
+            }
+        }
+    }
+}
+
+/// Find the UV coordinates for each triangle using the indices in `vertex_indices`.
+pub fn uv_triangles(data: &VectorData, vertex_indices: &[u32]) -> Vec<[[f32; 2]; 3]> {
+    let uvs: Vec<[f32; 2]> = match data {
+        VectorData::Vector2(values) => values.clone(),
+        VectorData::Vector3(values) => values.iter().map(|v| [v[0], v[1]]).collect(),
+        VectorData::Vector4(values) => values.iter().map(|v| [v[0], v[1]]).collect(),
+    };
+
+    vertex_indices
+        .chunks_exact(3)
+        .filter_map(|t| {
+            Some([
+                *uvs.get(t[0] as usize)?,
+                *uvs.get(t[1] as usize)?,
+                *uvs.get(t[2] as usize)?,
+            ])
+        })
+        .collect()
+}
+
+/// Returns `true` if any coordinate is outside the 0.0 to 1.0 range.
+/// Clamped wrap modes will stretch the texture edges for these triangles.
+pub fn is_outside_unit_square(triangle: &[[f32; 2]; 3]) -> bool {
+    triangle.iter().flatten().any(|f| !(0.0..=1.0).contains(f))
+}
+
+/// Returns `true` for each triangle that overlaps another triangle.
+/// Triangles that only share edges or vertices do not overlap.
+pub fn overlapping_triangles(triangles: &[[[f32; 2]; 3]]) -> Vec<bool> {
+    let mut overlapping = vec![false; triangles.len()];
+
+    let (min, max) = uv_bounds(triangles.iter().flatten());
+    if !min.iter().chain(&max).all(|f| f.is_finite()) {
+        return overlapping;
+    }
+
+    // Only compare triangles in the same grid cells to avoid checking every pair.
+    let cells_per_axis = ((triangles.len() as f32).sqrt() as usize).clamp(1, 256);
+    let cell = |f: f32, min: f32, max: f32| {
+        let extent = (max - min).max(f32::EPSILON);
+        (((f - min) / extent * cells_per_axis as f32) as usize).min(cells_per_axis - 1)
+    };
+    let mut grid = vec![Vec::new(); cells_per_axis * cells_per_axis];
+    for (i, t) in triangles.iter().enumerate() {
+        let (t_min, t_max) = uv_bounds(t);
+        for y in cell(t_min[1], min[1], max[1])..=cell(t_max[1], min[1], max[1]) {
+            for x in cell(t_min[0], min[0], max[0])..=cell(t_max[0], min[0], max[0]) {
+                grid[y * cells_per_axis + x].push(i);
+            }
+        }
+    }
+
+    for indices in &grid {
+        for (k, i) in indices.iter().enumerate() {
+            for j in &indices[k + 1..] {
+                if !(overlapping[*i] && overlapping[*j])
+                    && triangles_overlap(&triangles[*i], &triangles[*j])
+                {
+                    overlapping[*i] = true;
+                    overlapping[*j] = true;
+                }
+            }
+        }
+    }
+
+    overlapping
+}
+
+fn uv_bounds<'a>(uvs: impl IntoIterator<Item = &'a [f32; 2]>) -> ([f32; 2], [f32; 2]) {
+    uvs.into_iter().fold(
+        ([f32::MAX, f32::MAX], [f32::MIN, f32::MIN]),
+        |(min, max), [u, v]| {
+            (
+                [min[0].min(*u), min[1].min(*v)],
+                [max[0].max(*u), max[1].max(*v)],
+            )
+        },
+    )
+}
+
+fn triangles_overlap(a: &[[f32; 2]; 3], b: &[[f32; 2]; 3]) -> bool {
+    // Use a tolerance so that triangles touching along an edge are separated.
+    const EPSILON: f32 = 1e-6;
+
+    // Check for a separating axis perpendicular to any of the edges.
+    let is_separated = |t: &[[f32; 2]; 3]| {
+        (0..3).any(|i| {
+            let [x0, y0] = t[i];
+            let [x1, y1] = t[(i + 1) % 3];
+            let axis = [y0 - y1, x1 - x0];
+            if axis == [0.0, 0.0] {
+                return false;
+            }
+
+            let project = |t: &[[f32; 2]; 3]| {
+                t.iter()
+                    .map(|[x, y]| x * axis[0] + y * axis[1])
+                    .fold((f32::MAX, f32::MIN), |(min, max), d| {
+                        (min.min(d), max.max(d))
+                    })
+            };
+            let (a_min, a_max) = project(a);
+            let (b_min, b_max) = project(b);
+            a_max <= b_min + EPSILON || b_max <= a_min + EPSILON
+        })
+    };
+
+    // Triangles with zero area can't overlap anything.
+    let area = |[[x0, y0], [x1, y1], [x2, y2]]: &[[f32; 2]; 3]| {
+        ((x1 - x0) * (y2 - y0) - (x2 - x0) * (y1 - y0)).abs()
+    };
+    area(a) > EPSILON && area(b) > EPSILON && !is_separated(a) && !is_separated(b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn flip_v_vector2() {
+        let mut data = VectorData::Vector2(vec![[0.25, 0.25], [0.5, 1.0]]);
+        flip_v(&mut data);
+        assert_eq!(VectorData::Vector2(vec![[0.25, 0.75], [0.5, 0.0]]), data);
+    }
+
+    #[test]
+    fn scale_offset_vector2() {
+        let mut data = VectorData::Vector2(vec![[0.25, 0.5]]);
+        scale_offset_uvs(&mut data, [2.0, 0.5], [1.0, -1.0]);
+        assert_eq!(VectorData::Vector2(vec![[1.5, -0.75]]), data);
+    }
+
+    #[test]
+    fn uv_triangles_invalid_indices() {
+        let data = VectorData::Vector2(vec![[0.0, 0.0], [1.0, 0.0], [0.0, 1.0]]);
+        assert_eq!(
+            vec![[[0.0, 0.0], [1.0, 0.0], [0.0, 1.0]]],
+            uv_triangles(&data, &[0, 1, 2, 0, 1, 3])
+        );
+    }
+
+    #[test]
+    fn outside_unit_square() {
+        assert!(!is_outside_unit_square(&[
+            [0.0, 0.0],
+            [1.0, 0.0],
+            [0.0, 1.0]
+        ]));
+        assert!(is_outside_unit_square(&[
+            [0.0, 0.0],
+            [1.5, 0.0],
+            [0.0, 1.0]
+        ]));
+    }
+
+    #[test]
+    fn overlapping_triangles_shared_edges() {
+        let triangles = [
+            [[0.0, 0.0], [1.0, 0.0], [0.0, 1.0]],
+            // Shares an edge with the first triangle.
+            [[1.0, 0.0], [1.0, 1.0], [0.0, 1.0]],
+            // Overlaps the first triangle.
+            [[0.1, 0.1], [0.4, 0.1], [0.1, 0.4]],
+            // Separate from the other triangles.
+            [[2.0, 2.0], [3.0, 2.0], [2.0, 3.0]],
+        ];
+        assert_eq!(
+            vec![true, false, true, false],
+            overlapping_triangles(&triangles)
+        );
+    }
+}
@@ -0,0 +1,420 @@
+use glam::Vec3;
+use ssbh_data::mesh_data::{BoneInfluence, MeshObjectData, VertexWeight};
+
+use crate::app::WeightTransferMethod;
+
+use super::vector3_data;
+
+/// Calculate new bone influences for `target` by finding the closest point on `sources` for each vertex.
+/// Positions are compared in the bind pose, so all objects should use the same skeleton.
+pub fn transfer_bone_influences(
+    target: &MeshObjectData,
+    sources: &[&MeshObjectData],
+    method: WeightTransferMethod,
+) -> Option<Vec<BoneInfluence>> {
+    let target_positions = target.positions.first().map(|a| vector3_data(&a.data))?;
+
+    // Combine the sources to simplify the closest point searches.
+    let mut bone_names = Vec::new();
+    let mut points = Vec::new();
+    let mut point_weights = Vec::new();
+    let mut triangles = Vec::new();
+    for source in sources {
+        let Some(positions) = source.positions.first().map(|a| vector3_data(&a.data)) else {
+            continue;
+        };
+
+        let offset = points.len();
+        let mut weights = vec![Vec::new(); positions.len()];
+        for influence in &source.bone_influences {
+            let bone_index = bone_names
+                .iter()
+                .position(|n| n == &influence.bone_name)
+                .unwrap_or_else(|| {
+                    bone_names.push(influence.bone_name.clone());
+                    bone_names.len() - 1
+                });
+            for w in &influence.vertex_weights {
+                if let Some(vertex_weights) = weights.get_mut(w.vertex_index as usize) {
+                    vertex_weights.push((bone_index, w.vertex_weight));
+                }
+            }
+        }
+
+        triangles.extend(
+            source
+                .vertex_indices
+                .chunks_exact(3)
+                .filter(|t| t.iter().all(|i| (*i as usize) < positions.len()))
+                .map(|t| {
+                    [
+                        offset + t[0] as usize,
+                        offset + t[1] as usize,
+                        offset + t[2] as usize,
+                    ]
+                }),
+        );
+        points.extend(positions);
+        point_weights.extend(weights);
+    }
+
+    if points.is_empty() || bone_names.is_empty() {
+        return None;
+    }
+
+    // TODO: Use an acceleration structure if this is too slow for large meshes.
+    let mut bone_weights = vec![Vec::new(); bone_names.len()];
+    for (vertex_index, p) in target_positions.iter().enumerate() {
+        let weights = match method {
+            WeightTransferMethod::NearestSurface if !triangles.is_empty() => {
+                nearest_surface_weights(*p, &points, &point_weights, &triangles)
+            }
+            _ => nearest_vertex_weights(*p, &points, &point_weights),
+        };
+
+        for (bone_index, weight) in limit_weights(weights) {
+            bone_weights[bone_index].push(VertexWeight {
+                vertex_index: vertex_index as u32,
+                vertex_weight: weight,
+            });
+        }
+    }
+
+    Some(
+        bone_names
+            .into_iter()
+            .zip(bone_weights)
+            .filter(|(_, vertex_weights)| !vertex_weights.is_empty())
+            .map(|(bone_name, vertex_weights)| BoneInfluence {
+                bone_name,
+                vertex_weights,
+            })
+            .collect(),
+    )
+}
+
+/// Move the weights for `bone_name` to `new_bone_name` or remove them if there is no new bone.
+/// A parent bone of `bone_name` is also replaced or cleared.
+/// Returns `true` if the mesh object was modified.
+pub fn reassign_bone_influences(
+    mesh_object: &mut MeshObjectData,
+    bone_name: &str,
+    new_bone_name: Option<&str>,
+) -> bool {
+    let mut changed = false;
+
+    if mesh_object.parent_bone_name == bone_name {
+        mesh_object.parent_bone_name = new_bone_name.unwrap_or_default().to_owned();
+        changed = true;
+    }
+
+    let Some(index) = mesh_object
+        .bone_influences
+        .iter()
+        .position(|i| i.bone_name == bone_name)
+    else {
+        return changed;
+    };
+    let removed = mesh_object.bone_influences.remove(index);
+
+    if let Some(new_bone_name) = new_bone_name {
+        let influence = match mesh_object
+            .bone_influences
+            .iter()
+            .position(|i| i.bone_name == new_bone_name)
+        {
+            Some(i) => &mut mesh_object.bone_influences[i],
+            None => {
+                mesh_object.bone_influences.push(BoneInfluence {
+                    bone_name: new_bone_name.to_owned(),
+                    vertex_weights: Vec::new(),
+                });
+                mesh_object.bone_influences.last_mut().unwrap()
+            }
+        };
+
+        // Combine weights for vertices already influenced by the new bone.
+        for w in removed.vertex_weights {
+            match influence
+                .vertex_weights
+                .iter_mut()
+                .find(|v| v.vertex_index == w.vertex_index)
+            {
+                Some(v) => v.vertex_weight += w.vertex_weight,
+                None => influence.vertex_weights.push(w),
+            }
+        }
+    }
+
+    true
+}
+
+fn nearest_vertex_weights(
+    p: Vec3,
+    points: &[Vec3],
+    weights: &[Vec<(usize, f32)>],
+) -> Vec<(usize, f32)> {
+    points
+        .iter()
+        .enumerate()
+        .min_by(|(_, a), (_, b)| p.distance_squared(**a).total_cmp(&p.distance_squared(**b)))
+        .map(|(i, _)| weights[i].clone())
+        .unwrap_or_default()
+}
+
+fn nearest_surface_weights(
+    p: Vec3,
+    points: &[Vec3],
+    weights: &[Vec<(usize, f32)>],
+    triangles: &[[usize; 3]],
+) -> Vec<(usize, f32)> {
+    let mut closest = None;
+    let mut closest_distance = f32::INFINITY;
+    for triangle in triangles {
+        let [a, b, c] = triangle.map(|i| points[i]);
+        let (point, barycentric) = closest_point_on_triangle(p, a, b, c);
+        let distance = p.distance_squared(point);
+        if distance < closest_distance {
+            closest_distance = distance;
+            closest = Some((triangle, barycentric));
+        }
+    }
+
+    // Blend the weights of the triangle's vertices.
+    let mut blended: Vec<(usize, f32)> = Vec::new();
+    if let Some((triangle, barycentric)) = closest {
+        for (i, factor) in triangle.iter().zip(barycentric) {
+            for (bone_index, weight) in &weights[*i] {
+                match blended.iter_mut().find(|(b, _)| b == bone_index) {
+                    Some((_, w)) => *w += weight * factor,
+                    None => blended.push((*bone_index, weight * factor)),
+                }
+            }
+        }
+    }
+    blended
+}
+
+fn limit_weights(mut weights: Vec<(usize, f32)>) -> Vec<(usize, f32)> {
+    // The in game shaders only support up to 4 influences per vertex.
+    weights.retain(|(_, w)| *w > 0.0);
+    weights.sort_by(|(_, a), (_, b)| b.total_cmp(a));
+    weights.truncate(4);
+
+    let total: f32 = weights.iter().map(|(_, w)| w).sum();
+    if total > 0.0 {
+        for (_, w) in &mut weights {
+            *w /= total;
+        }
+    }
+    weights
+}
+
+// Real-Time Collision Detection by Christer Ericson section 5.1.5.
+fn closest_point_on_triangle(p: Vec3, a: Vec3, b: Vec3, c: Vec3) -> (Vec3, [f32; 3]) {
+    let ab = b - a;
+    let ac = c - a;
+    let ap = p - a;
+    let d1 = ab.dot(ap);
+    let d2 = ac.dot(ap);
+    if d1 <= 0.0 && d2 <= 0.0 {
+        return (a, [1.0, 0.0, 0.0]);
+    }
+
+    let bp = p - b;
+    let d3 = ab.dot(bp);
+    let d4 = ac.dot(bp);
+    if d3 >= 0.0 && d4 <= d3 {
+        return (b, [0.0, 1.0, 0.0]);
+    }
+
+    let vc = d1 * d4 - d3 * d2;
+    if vc <= 0.0 && d1 >= 0.0 && d3 <= 0.0 {
+        let v = d1 / (d1 - d3);
+        return (a + ab * v, [1.0 - v, v, 0.0]);
+    }
+
+    let cp = p - c;
+    let d5 = ab.dot(cp);
+    let d6 = ac.dot(cp);
+    if d6 >= 0.0 && d5 <= d6 {
+        return (c, [0.0, 0.0, 1.0]);
+    }
+
+    let vb = d5 * d2 - d1 * d6;
+    if vb <= 0.0 && d2 >= 0.0 && d6 <= 0.0 {
+        let w = d2 / (d2 - d6);
+        return (a + ac * w, [1.0 - w, 0.0, w]);
+    }
+
+    let va = d3 * d6 - d5 * d4;
+    if va <= 0.0 && (d4 - d3) >= 0.0 && (d5 - d6) >= 0.0 {
+        let w = (d4 - d3) / ((d4 - d3) + (d5 - d6));
+        return (b + (c - b) * w, [0.0, 1.0 - w, w]);
+    }
+
+    let sum = va + vb + vc;
+    if sum == 0.0 {
+        // Degenerate triangles don't have a well defined interior.
+        return (a, [1.0, 0.0, 0.0]);
+    }
+    let v = vb / sum;
+    let w = vc / sum;
+    (a + ab * v + ac * w, [1.0 - v - w, v, w])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ssbh_data::mesh_data::{AttributeData, VectorData};
+
+    fn mesh_object(
+        positions: Vec<[f32; 3]>,
+        bone_influences: Vec<BoneInfluence>,
+    ) -> MeshObjectData {
+        MeshObjectData {
+            name: "a".to_owned(),
+            vertex_indices: (0..positions.len() as u32).collect(),
+            positions: vec![AttributeData {
+                name: "Position0".to_owned(),
+                data: VectorData::Vector3(positions),
+            }],
+            bone_influences,
+            ..Default::default()
+        }
+    }
+
+    fn influence(bone_name: &str, weights: &[(u32, f32)]) -> BoneInfluence {
+        BoneInfluence {
+            bone_name: bone_name.to_owned(),
+            vertex_weights: weights
+                .iter()
+                .map(|(vertex_index, vertex_weight)| VertexWeight {
+                    vertex_index: *vertex_index,
+                    vertex_weight: *vertex_weight,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn transfer_nearest_vertex() {
+        let source = mesh_object(
+            vec![[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]],
+            vec![
+                influence("a", &[(0, 1.0), (2, 1.0)]),
+                influence("b", &[(1, 1.0)]),
+            ],
+        );
+        let target = mesh_object(vec![[0.9, 0.1, 0.0], [0.1, 0.1, 0.0]], Vec::new());
+
+        assert_eq!(
+            Some(vec![
+                influence("a", &[(1, 1.0)]),
+                influence("b", &[(0, 1.0)])
+            ]),
+            transfer_bone_influences(&target, &[&source], WeightTransferMethod::NearestVertex)
+        );
+    }
+
+    #[test]
+    fn transfer_nearest_surface() {
+        let source = mesh_object(
+            vec![[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]],
+            vec![
+                influence("a", &[(0, 1.0), (2, 1.0)]),
+                influence("b", &[(1, 1.0)]),
+            ],
+        );
+        let target = mesh_object(vec![[0.5, 0.0, 1.0]], Vec::new());
+
+        assert_eq!(
+            Some(vec![
+                influence("a", &[(0, 0.5)]),
+                influence("b", &[(0, 0.5)])
+            ]),
+            transfer_bone_influences(&target, &[&source], WeightTransferMethod::NearestSurface)
+        );
+    }
+
+    #[test]
+    fn transfer_no_influences() {
+        let source = mesh_object(vec![[0.0, 0.0, 0.0]], Vec::new());
+        let target = mesh_object(vec![[0.0, 0.0, 0.0]], Vec::new());
+
+        assert_eq!(
+            None,
+            transfer_bone_influences(&target, &[&source], WeightTransferMethod::NearestVertex)
+        );
+    }
+
+    #[test]
+    fn limit_weights_normalized() {
+        assert_eq!(
+            vec![(0, 0.5), (1, 0.25), (2, 0.125), (3, 0.125)],
+            limit_weights(vec![(4, 0.0), (2, 0.125), (3, 0.125), (1, 0.25), (0, 0.5)])
+        );
+    }
+
+    #[test]
+    fn reassign_bone_influences_to_parent() {
+        let mut mesh_object = MeshObjectData {
+            bone_influences: vec![
+                BoneInfluence {
+                    bone_name: "parent".to_owned(),
+                    vertex_weights: vec![VertexWeight {
+                        vertex_index: 0,
+                        vertex_weight: 0.5,
+                    }],
+                },
+                BoneInfluence {
+                    bone_name: "child".to_owned(),
+                    vertex_weights: vec![
+                        VertexWeight {
+                            vertex_index: 0,
+                            vertex_weight: 0.5,
+                        },
+                        VertexWeight {
+                            vertex_index: 1,
+                            vertex_weight: 1.0,
+                        },
+                    ],
+                },
+            ],
+            ..Default::default()
+        };
+
+        assert!(reassign_bone_influences(
+            &mut mesh_object,
+            "child",
+            Some("parent")
+        ));
+        assert_eq!(
+            vec![BoneInfluence {
+                bone_name: "parent".to_owned(),
+                vertex_weights: vec![
+                    VertexWeight {
+                        vertex_index: 0,
+                        vertex_weight: 1.0,
+                    },
+                    VertexWeight {
+                        vertex_index: 1,
+                        vertex_weight: 1.0,
+                    },
+                ],
+            }],
+            mesh_object.bone_influences
+        );
+    }
+
+    #[test]
+    fn reassign_bone_influences_clear_parent_bone() {
+        let mut mesh_object = MeshObjectData {
+            parent_bone_name: "root".to_owned(),
+            ..Default::default()
+        };
+
+        assert!(reassign_bone_influences(&mut mesh_object, "root", None));
+        assert_eq!("", mesh_object.parent_bone_name);
+    }
+}
@@ -1,4 +1,7 @@
-use crate::{EditorMessage, EditorResponse, path::folder_editor_title, save_file, save_file_as};
+use crate::{
+    EditorMessage, EditorResponse, export_json, import_json, path::folder_editor_title, save_file,
+    save_file_as,
+};
 use egui::{Grid, Label, Response, ScrollArea, Sense, Ui, special_emojis::GITHUB};
 
 use ssbh_data::prelude::*;
@@ -30,6 +33,15 @@ pub fn meshex_editor(
                     if ui.button("Save As...").clicked() {
                         saved |= save_file_as(meshex, folder_name, file_name, "MeshEx", "numshexb");
                     }
+
+                    ui.separator();
+                    if ui.button("Import JSON...").clicked() {
+                        changed |= import_json(meshex, folder_name);
+                    }
+
+                    if ui.button("Export JSON...").clicked() {
+                        export_json(meshex, folder_name, file_name);
+                    }
                 });
 
                 ui.menu_button("MeshEx", |ui| {
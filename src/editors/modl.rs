@@ -1,12 +1,12 @@
 use crate::{
     EditorMessage, EditorResponse,
     app::{ModlEditorState, ModlEditorTab, draggable_icon, warning_icon_text},
-    horizontal_separator_empty,
+    export_json, horizontal_separator_empty, import_json,
     path::folder_editor_title,
     save_file, save_file_as,
     validation::{ModlValidationError, ModlValidationErrorKind},
 };
-use egui::{Grid, Label, RichText, ScrollArea, TextEdit, special_emojis::GITHUB};
+use egui::{Grid, RichText, ScrollArea, TextEdit, special_emojis::GITHUB};
 use egui_dnd::dnd;
 
 use ssbh_data::{mesh_data::MeshObjectData, modl_data::ModlEntryData, prelude::*};
@@ -42,6 +42,15 @@ pub fn modl_editor(
                     if ui.button("Save As...").clicked() {
                         saved |= save_file_as(modl, folder_name, file_name, "Modl", "numdlb");
                     }
+
+                    ui.separator();
+                    if ui.button("Import JSON...").clicked() {
+                        changed |= import_json(modl, folder_name);
+                    }
+
+                    if ui.button("Export JSON...").clicked() {
+                        export_json(modl, folder_name, file_name);
+                    }
                 });
 
                 ui.menu_button("Modl", |ui| {
@@ -83,6 +92,7 @@ pub fn modl_editor(
                     mesh,
                     matl,
                     validation_errors,
+                    &mut state.selected_entry_index,
                     dark_mode,
                     &mut message,
                 ),
@@ -139,6 +149,7 @@ fn edit_modl_entries(
     mesh: Option<&MeshData>,
     matl: Option<&MatlData>,
     validation_errors: &[ModlValidationError],
+    selected_entry_index: &mut Option<usize>,
     dark_mode: bool,
     message: &mut Option<EditorMessage>,
 ) -> bool {
@@ -226,8 +237,11 @@ fn edit_modl_entries(
                                 warning_icon_text(&entry.mesh_object_name)
                             };
 
-                            let name_response =
-                                ui.add(Label::new(mesh_text).sense(egui::Sense::click()));
+                            let is_selected = *selected_entry_index == Some(*item);
+                            let name_response = ui.selectable_label(is_selected, mesh_text);
+                            if name_response.clicked() {
+                                *selected_entry_index = (!is_selected).then_some(*item);
+                            }
 
                             name_response.context_menu(|ui| {
                                 if ui.button("Duplicate").clicked() {
@@ -267,6 +281,11 @@ fn edit_modl_entries(
                 });
             });
 
+            // Entry indices change after editing the list.
+            if entry_to_duplicate.is_some() || entry_to_remove.is_some() {
+                *selected_entry_index = None;
+            }
+
             if let Some(i) = entry_to_duplicate {
                 let duplicated_entry = modl.entries[i].clone();
                 modl.entries.insert(i + 1, duplicated_entry);
@@ -278,6 +297,7 @@ fn edit_modl_entries(
 
             if let Some(response) = response.final_update() {
                 egui_dnd::utils::shift_vec(response.from, response.to, &mut modl.entries);
+                *selected_entry_index = None;
                 changed = true;
             }
         });
@@ -1,31 +1,45 @@
-use std::path::Path;
+use std::{collections::BTreeSet, path::Path};
 
 use crate::{
-    EditorResponse,
-    app::{SkelEditorState, SkelMode, draggable_icon},
+    EditorMessage, EditorResponse,
+    app::{
+        BoneTransformSpace, ERROR_COLOR, MergeBonesState, SkelDiffState, SkelEditorState, SkelMode,
+        draggable_icon,
+    },
+    export_json, import_json,
     path::folder_editor_title,
     widgets::enum_combo_box,
 };
 use egui::{
-    Button, CollapsingHeader, Grid, Label, RichText, ScrollArea, TextWrapMode,
-    special_emojis::GITHUB,
+    Button, CentralPanel, CollapsingHeader, DragValue, Grid, RichText, ScrollArea, SidePanel,
+    TextWrapMode, collapsing_header::CollapsingState, special_emojis::GITHUB,
 };
 use egui_dnd::dnd;
 use log::error;
 use rfd::FileDialog;
-use ssbh_data::{prelude::*, skel_data::BoneData};
+use ssbh_data::{
+    prelude::*,
+    skel_data::{BillboardType, BoneData, BoneTransformError},
+};
+
+mod diff;
+pub use diff::skel_diff;
+use diff::skel_diff_text;
 
 pub fn skel_editor(
     ctx: &egui::Context,
     folder_name: &Path,
     file_name: &str,
     skel: &mut SkelData,
+    mesh: Option<&MeshData>,
     state: &mut SkelEditorState,
     dark_mode: bool,
 ) -> EditorResponse {
     let mut open = true;
     let mut changed = false;
     let mut saved = false;
+    let mut message = None;
+    let mut bone_action = None;
 
     let title = folder_editor_title(folder_name, file_name);
     egui::Window::new(format!("Skel Editor ({title})"))
@@ -51,9 +65,22 @@ pub fn skel_editor(
                     {
                         error!("Failed to save {file:?}: {e}");
                     }
+
+                    ui.separator();
+                    if ui.button("Import JSON...").clicked() {
+                        changed |= import_json(skel, folder_name);
+                    }
+
+                    if ui.button("Export JSON...").clicked() {
+                        export_json(skel, folder_name, file_name);
+                    }
                 });
 
                 ui.menu_button("Skeleton", |ui| {
+                    if ui.button("Add Bone").clicked() {
+                        bone_action = Some(BoneAction::Add(None));
+                    }
+
                     if ui
                         .add(
                             Button::new("Match Reference Bone Order...")
@@ -69,6 +96,44 @@ pub fn skel_editor(
                             Err(e) => error!("Failed to read {file:?}: {e}"),
                         }
                     }
+
+                    if ui
+                        .add(
+                            Button::new("Compare With Reference...")
+                                .wrap_mode(TextWrapMode::Extend),
+                        )
+                        .clicked()
+                        && let Some(file) = FileDialog::new()
+                            .add_filter("Skel", &["nusktb"])
+                            .pick_file()
+                    {
+                        match SkelData::from_file(&file) {
+                            Ok(reference) => {
+                                state.diff.reference = Some((file, reference));
+                                state.diff.is_open = true;
+                            }
+                            Err(e) => error!("Failed to read {file:?}: {e}"),
+                        }
+                    }
+
+                    if ui
+                        .add(Button::new("Merge Bones From...").wrap_mode(TextWrapMode::Extend))
+                        .clicked()
+                        && let Some(file) = FileDialog::new()
+                            .add_filter("Skel", &["nusktb"])
+                            .pick_file()
+                    {
+                        match SkelData::from_file(&file) {
+                            Ok(reference) => {
+                                // Select all missing bones by default.
+                                state.merge_bones.selected_bones =
+                                    missing_bones(skel, &reference).into_iter().collect();
+                                state.merge_bones.reference = Some((file, reference));
+                                state.merge_bones.is_open = true;
+                            }
+                            Err(e) => error!("Failed to read {file:?}: {e}"),
+                        }
+                    }
                 });
 
                 ui.menu_button("Help", |ui| {
@@ -82,39 +147,130 @@ pub fn skel_editor(
             });
             ui.separator();
 
-            ui.horizontal(|ui| {
-                ui.selectable_value(
-                    &mut state.mode,
-                    SkelMode::List,
-                    RichText::new("List").heading(),
-                );
-                ui.selectable_value(
-                    &mut state.mode,
-                    SkelMode::Hierarchy,
-                    RichText::new("Hierarchy").heading(),
-                );
-            });
-            ScrollArea::vertical()
-                .auto_shrink([false; 2])
-                .show(ui, |ui| match state.mode {
-                    SkelMode::List => {
-                        changed |= edit_bones_list(ui, skel, dark_mode);
-                    }
-                    SkelMode::Hierarchy => {
-                        changed |= edit_bones_hierarchy(ui, skel);
-                    }
+            if let Some(index) = state.selected_bone_index
+                && index < skel.bones.len()
+            {
+                SidePanel::right("skel_right_panel")
+                    .default_width(300.0)
+                    .show_inside(ui, |ui| {
+                        changed |= edit_bone_transform(ui, skel, index, &mut state.transform_space);
+                    });
+            }
+
+            CentralPanel::default().show_inside(ui, |ui| {
+                ui.horizontal(|ui| {
+                    ui.selectable_value(
+                        &mut state.mode,
+                        SkelMode::List,
+                        RichText::new("List").heading(),
+                    );
+                    ui.selectable_value(
+                        &mut state.mode,
+                        SkelMode::Hierarchy,
+                        RichText::new("Hierarchy").heading(),
+                    );
                 });
+                ScrollArea::vertical()
+                    .auto_shrink([false; 2])
+                    .show(ui, |ui| match state.mode {
+                        SkelMode::List => {
+                            changed |= edit_bones_list(
+                                ui,
+                                skel,
+                                &mut state.selected_bone_index,
+                                &mut bone_action,
+                                dark_mode,
+                            );
+                        }
+                        SkelMode::Hierarchy => {
+                            edit_bones_hierarchy(
+                                ui,
+                                skel,
+                                &mut state.selected_bone_index,
+                                &mut bone_action,
+                            );
+                        }
+                    });
+            });
         });
 
+    changed |= merge_bones_window(ctx, skel, &mut state.merge_bones);
+    changed |= diff_window(ctx, skel, &mut state.diff);
+
+    match bone_action {
+        Some(BoneAction::Add(parent_index)) => {
+            state.selected_bone_index = Some(add_bone(skel, parent_index));
+            changed = true;
+        }
+        Some(BoneAction::Rename(i)) => {
+            message = Some(EditorMessage::RenameBone {
+                bone_name: skel.bones[i].name.clone(),
+            });
+        }
+        Some(BoneAction::Delete(i)) if !can_remove_bone(skel, mesh, i) => {
+            // Weights and child bones are moved to the parent, so they would be lost.
+            error!(
+                "Failed to delete {:?}. Bones without a parent can't have child bones or vertex weights.",
+                skel.bones[i].name
+            );
+        }
+        Some(BoneAction::Delete(i)) => {
+            let bone_name = skel.bones[i].name.clone();
+            let parent_bone_name = skel.bones[i]
+                .parent_index
+                .and_then(|p| skel.bones.get(p))
+                .map(|p| p.name.clone());
+
+            skel.bones = remove_bone(i, &skel.bones);
+            state.selected_bone_index = None;
+            changed = true;
+
+            // Meshes are stored in other files, so let the app update the weights.
+            message = Some(EditorMessage::ReassignBoneWeights {
+                bone_name,
+                parent_bone_name,
+            });
+        }
+        None => (),
+    }
+
     EditorResponse {
         open,
         changed,
         saved,
-        message: None,
+        message,
     }
 }
 
-fn edit_bones_list(ui: &mut egui::Ui, skel: &mut SkelData, dark_mode: bool) -> bool {
+enum BoneAction {
+    Add(Option<usize>),
+    Rename(usize),
+    Delete(usize),
+}
+
+fn bone_context_menu(response: &egui::Response, index: usize, action: &mut Option<BoneAction>) {
+    response.context_menu(|ui| {
+        if ui.button("Add Child Bone").clicked() {
+            *action = Some(BoneAction::Add(Some(index)));
+        }
+
+        if ui.button("Rename...").clicked() {
+            *action = Some(BoneAction::Rename(index));
+        }
+
+        if ui.button("Delete").clicked() {
+            *action = Some(BoneAction::Delete(index));
+        }
+    });
+}
+
+fn edit_bones_list(
+    ui: &mut egui::Ui,
+    skel: &mut SkelData,
+    selected_bone_index: &mut Option<usize>,
+    bone_action: &mut Option<BoneAction>,
+    dark_mode: bool,
+) -> bool {
     let mut changed = false;
 
     // TODO: Do this without clone?
@@ -155,7 +311,9 @@ fn edit_bones_list(ui: &mut egui::Ui, skel: &mut SkelData, dark_mode: bool) -> b
                     });
 
                     // TODO: Highlight the selected bone on hover.
-                    ui.add(Label::new(&bone.name).sense(egui::Sense::click()));
+                    let bone_response =
+                        ui.selectable_value(selected_bone_index, Some(*item), &bone.name);
+                    bone_context_menu(&bone_response, *item, bone_action);
 
                     let id = egui::Id::new("bone").with(item);
                     let parent_bone_name = bone
@@ -199,6 +357,12 @@ fn edit_bones_list(ui: &mut egui::Ui, skel: &mut SkelData, dark_mode: bool) -> b
     });
 
     if let Some(response) = response.final_update() {
+        // Keep the same bone selected after reordering.
+        *selected_bone_index = selected_bone_index.and_then(|selected| {
+            let mut indices: Vec<_> = (0..skel.bones.len()).collect();
+            egui_dnd::utils::shift_vec(response.from, response.to, &mut indices);
+            indices.iter().position(|i| *i == selected)
+        });
         skel.bones = move_bone(response.from, response.to, &skel.bones);
         changed = true;
     }
@@ -224,37 +388,510 @@ fn move_bone(from: usize, to: usize, bones: &[BoneData]) -> Vec<BoneData> {
         .collect()
 }
 
-fn edit_bones_hierarchy(ui: &mut egui::Ui, skel: &mut SkelData) -> bool {
-    let changed = false;
-
+fn edit_bones_hierarchy(
+    ui: &mut egui::Ui,
+    skel: &SkelData,
+    selected_bone_index: &mut Option<usize>,
+    bone_action: &mut Option<BoneAction>,
+) {
     for (i, bone) in skel.bones.iter().enumerate() {
         if bone.parent_index.is_none() {
-            display_bones_recursive(ui, i, &skel.bones);
+            display_bones_recursive(ui, i, &skel.bones, selected_bone_index, bone_action);
         }
     }
-
-    changed
 }
 
-fn display_bones_recursive(ui: &mut egui::Ui, root_index: usize, bones: &[BoneData]) {
+fn display_bones_recursive(
+    ui: &mut egui::Ui,
+    root_index: usize,
+    bones: &[BoneData],
+    selected_bone_index: &mut Option<usize>,
+    bone_action: &mut Option<BoneAction>,
+) {
     // TODO: Does this handle cycles?
     // Don't assume bone names are unique.
     let name = &bones[root_index].name;
     let id = ui.make_persistent_id("skel").with(name).with(root_index);
 
-    CollapsingHeader::new(name)
-        .id_salt(id)
-        .default_open(true)
-        .show(ui, |ui| {
+    CollapsingState::load_with_default_open(ui.ctx(), id, true)
+        .show_header(ui, |ui| {
+            let response = ui.selectable_value(selected_bone_index, Some(root_index), name);
+            bone_context_menu(&response, root_index, bone_action);
+        })
+        .body(|ui| {
             // Recursively iterate over the child bones.
             for (i, _) in bones
                 .iter()
                 .enumerate()
                 .filter(|(_, b)| b.parent_index == Some(root_index))
             {
-                display_bones_recursive(ui, i, bones);
+                display_bones_recursive(ui, i, bones, selected_bone_index, bone_action);
+            }
+        });
+}
+
+fn diff_window(ctx: &egui::Context, skel: &mut SkelData, state: &mut SkelDiffState) -> bool {
+    let mut changed = false;
+
+    egui::Window::new("Skeleton Differences")
+        .open(&mut state.is_open)
+        .resizable(true)
+        .show(ctx, |ui| {
+            let Some((path, reference)) = &state.reference else {
+                return;
+            };
+            ui.label(format!(
+                "Reference: {}",
+                path.file_name()
+                    .map(|f| f.to_string_lossy().to_string())
+                    .unwrap_or_default()
+            ))
+            .on_hover_text(path.to_string_lossy());
+
+            ui.horizontal(|ui| {
+                ui.label("Transform Tolerance");
+                ui.add(
+                    DragValue::new(&mut state.tolerance)
+                        .speed(0.0001)
+                        .range(0.0..=f32::MAX),
+                );
+            });
+
+            let diff = skel_diff(skel, reference, state.tolerance);
+
+            ui.horizontal(|ui| {
+                if ui.button("Export...").clicked()
+                    && let Some(file) = FileDialog::new().add_filter("Text", &["txt"]).save_file()
+                    && let Err(e) = std::fs::write(&file, skel_diff_text(&diff))
+                {
+                    error!("Failed to save {file:?}: {e}");
+                }
+
+                if ui
+                    .add_enabled(!diff.reordered.is_empty(), Button::new("Match Bone Order"))
+                    .clicked()
+                {
+                    match_skel_order(skel, reference);
+                    changed = true;
+                }
+            });
+            ui.separator();
+
+            if diff.is_empty() {
+                ui.label("No differences found.");
+                return;
+            }
+
+            ScrollArea::vertical().max_height(500.0).show(ui, |ui| {
+                diff_section(ui, "Added Bones", &diff.added, |ui, name| {
+                    ui.label(name);
+                });
+                diff_section(ui, "Removed Bones", &diff.removed, |ui, name| {
+                    ui.label(name);
+                });
+                diff_section(ui, "Reparented Bones", &diff.reparented, |ui, b| {
+                    ui.label(format!(
+                        "{}: {} -> {}",
+                        b.name,
+                        b.reference_parent.as_deref().unwrap_or("None"),
+                        b.parent.as_deref().unwrap_or("None")
+                    ));
+                });
+                diff_section(
+                    ui,
+                    "Changed Transforms",
+                    &diff.changed_transforms,
+                    |ui, t| {
+                        ui.label(format!("{}: {}", t.name, t.max_difference));
+                    },
+                );
+                diff_section(ui, "Order Differences", &diff.reordered, |ui, b| {
+                    ui.label(format!("{}: {} -> {}", b.name, b.reference_index, b.index));
+                });
+            });
+        });
+
+    changed
+}
+
+fn diff_section<T>(
+    ui: &mut egui::Ui,
+    name: &str,
+    items: &[T],
+    add_item: impl Fn(&mut egui::Ui, &T),
+) {
+    CollapsingHeader::new(format!("{name} ({})", items.len()))
+        .default_open(!items.is_empty())
+        .show(ui, |ui| {
+            for item in items {
+                add_item(ui, item);
+            }
+        });
+}
+
+fn merge_bones_window(
+    ctx: &egui::Context,
+    skel: &mut SkelData,
+    state: &mut MergeBonesState,
+) -> bool {
+    let mut changed = false;
+
+    let mut should_close = false;
+    egui::Window::new("Merge Bones")
+        .open(&mut state.is_open)
+        .resizable(true)
+        .show(ctx, |ui| {
+            let Some((path, reference)) = &state.reference else {
+                return;
+            };
+            ui.label(format!(
+                "Bones from {}",
+                path.file_name()
+                    .map(|f| f.to_string_lossy().to_string())
+                    .unwrap_or_default()
+            ));
+
+            let missing = missing_bones(skel, reference);
+            if missing.is_empty() {
+                ui.label("All bones are already in the current skeleton.");
+                return;
+            }
+
+            ui.horizontal(|ui| {
+                if ui.button("Select All").clicked() {
+                    state.selected_bones = missing.iter().copied().collect();
+                }
+                if ui.button("Select None").clicked() {
+                    state.selected_bones.clear();
+                }
+            });
+            ui.separator();
+
+            ScrollArea::vertical().max_height(400.0).show(ui, |ui| {
+                for i in &missing {
+                    let bone = &reference.bones[*i];
+                    let mut selected = state.selected_bones.contains(i);
+                    ui.horizontal(|ui| {
+                        ui.add_space(bone_depth(&reference.bones, *i) as f32 * 16.0);
+                        if ui.checkbox(&mut selected, &bone.name).changed() {
+                            if selected {
+                                // Child bones need their parent to preserve the hierarchy.
+                                let mut parent = bone.parent_index;
+                                while let Some(p) = parent.filter(|p| missing.contains(p)) {
+                                    if !state.selected_bones.insert(p) {
+                                        break;
+                                    }
+                                    parent = reference.bones[p].parent_index;
+                                }
+                                state
+                                    .selected_bones
+                                    .extend(bone_subtree(&reference.bones, *i));
+                            } else {
+                                for child in bone_subtree(&reference.bones, *i) {
+                                    state.selected_bones.remove(&child);
+                                }
+                            }
+                        }
+                    });
+                }
+            });
+            ui.separator();
+
+            if ui
+                .add_enabled(!state.selected_bones.is_empty(), Button::new("Merge"))
+                .clicked()
+            {
+                merge_bones(skel, reference, &state.selected_bones);
+                changed = true;
+                should_close = true;
+            }
+        });
+
+    if should_close {
+        state.is_open = false;
+        state.reference = None;
+        state.selected_bones.clear();
+    }
+
+    changed
+}
+
+/// Returns the indices of bones in `reference` with no matching name in `skel`.
+fn missing_bones(skel: &SkelData, reference: &SkelData) -> Vec<usize> {
+    reference
+        .bones
+        .iter()
+        .enumerate()
+        .filter(|(_, r)| !skel.bones.iter().any(|b| b.name == r.name))
+        .map(|(i, _)| i)
+        .collect()
+}
+
+fn bone_depth(bones: &[BoneData], index: usize) -> usize {
+    // Limit the depth to avoid infinite loops for cycles.
+    std::iter::successors(bones[index].parent_index, |p| {
+        bones.get(*p).and_then(|b| b.parent_index)
+    })
+    .take(bones.len())
+    .count()
+}
+
+/// Returns `index` and the indices of all its descendants.
+fn bone_subtree(bones: &[BoneData], index: usize) -> Vec<usize> {
+    let mut subtree = vec![index];
+    let mut i = 0;
+    while let Some(parent) = subtree.get(i).copied() {
+        let children: Vec<_> = bones
+            .iter()
+            .enumerate()
+            .filter(|(c, b)| b.parent_index == Some(parent) && !subtree.contains(c))
+            .map(|(c, _)| c)
+            .collect();
+        subtree.extend(children);
+        i += 1;
+    }
+    subtree
+}
+
+/// Append the selected `reference` bones with parents remapped by name.
+/// Parents are always added before their children.
+fn merge_bones(skel: &mut SkelData, reference: &SkelData, selected_bones: &BTreeSet<usize>) {
+    fn add_bone_recursive(
+        skel: &mut SkelData,
+        reference: &SkelData,
+        selected_bones: &BTreeSet<usize>,
+        index: usize,
+        depth: usize,
+    ) {
+        let bone = &reference.bones[index];
+        if skel.bones.iter().any(|b| b.name == bone.name) {
+            return;
+        }
+
+        // Limit the depth to avoid infinite recursion for cycles.
+        if let Some(parent) = bone.parent_index
+            && selected_bones.contains(&parent)
+            && depth < reference.bones.len()
+        {
+            add_bone_recursive(skel, reference, selected_bones, parent, depth + 1);
+
+            // Cycles may have already added this bone.
+            if skel.bones.iter().any(|b| b.name == bone.name) {
+                return;
+            }
+        }
+
+        let parent_index = bone
+            .parent_index
+            .and_then(|p| reference.bones.get(p))
+            .and_then(|p| skel.bones.iter().position(|b| b.name == p.name));
+        skel.bones.push(BoneData {
+            parent_index,
+            ..bone.clone()
+        });
+    }
+
+    for i in selected_bones {
+        add_bone_recursive(skel, reference, selected_bones, *i, 0);
+    }
+}
+
+fn add_bone(skel: &mut SkelData, parent_index: Option<usize>) -> usize {
+    let mut name = "NewBone".to_owned();
+    let mut i = 1;
+    while skel.bones.iter().any(|b| b.name == name) {
+        name = format!("NewBone{i}");
+        i += 1;
+    }
+
+    skel.bones.push(BoneData {
+        name,
+        transform: glam::Mat4::IDENTITY.to_cols_array_2d(),
+        parent_index,
+        billboard_type: BillboardType::Disabled,
+    });
+    skel.bones.len() - 1
+}
+
+fn can_remove_bone(skel: &SkelData, mesh: Option<&MeshData>, index: usize) -> bool {
+    let bone = &skel.bones[index];
+    bone.parent_index.is_some()
+        || (!skel.bones.iter().any(|b| b.parent_index == Some(index))
+            && !mesh.is_some_and(|mesh| {
+                mesh.objects.iter().any(|o| {
+                    o.bone_influences
+                        .iter()
+                        .any(|i| i.bone_name == bone.name && !i.vertex_weights.is_empty())
+                })
+            }))
+}
+
+fn remove_bone(index: usize, bones: &[BoneData]) -> Vec<BoneData> {
+    let removed = &bones[index];
+    let removed_transform = glam::Mat4::from_cols_array_2d(&removed.transform);
+
+    bones
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| *i != index)
+        .map(|(_, b)| {
+            if b.parent_index == Some(index) {
+                // Reparent children to the removed bone's parent without moving them.
+                BoneData {
+                    parent_index: removed.parent_index,
+                    transform: (removed_transform * glam::Mat4::from_cols_array_2d(&b.transform))
+                        .to_cols_array_2d(),
+                    ..b.clone()
+                }
+            } else {
+                b.clone()
+            }
+        })
+        .map(|b| BoneData {
+            // Account for the removed bone when shifting indices.
+            parent_index: b.parent_index.map(|p| if p > index { p - 1 } else { p }),
+            ..b
+        })
+        .collect()
+}
+
+fn edit_bone_transform(
+    ui: &mut egui::Ui,
+    skel: &mut SkelData,
+    index: usize,
+    space: &mut BoneTransformSpace,
+) -> bool {
+    let mut changed = false;
+
+    ui.heading(&skel.bones[index].name);
+    ui.horizontal(|ui| {
+        ui.radio_value(space, BoneTransformSpace::Local, "Local");
+        ui.radio_value(space, BoneTransformSpace::World, "World");
+    });
+    ui.separator();
+
+    let transform = match bone_transform(skel, index, *space) {
+        Ok(transform) => transform,
+        Err(e) => {
+            ui.label(
+                RichText::new(format!("Failed to calculate transform: {e}")).color(ERROR_COLOR),
+            );
+            return false;
+        }
+    };
+
+    let (mut scale, mut rotation, mut translation) = transform.to_scale_rotation_translation();
+    let (rx, ry, rz) = rotation.to_euler(glam::EulerRot::XYZ);
+    let mut euler = glam::vec3(rx.to_degrees(), ry.to_degrees(), rz.to_degrees());
+
+    Grid::new("bone_transform_grid").show(ui, |ui| {
+        ui.label("Translation");
+        changed |= drag_vec3(ui, &mut translation, 0.1);
+        ui.end_row();
+
+        ui.label("Rotation (Degrees)");
+        let euler_changed = drag_vec3(ui, &mut euler, 0.5);
+        if euler_changed {
+            rotation = glam::Quat::from_euler(
+                glam::EulerRot::XYZ,
+                euler.x.to_radians(),
+                euler.y.to_radians(),
+                euler.z.to_radians(),
+            );
+            changed = true;
+        }
+        ui.end_row();
+
+        ui.label("Rotation (Quaternion)");
+        ui.horizontal(|ui| {
+            let mut quat_changed = false;
+            quat_changed |= ui
+                .add(DragValue::new(&mut rotation.x).speed(0.01))
+                .changed();
+            quat_changed |= ui
+                .add(DragValue::new(&mut rotation.y).speed(0.01))
+                .changed();
+            quat_changed |= ui
+                .add(DragValue::new(&mut rotation.z).speed(0.01))
+                .changed();
+            quat_changed |= ui
+                .add(DragValue::new(&mut rotation.w).speed(0.01))
+                .changed();
+            if quat_changed {
+                rotation = rotation.try_normalize().unwrap_or(glam::Quat::IDENTITY);
+                changed = true;
             }
         });
+        ui.end_row();
+
+        ui.label("Scale");
+        changed |= drag_vec3(ui, &mut scale, 0.01);
+        ui.end_row();
+    });
+
+    if changed {
+        let transform = glam::Mat4::from_scale_rotation_translation(scale, rotation, translation);
+        if let Err(e) = set_bone_transform(skel, index, transform, *space) {
+            error!(
+                "Failed to set transform for {:?}: {e}",
+                skel.bones[index].name
+            );
+        }
+    }
+
+    changed
+}
+
+fn drag_vec3(ui: &mut egui::Ui, value: &mut glam::Vec3, speed: f32) -> bool {
+    ui.horizontal(|ui| {
+        let mut changed = false;
+        changed |= ui.add(DragValue::new(&mut value.x).speed(speed)).changed();
+        changed |= ui.add(DragValue::new(&mut value.y).speed(speed)).changed();
+        changed |= ui.add(DragValue::new(&mut value.z).speed(speed)).changed();
+        changed
+    })
+    .inner
+}
+
+fn bone_transform(
+    skel: &SkelData,
+    index: usize,
+    space: BoneTransformSpace,
+) -> Result<glam::Mat4, BoneTransformError> {
+    let bone = &skel.bones[index];
+    match space {
+        BoneTransformSpace::Local => Ok(glam::Mat4::from_cols_array_2d(&bone.transform)),
+        BoneTransformSpace::World => Ok(glam::Mat4::from_cols_array_2d(
+            &skel.calculate_world_transform(bone)?,
+        )),
+    }
+}
+
+fn set_bone_transform(
+    skel: &mut SkelData,
+    index: usize,
+    transform: glam::Mat4,
+    space: BoneTransformSpace,
+) -> Result<(), BoneTransformError> {
+    let local_transform = match space {
+        BoneTransformSpace::Local => transform,
+        BoneTransformSpace::World => {
+            // Convert to the parent's space since bones only store local transforms.
+            match skel.bones[index]
+                .parent_index
+                .and_then(|p| skel.bones.get(p))
+            {
+                Some(parent) => {
+                    let parent_transform =
+                        glam::Mat4::from_cols_array_2d(&skel.calculate_world_transform(parent)?);
+                    parent_transform.inverse() * transform
+                }
+                None => transform,
+            }
+        }
+    };
+    skel.bones[index].transform = local_transform.to_cols_array_2d();
+    Ok(())
 }
 
 fn match_skel_order(skel: &mut SkelData, reference: &SkelData) {
@@ -272,9 +909,9 @@ fn match_skel_order(skel: &mut SkelData, reference: &SkelData) {
 
 #[cfg(test)]
 mod tests {
-    use ssbh_data::skel_data::{BillboardType, BoneData};
-
     use super::*;
+    use crate::test_utils::bone;
+    use ssbh_data::mesh_data::{BoneInfluence, MeshObjectData, VertexWeight};
 
     #[test]
     fn skel_order_empty_reference() {
@@ -482,4 +1119,198 @@ mod tests {
             after
         );
     }
+
+    #[test]
+    fn set_bone_transform_world_space() {
+        let mut skel = SkelData {
+            major_version: 1,
+            minor_version: 0,
+            bones: vec![
+                BoneData {
+                    name: "a".to_owned(),
+                    transform: glam::Mat4::from_translation(glam::vec3(1.0, 2.0, 3.0))
+                        .to_cols_array_2d(),
+                    parent_index: None,
+                    billboard_type: BillboardType::Disabled,
+                },
+                BoneData {
+                    name: "b".to_owned(),
+                    transform: glam::Mat4::IDENTITY.to_cols_array_2d(),
+                    parent_index: Some(0),
+                    billboard_type: BillboardType::Disabled,
+                },
+            ],
+        };
+
+        let world = glam::Mat4::from_translation(glam::vec3(2.0, 2.0, 2.0));
+        set_bone_transform(&mut skel, 1, world, BoneTransformSpace::World).unwrap();
+
+        // The local transform is relative to the parent.
+        assert_eq!(
+            glam::Mat4::from_translation(glam::vec3(1.0, 0.0, -1.0)).to_cols_array_2d(),
+            skel.bones[1].transform
+        );
+        assert_eq!(
+            world,
+            bone_transform(&skel, 1, BoneTransformSpace::World).unwrap()
+        );
+    }
+
+    #[test]
+    fn remove_bone_reparent_children() {
+        let bones = vec![
+            BoneData {
+                name: "a".to_owned(),
+                transform: glam::Mat4::IDENTITY.to_cols_array_2d(),
+                parent_index: None,
+                billboard_type: BillboardType::Disabled,
+            },
+            BoneData {
+                name: "b".to_owned(),
+                transform: glam::Mat4::from_translation(glam::vec3(1.0, 0.0, 0.0))
+                    .to_cols_array_2d(),
+                parent_index: Some(0),
+                billboard_type: BillboardType::Disabled,
+            },
+            BoneData {
+                name: "c".to_owned(),
+                transform: glam::Mat4::from_translation(glam::vec3(0.0, 1.0, 0.0))
+                    .to_cols_array_2d(),
+                parent_index: Some(1),
+                billboard_type: BillboardType::Disabled,
+            },
+        ];
+
+        // The child keeps the same world transform.
+        assert_eq!(
+            vec![
+                BoneData {
+                    name: "a".to_owned(),
+                    transform: glam::Mat4::IDENTITY.to_cols_array_2d(),
+                    parent_index: None,
+                    billboard_type: BillboardType::Disabled,
+                },
+                BoneData {
+                    name: "c".to_owned(),
+                    transform: glam::Mat4::from_translation(glam::vec3(1.0, 1.0, 0.0))
+                        .to_cols_array_2d(),
+                    parent_index: Some(0),
+                    billboard_type: BillboardType::Disabled,
+                },
+            ],
+            remove_bone(1, &bones)
+        );
+    }
+
+    #[test]
+    fn can_remove_root_bone() {
+        let skel = SkelData {
+            major_version: 1,
+            minor_version: 0,
+            bones: vec![
+                BoneData {
+                    name: "a".to_owned(),
+                    transform: glam::Mat4::IDENTITY.to_cols_array_2d(),
+                    parent_index: None,
+                    billboard_type: BillboardType::Disabled,
+                },
+                BoneData {
+                    name: "b".to_owned(),
+                    transform: glam::Mat4::IDENTITY.to_cols_array_2d(),
+                    parent_index: Some(0),
+                    billboard_type: BillboardType::Disabled,
+                },
+                BoneData {
+                    name: "c".to_owned(),
+                    transform: glam::Mat4::IDENTITY.to_cols_array_2d(),
+                    parent_index: None,
+                    billboard_type: BillboardType::Disabled,
+                },
+            ],
+        };
+        let mesh = MeshData {
+            major_version: 1,
+            minor_version: 10,
+            objects: vec![MeshObjectData {
+                bone_influences: vec![BoneInfluence {
+                    bone_name: "c".to_owned(),
+                    vertex_weights: vec![VertexWeight {
+                        vertex_index: 0,
+                        vertex_weight: 1.0,
+                    }],
+                }],
+                ..Default::default()
+            }],
+        };
+
+        assert!(!can_remove_bone(&skel, None, 0));
+        assert!(can_remove_bone(&skel, Some(&mesh), 1));
+        assert!(can_remove_bone(&skel, None, 2));
+        assert!(!can_remove_bone(&skel, Some(&mesh), 2));
+    }
+
+    #[test]
+    fn merge_bones_parents_before_children() {
+        let mut skel = SkelData {
+            major_version: 1,
+            minor_version: 0,
+            bones: vec![
+                bone("Trans", None, glam::Mat4::ZERO),
+                bone("Hip", Some(0), glam::Mat4::ZERO),
+            ],
+        };
+
+        // The child appears before its parent in the reference.
+        let reference = SkelData {
+            major_version: 1,
+            minor_version: 0,
+            bones: vec![
+                bone("Trans", None, glam::Mat4::ZERO),
+                bone("S_Tail2", Some(3), glam::Mat4::ZERO),
+                bone("Hip", Some(0), glam::Mat4::ZERO),
+                bone("S_Tail1", Some(2), glam::Mat4::ZERO),
+                bone("S_Ear", Some(0), glam::Mat4::ZERO),
+            ],
+        };
+
+        assert_eq!(vec![1, 3, 4], missing_bones(&skel, &reference));
+        assert_eq!(vec![3, 1], bone_subtree(&reference.bones, 3));
+
+        merge_bones(&mut skel, &reference, &[1, 3].into_iter().collect());
+        assert_eq!(
+            vec![
+                bone("Trans", None, glam::Mat4::ZERO),
+                bone("Hip", Some(0), glam::Mat4::ZERO),
+                bone("S_Tail1", Some(1), glam::Mat4::ZERO),
+                bone("S_Tail2", Some(2), glam::Mat4::ZERO),
+            ],
+            skel.bones
+        );
+    }
+
+    #[test]
+    fn merge_bones_cyclic_parents() {
+        let mut skel = SkelData {
+            major_version: 1,
+            minor_version: 0,
+            bones: Vec::new(),
+        };
+        let reference = SkelData {
+            major_version: 1,
+            minor_version: 0,
+            bones: vec![
+                bone("A", Some(1), glam::Mat4::ZERO),
+                bone("B", Some(0), glam::Mat4::ZERO),
+            ],
+        };
+
+        merge_bones(&mut skel, &reference, &[0, 1].into_iter().collect());
+        assert_eq!(
+            vec![
+                bone("A", None, glam::Mat4::ZERO),
+                bone("B", Some(0), glam::Mat4::ZERO)
+            ],
+            skel.bones
+        );
+    }
 }
@@ -0,0 +1,278 @@
+use std::{collections::BTreeSet, fmt::Write};
+
+use ssbh_data::{prelude::*, skel_data::BoneData};
+
+/// Differences between a skeleton and a reference skeleton matched by bone name.
+#[derive(Debug, PartialEq, Default)]
+pub struct SkelDiff {
+    /// Bones not present in the reference.
+    pub added: Vec<String>,
+    /// Reference bones not present in the skeleton.
+    pub removed: Vec<String>,
+    pub reparented: Vec<ReparentedBone>,
+    pub changed_transforms: Vec<ChangedTransform>,
+    pub reordered: Vec<ReorderedBone>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct ReparentedBone {
+    pub name: String,
+    pub reference_parent: Option<String>,
+    pub parent: Option<String>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct ChangedTransform {
+    pub name: String,
+    /// The largest absolute difference of any matrix element.
+    pub max_difference: f32,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct ReorderedBone {
+    pub name: String,
+    pub reference_index: usize,
+    pub index: usize,
+}
+
+impl SkelDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty()
+            && self.removed.is_empty()
+            && self.reparented.is_empty()
+            && self.changed_transforms.is_empty()
+            && self.reordered.is_empty()
+    }
+}
+
+pub fn skel_diff(skel: &SkelData, reference: &SkelData, tolerance: f32) -> SkelDiff {
+    let find_bone = |bones: &[BoneData], name: &str| bones.iter().position(|b| b.name == name);
+    let parent_name = |bones: &[BoneData], bone: &BoneData| {
+        bone.parent_index
+            .and_then(|p| bones.get(p))
+            .map(|p| p.name.clone())
+    };
+
+    let mut diff = SkelDiff {
+        added: skel
+            .bones
+            .iter()
+            .filter(|b| find_bone(&reference.bones, &b.name).is_none())
+            .map(|b| b.name.clone())
+            .collect(),
+        removed: reference
+            .bones
+            .iter()
+            .filter(|r| find_bone(&skel.bones, &r.name).is_none())
+            .map(|r| r.name.clone())
+            .collect(),
+        ..Default::default()
+    };
+
+    for bone in &skel.bones {
+        let Some(reference_bone) = reference.bones.iter().find(|r| r.name == bone.name) else {
+            continue;
+        };
+
+        let parent = parent_name(&skel.bones, bone);
+        let reference_parent = parent_name(&reference.bones, reference_bone);
+        if parent != reference_parent {
+            diff.reparented.push(ReparentedBone {
+                name: bone.name.clone(),
+                reference_parent,
+                parent,
+            });
+        }
+
+        let max_difference = bone
+            .transform
+            .iter()
+            .flatten()
+            .zip(reference_bone.transform.iter().flatten())
+            .map(|(a, b)| (a - b).abs())
+            .fold(0.0, f32::max);
+        if max_difference > tolerance {
+            diff.changed_transforms.push(ChangedTransform {
+                name: bone.name.clone(),
+                max_difference,
+            });
+        }
+    }
+
+    // Added or removed bones shift indices without changing the relative order.
+    // Bones outside the longest run of shared bones in reference order are reordered.
+    let shared: Vec<_> = skel
+        .bones
+        .iter()
+        .enumerate()
+        .filter_map(|(i, b)| Some((i, find_bone(&reference.bones, &b.name)?)))
+        .collect();
+    let in_order =
+        longest_increasing_subsequence(&shared.iter().map(|(_, r)| *r).collect::<Vec<_>>());
+    diff.reordered = shared
+        .iter()
+        .enumerate()
+        .filter(|(k, _)| !in_order.contains(k))
+        .map(|(_, (i, reference_index))| ReorderedBone {
+            name: skel.bones[*i].name.clone(),
+            reference_index: *reference_index,
+            index: *i,
+        })
+        .collect();
+
+    diff
+}
+
+/// Returns the sorted positions in `values` of a longest strictly increasing subsequence.
+fn longest_increasing_subsequence(values: &[usize]) -> BTreeSet<usize> {
+    // The position of the smallest last value for each subsequence length.
+    let mut tails: Vec<usize> = Vec::new();
+    let mut previous = vec![None; values.len()];
+    for (i, value) in values.iter().enumerate() {
+        let length = tails.partition_point(|t| values[*t] < *value);
+        previous[i] = length.checked_sub(1).map(|l| tails[l]);
+        if length == tails.len() {
+            tails.push(i);
+        } else {
+            tails[length] = i;
+        }
+    }
+
+    let mut positions = BTreeSet::new();
+    let mut current = tails.last().copied();
+    while let Some(i) = current {
+        positions.insert(i);
+        current = previous[i];
+    }
+    positions
+}
+
+pub fn skel_diff_text(diff: &SkelDiff) -> String {
+    let mut text = String::new();
+
+    let optional_name = |name: &Option<String>| name.clone().unwrap_or("None".to_owned());
+
+    // Writing to a String can't fail.
+    writeln!(&mut text, "Added Bones ({})", diff.added.len()).unwrap();
+    for name in &diff.added {
+        writeln!(&mut text, "  {name}").unwrap();
+    }
+
+    writeln!(&mut text, "Removed Bones ({})", diff.removed.len()).unwrap();
+    for name in &diff.removed {
+        writeln!(&mut text, "  {name}").unwrap();
+    }
+
+    writeln!(&mut text, "Reparented Bones ({})", diff.reparented.len()).unwrap();
+    for b in &diff.reparented {
+        writeln!(
+            &mut text,
+            "  {}: {} -> {}",
+            b.name,
+            optional_name(&b.reference_parent),
+            optional_name(&b.parent)
+        )
+        .unwrap();
+    }
+
+    writeln!(
+        &mut text,
+        "Changed Transforms ({})",
+        diff.changed_transforms.len()
+    )
+    .unwrap();
+    for t in &diff.changed_transforms {
+        writeln!(&mut text, "  {}: {}", t.name, t.max_difference).unwrap();
+    }
+
+    writeln!(&mut text, "Order Differences ({})", diff.reordered.len()).unwrap();
+    for b in &diff.reordered {
+        writeln!(
+            &mut text,
+            "  {}: {} -> {}",
+            b.name, b.reference_index, b.index
+        )
+        .unwrap();
+    }
+
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{bone, skel};
+    use glam::{Mat4, Vec3};
+
+    #[test]
+    fn skel_diff_identical() {
+        let a = skel(vec![
+            bone("Trans", None, Mat4::IDENTITY),
+            bone("Hip", Some(0), Mat4::from_translation(Vec3::X)),
+        ]);
+        let diff = skel_diff(&a, &a, 0.001);
+        assert!(diff.is_empty());
+    }
+
+    #[test]
+    fn skel_diff_all_changes() {
+        let reference = skel(vec![
+            bone("Trans", None, Mat4::IDENTITY),
+            bone("Hip", Some(0), Mat4::from_translation(Vec3::X)),
+            bone("Waist", Some(1), Mat4::from_translation(Vec3::X)),
+        ]);
+        let current = skel(vec![
+            bone("Trans", None, Mat4::IDENTITY),
+            bone("Waist", Some(0), Mat4::from_translation(Vec3::X * 1.0005)),
+            bone("Hip", Some(0), Mat4::from_translation(Vec3::X * 2.0)),
+            bone("S_Tail", Some(2), Mat4::IDENTITY),
+        ]);
+
+        let diff = skel_diff(&current, &reference, 0.001);
+        assert_eq!(
+            SkelDiff {
+                added: vec!["S_Tail".to_owned()],
+                removed: Vec::new(),
+                reparented: vec![ReparentedBone {
+                    name: "Waist".to_owned(),
+                    reference_parent: Some("Hip".to_owned()),
+                    parent: Some("Trans".to_owned()),
+                }],
+                changed_transforms: vec![ChangedTransform {
+                    name: "Hip".to_owned(),
+                    max_difference: 1.0,
+                }],
+                reordered: vec![ReorderedBone {
+                    name: "Waist".to_owned(),
+                    reference_index: 2,
+                    index: 1,
+                }],
+            },
+            diff
+        );
+
+        assert_eq!(
+            "Added Bones (1)\n  S_Tail\nRemoved Bones (0)\nReparented Bones (1)\n  Waist: Hip -> Trans\nChanged Transforms (1)\n  Hip: 1\nOrder Differences (1)\n  Waist: 2 -> 1\n",
+            skel_diff_text(&diff)
+        );
+    }
+
+    #[test]
+    fn skel_diff_inserted_bone_not_reordered() {
+        let reference = skel(vec![
+            bone("Trans", None, Mat4::IDENTITY),
+            bone("Hip", Some(0), Mat4::from_translation(Vec3::X)),
+            bone("Waist", Some(1), Mat4::from_translation(Vec3::X)),
+        ]);
+        let current = skel(vec![
+            bone("Trans", None, Mat4::IDENTITY),
+            bone("S_Tail", Some(0), Mat4::IDENTITY),
+            bone("Hip", Some(0), Mat4::from_translation(Vec3::X)),
+            bone("Waist", Some(2), Mat4::from_translation(Vec3::X)),
+        ]);
+
+        let diff = skel_diff(&current, &reference, 0.001);
+        assert_eq!(vec!["S_Tail".to_owned()], diff.added);
+        assert!(diff.reordered.is_empty());
+    }
+}
@@ -24,7 +24,9 @@ use std::{
 use thumbnail::Thumbnail;
 
 pub mod app;
+pub mod blend;
 pub mod capture;
+pub mod compare;
 pub mod editors;
 pub mod log;
 pub mod material;
@@ -32,11 +34,16 @@ pub mod model_folder;
 pub mod path;
 pub mod preferences;
 pub mod presets;
+pub mod rename;
+pub mod search;
 pub mod thumbnail;
 pub mod update;
 pub mod validation;
 pub mod widgets;
 
+#[cfg(test)]
+mod test_utils;
+
 pub static FONT_BYTES: &[u8] = include_bytes!("fonts/NotoSansSC-Regular.otf");
 
 type FileResult<T> = Option<T>;
@@ -55,6 +62,21 @@ pub enum EditorMessage {
         mesh_object_name: String,
         mesh_object_subindex: u64,
     },
+    RenameMeshObject {
+        mesh_object_name: String,
+        mesh_object_subindex: u64,
+    },
+    RenameMaterial {
+        material_label: String,
+    },
+    RenameBone {
+        bone_name: String,
+    },
+    /// Move the vertex weights for a deleted bone to its parent if present.
+    ReassignBoneWeights {
+        bone_name: String,
+        parent_bone_name: Option<String>,
+    },
 }
 
 impl EditorResponse {
@@ -440,6 +462,8 @@ impl LightingData {
 pub struct SwingState {
     pub selected_swing_folders: Vec<Option<usize>>,
     pub should_update_swing: bool,
+    /// The folder index of the swing.prc and the index of the selected swing bone.
+    pub selected_swing_bone: Option<(usize, usize)>,
 
     // Collisions are often shared between params.
     // Use a shared set to avoid tracking shape types separately.
@@ -479,6 +503,13 @@ impl Default for AnimationState {
 pub struct AnimationSlot {
     pub is_enabled: bool,
     pub animation: Option<AnimationIndex>,
+    /// The contribution of this slot's transforms from 0.0 to 1.0.
+    pub weight: f32,
+    pub blend_mode: BlendMode,
+    /// Only animate this bone and its descendants if set.
+    pub bone_mask: Option<String>,
+    pub frame_offset: f32,
+    pub speed: f32,
 }
 
 impl AnimationSlot {
@@ -487,8 +518,22 @@ impl AnimationSlot {
         Self {
             is_enabled: true,
             animation: None,
+            weight: 1.0,
+            blend_mode: BlendMode::Override,
+            bone_mask: None,
+            frame_offset: 0.0,
+            speed: 1.0,
         }
     }
+
+    /// Returns `true` if the slot applies its anim without any blending.
+    pub fn is_unblended(&self) -> bool {
+        self.weight == 1.0
+            && self.blend_mode == BlendMode::Override
+            && self.bone_mask.is_none()
+            && self.frame_offset == 0.0
+            && self.speed == 1.0
+    }
 }
 
 impl Default for AnimationSlot {
@@ -497,6 +542,14 @@ impl Default for AnimationSlot {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BlendMode {
+    /// Replace the values from previous slots.
+    Override,
+    /// Add the difference from the rest pose to the values from previous slots.
+    Additive,
+}
+
 #[derive(Clone, Copy, PartialEq, Eq)]
 pub struct AnimationIndex {
     pub folder_index: usize,
@@ -869,6 +922,45 @@ fn save_file_as<T: SsbhData>(
     }
 }
 
+fn export_json<T: Serialize>(file: &T, folder_name: &Path, file_name: &str) {
+    if let Some(file_path) = FileDialog::new()
+        .set_directory(folder_name)
+        .set_file_name(format!("{file_name}.json"))
+        .add_filter("JSON", &["json"])
+        .save_file()
+    {
+        match serde_json::to_string_pretty(file) {
+            Ok(json) => {
+                if let Err(e) = std::fs::write(&file_path, json) {
+                    error!("Failed to save {file_path:?}: {e}");
+                }
+            }
+            Err(e) => error!("Failed to convert {file_name} to JSON: {e}"),
+        }
+    }
+}
+
+fn import_json<T: for<'de> Deserialize<'de>>(file: &mut T, folder_name: &Path) -> bool {
+    if let Some(file_path) = FileDialog::new()
+        .set_directory(folder_name)
+        .add_filter("JSON", &["json"])
+        .pick_file()
+    {
+        match std::fs::read(&file_path).and_then(|data| Ok(serde_json::from_slice(&data)?)) {
+            Ok(new_file) => {
+                *file = new_file;
+                true
+            }
+            Err(e) => {
+                error!("Failed to read {file_path:?}: {e}");
+                false
+            }
+        }
+    } else {
+        false
+    }
+}
+
 pub fn update_color_theme(preferences: &AppPreferences, ctx: &egui::Context) {
     if preferences.dark_mode {
         ctx.set_visuals(Visuals {
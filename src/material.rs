@@ -19,6 +19,22 @@ pub fn load_material_presets<P: AsRef<std::path::Path>>(path: P) -> Vec<MatlEntr
         .unwrap_or_default()
 }
 
+pub fn save_material_presets(presets: &[MatlEntryData], file: std::path::PathBuf) {
+    // TODO: Give a visual indication that the file saved?
+    match serde_json::to_string_pretty(&MatlData {
+        major_version: 1,
+        minor_version: 6,
+        entries: presets.to_vec(),
+    }) {
+        Ok(presets_json) => {
+            if let Err(e) = std::fs::write(file, presets_json) {
+                error!("Failed to save material presets JSON: {e}");
+            }
+        }
+        Err(e) => error!("Failed to convert material presets to JSON: {e}"),
+    }
+}
+
 pub fn apply_preset(entry: &MatlEntryData, preset: &MatlEntryData) -> MatlEntryData {
     // Textures paths are mesh specific and should be preserved if possible.
     // Remaining textures should use neutral default textures.
@@ -1,4 +1,6 @@
-use crate::{CameraValues, path::preferences_file, widgets_dark};
+use std::collections::BTreeMap;
+
+use crate::{BlendMode, CameraValues, path::preferences_file, widgets_dark};
 use log::error;
 use serde::{Deserialize, Serialize};
 use strum::{Display, EnumString, EnumVariantNames};
@@ -35,6 +37,63 @@ pub struct AppPreferences {
     pub graphics_backend: GraphicsBackend,
     pub scale_factor: f32,
     pub default_camera: CameraValues,
+    /// Animation and swing assignments for each model folder path.
+    pub folder_assignments: BTreeMap<String, FolderAssignments>,
+}
+
+/// Assignments stored by path and file name since indices change between sessions.
+#[derive(Debug, Default, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct FolderAssignments {
+    pub animation_slots: Vec<AnimationSlotAssignment>,
+    pub swing_folder: Option<String>,
+}
+
+impl FolderAssignments {
+    /// The paths of the folders containing the assigned anims and swing.prc.
+    pub fn referenced_folders(&self) -> impl Iterator<Item = &str> {
+        self.animation_slots
+            .iter()
+            .filter_map(|s| s.folder_path.as_deref())
+            .chain(self.swing_folder.as_deref())
+    }
+
+    /// Returns `true` if there is nothing to restore.
+    pub fn is_default(&self) -> bool {
+        self.swing_folder.is_none()
+            && self
+                .animation_slots
+                .iter()
+                .all(|s| *s == AnimationSlotAssignment::default())
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct AnimationSlotAssignment {
+    pub is_enabled: bool,
+    pub folder_path: Option<String>,
+    pub file_name: Option<String>,
+    pub weight: f32,
+    pub blend_mode: BlendMode,
+    pub bone_mask: Option<String>,
+    pub frame_offset: f32,
+    pub speed: f32,
+}
+
+impl Default for AnimationSlotAssignment {
+    fn default() -> Self {
+        Self {
+            is_enabled: true,
+            folder_path: None,
+            file_name: None,
+            weight: 1.0,
+            blend_mode: BlendMode::Override,
+            bone_mask: None,
+            frame_offset: 0.0,
+            speed: 1.0,
+        }
+    }
 }
 
 impl AppPreferences {
@@ -83,6 +142,7 @@ impl Default for AppPreferences {
             graphics_backend: GraphicsBackend::default(),
             scale_factor: 1.0,
             default_camera: CameraValues::default(),
+            folder_assignments: BTreeMap::new(),
         }
     }
 }
@@ -0,0 +1,1098 @@
+use std::collections::BTreeSet;
+
+use ssbh_data::{
+    anim_data::{GroupType, NodeData},
+    matl_data::MatlEntryData,
+    mesh_data::MeshObjectData,
+    prelude::*,
+};
+
+use crate::model_folder::{ModelFolderState, find_anim_folders};
+
+/// The item to rename and any files that should be left unchanged.
+pub struct RenameState {
+    pub folder_index: usize,
+    pub target: RenameTarget,
+    pub new_name: String,
+    pub new_subindex: u64,
+    pub skipped_files: BTreeSet<(Option<usize>, String)>,
+}
+
+impl RenameState {
+    pub fn new(folder_index: usize, target: RenameTarget) -> Self {
+        let (new_name, new_subindex) = match &target {
+            RenameTarget::MeshObject { name, subindex } => (name.clone(), *subindex),
+            RenameTarget::MaterialLabel { label } => (label.clone(), 0),
+            RenameTarget::Bone { name } => (name.clone(), 0),
+        };
+        Self {
+            folder_index,
+            target,
+            new_name,
+            new_subindex,
+            skipped_files: BTreeSet::new(),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum RenameTarget {
+    MeshObject { name: String, subindex: u64 },
+    MaterialLabel { label: String },
+    Bone { name: String },
+}
+
+/// A file that references the renamed item.
+/// The folder index is `None` for the user material presets.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct RenameChange {
+    pub folder_index: Option<usize>,
+    pub file_name: String,
+    pub count: usize,
+}
+
+/// Check if the new name is valid before renaming.
+pub fn rename_error(models: &[ModelFolderState], state: &RenameState) -> Option<String> {
+    if state.new_name.is_empty() {
+        return Some("The new name must not be empty.".to_owned());
+    }
+
+    let model = models.get(state.folder_index)?;
+    match &state.target {
+        RenameTarget::MeshObject { name, subindex } => {
+            if state.new_name == *name && state.new_subindex == *subindex {
+                return None;
+            }
+
+            let is_duplicate = model.model.meshes.iter().any(|(_, mesh)| {
+                mesh.iter()
+                    .flat_map(|m| &m.objects)
+                    .any(|o| o.name == state.new_name && o.subindex == state.new_subindex)
+            });
+            is_duplicate.then(|| {
+                format!(
+                    "A mesh object named {:?} with subindex {} already exists.",
+                    state.new_name, state.new_subindex
+                )
+            })
+        }
+        RenameTarget::MaterialLabel { label } => {
+            if state.new_name == *label {
+                return None;
+            }
+
+            let is_duplicate = model.model.matls.iter().any(|(_, matl)| {
+                matl.iter()
+                    .flat_map(|m| &m.entries)
+                    .any(|e| e.material_label == state.new_name)
+            });
+            is_duplicate
+                .then(|| format!("A material with label {:?} already exists.", state.new_name))
+        }
+        RenameTarget::Bone { name } => {
+            if state.new_name == *name {
+                return None;
+            }
+
+            let is_duplicate = model.model.skels.iter().any(|(_, skel)| {
+                skel.iter()
+                    .flat_map(|s| &s.bones)
+                    .any(|b| b.name == state.new_name)
+            });
+            is_duplicate.then(|| format!("A bone named {:?} already exists.", state.new_name))
+        }
+    }
+}
+
+/// Find the files referencing the renamed item and update the files where `should_apply` returns `true`.
+pub fn rename(
+    models: &mut [ModelFolderState],
+    material_presets: &mut [MatlEntryData],
+    state: &RenameState,
+    should_apply: impl Fn(Option<usize>, &str) -> bool,
+) -> Vec<RenameChange> {
+    match &state.target {
+        RenameTarget::MeshObject { name, subindex } => rename_mesh_object(
+            models,
+            state.folder_index,
+            name,
+            *subindex,
+            &state.new_name,
+            state.new_subindex,
+            should_apply,
+        ),
+        RenameTarget::MaterialLabel { label } => rename_material_label(
+            models,
+            material_presets,
+            state.folder_index,
+            label,
+            &state.new_name,
+            should_apply,
+        ),
+        RenameTarget::Bone { name } => rename_bone(
+            models,
+            state.folder_index,
+            name,
+            &state.new_name,
+            should_apply,
+        ),
+    }
+}
+
+fn rename_mesh_object(
+    models: &mut [ModelFolderState],
+    folder_index: usize,
+    name: &str,
+    subindex: u64,
+    new_name: &str,
+    new_subindex: u64,
+    should_apply: impl Fn(Option<usize>, &str) -> bool,
+) -> Vec<RenameChange> {
+    let mut changes = Vec::new();
+    if models.get(folder_index).is_none() {
+        return changes;
+    }
+
+    // Find the related animations before modifying any files.
+    let anim_folder_indices: Vec<_> = find_anim_folders(&models[folder_index], models)
+        .into_iter()
+        .rev()
+        .map(|(i, _)| i)
+        .collect();
+
+    let model = &mut models[folder_index];
+    let renamed_object = model
+        .model
+        .meshes
+        .iter()
+        .filter_map(|(_, m)| m.as_ref())
+        .flat_map(|m| &m.objects)
+        .find(|o| o.name == name && o.subindex == subindex)
+        .map(|o| MeshObjectData {
+            name: new_name.to_owned(),
+            subindex: new_subindex,
+            ..o.clone()
+        });
+    let other_names: Vec<_> = model
+        .model
+        .meshes
+        .iter()
+        .filter_map(|(_, m)| m.as_ref())
+        .flat_map(|m| &m.objects)
+        .filter(|o| o.name != name || o.subindex != subindex)
+        .map(|o| o.name.clone())
+        .collect();
+
+    // Meshex entries are in order of subindex for mesh objects with the same name.
+    let subindices = |object_name: &str| {
+        let mut subindices: Vec<_> = model
+            .model
+            .meshes
+            .iter()
+            .filter_map(|(_, m)| m.as_ref())
+            .flat_map(|m| &m.objects)
+            .filter(|o| o.name == object_name && (o.name != name || o.subindex != subindex))
+            .map(|o| o.subindex)
+            .collect();
+        subindices.sort();
+        subindices
+    };
+    let old_subindices = subindices(name);
+    let new_subindices = subindices(new_name);
+
+    rename_in_files(
+        &mut model.model.meshes,
+        &mut model.changed.meshes,
+        folder_index,
+        &should_apply,
+        &mut changes,
+        |mesh, apply| {
+            let mut count = 0;
+            for o in &mut mesh.objects {
+                if o.name == name && o.subindex == subindex {
+                    count += 1;
+                    if apply {
+                        o.name = new_name.to_owned();
+                        o.subindex = new_subindex;
+                    }
+                }
+            }
+            count
+        },
+    );
+
+    rename_in_files(
+        &mut model.model.modls,
+        &mut model.changed.modls,
+        folder_index,
+        &should_apply,
+        &mut changes,
+        |modl, apply| {
+            let mut count = 0;
+            for e in &mut modl.entries {
+                if e.mesh_object_name == name && e.mesh_object_subindex == subindex {
+                    count += 1;
+                    if apply {
+                        e.mesh_object_name = new_name.to_owned();
+                        e.mesh_object_subindex = new_subindex;
+                    }
+                }
+            }
+            count
+        },
+    );
+
+    if let Some(renamed_object) = &renamed_object {
+        rename_in_files(
+            &mut model.model.meshexes,
+            &mut model.changed.meshexes,
+            folder_index,
+            &should_apply,
+            &mut changes,
+            |meshex, apply| {
+                rename_meshex_entry(
+                    meshex,
+                    name,
+                    subindex,
+                    &old_subindices,
+                    &new_subindices,
+                    renamed_object,
+                    apply,
+                )
+            },
+        );
+    }
+
+    // The adjb references mesh objects by index, so renaming doesn't require any changes.
+
+    for anim_folder_index in anim_folder_indices {
+        let model = &mut models[anim_folder_index];
+        rename_in_files(
+            &mut model.model.anims,
+            &mut model.changed.anims,
+            anim_folder_index,
+            &should_apply,
+            &mut changes,
+            |anim, apply| rename_visibility_nodes(anim, name, new_name, &other_names, apply),
+        );
+    }
+
+    changes
+}
+
+fn rename_material_label(
+    models: &mut [ModelFolderState],
+    material_presets: &mut [MatlEntryData],
+    folder_index: usize,
+    label: &str,
+    new_label: &str,
+    should_apply: impl Fn(Option<usize>, &str) -> bool,
+) -> Vec<RenameChange> {
+    let mut changes = Vec::new();
+    if models.get(folder_index).is_none() {
+        return changes;
+    }
+
+    // Find the related animations before modifying any files.
+    let anim_folder_indices: Vec<_> = find_anim_folders(&models[folder_index], models)
+        .into_iter()
+        .rev()
+        .map(|(i, _)| i)
+        .collect();
+
+    let model = &mut models[folder_index];
+    rename_in_files(
+        &mut model.model.matls,
+        &mut model.changed.matls,
+        folder_index,
+        &should_apply,
+        &mut changes,
+        |matl, apply| rename_matl_entries(&mut matl.entries, label, new_label, apply),
+    );
+
+    // Other numdlb files in the folder may also use the model.numatb.
+    rename_in_files(
+        &mut model.model.modls,
+        &mut model.changed.modls,
+        folder_index,
+        &should_apply,
+        &mut changes,
+        |modl, apply| {
+            let mut count = 0;
+            for e in &mut modl.entries {
+                if e.material_label == label {
+                    count += 1;
+                    if apply {
+                        e.material_label = new_label.to_owned();
+                    }
+                }
+            }
+            count
+        },
+    );
+
+    for anim_folder_index in anim_folder_indices {
+        let model = &mut models[anim_folder_index];
+        rename_in_files(
+            &mut model.model.anims,
+            &mut model.changed.anims,
+            anim_folder_index,
+            &should_apply,
+            &mut changes,
+            |anim, apply| rename_material_nodes(anim, label, new_label, apply),
+        );
+    }
+
+    let file_name = "Material Presets";
+    let count = rename_matl_entries(
+        material_presets,
+        label,
+        new_label,
+        should_apply(None, file_name),
+    );
+    if count > 0 {
+        changes.push(RenameChange {
+            folder_index: None,
+            file_name: file_name.to_owned(),
+            count,
+        });
+    }
+
+    changes
+}
+
+fn rename_bone(
+    models: &mut [ModelFolderState],
+    folder_index: usize,
+    name: &str,
+    new_name: &str,
+    should_apply: impl Fn(Option<usize>, &str) -> bool,
+) -> Vec<RenameChange> {
+    let mut changes = Vec::new();
+    if models.get(folder_index).is_none() {
+        return changes;
+    }
+
+    // Find the related animations before modifying any files.
+    let anim_folder_indices: Vec<_> = find_anim_folders(&models[folder_index], models)
+        .into_iter()
+        .rev()
+        .map(|(i, _)| i)
+        .collect();
+
+    let model = &mut models[folder_index];
+    rename_in_files(
+        &mut model.model.skels,
+        &mut model.changed.skels,
+        folder_index,
+        &should_apply,
+        &mut changes,
+        |skel, apply| {
+            let mut count = 0;
+            for b in &mut skel.bones {
+                count += rename_bone_name(&mut b.name, name, new_name, apply);
+            }
+            count
+        },
+    );
+
+    rename_in_files(
+        &mut model.model.meshes,
+        &mut model.changed.meshes,
+        folder_index,
+        &should_apply,
+        &mut changes,
+        |mesh, apply| {
+            let mut count = 0;
+            for o in &mut mesh.objects {
+                count += rename_bone_name(&mut o.parent_bone_name, name, new_name, apply);
+                for influence in &mut o.bone_influences {
+                    count += rename_bone_name(&mut influence.bone_name, name, new_name, apply);
+                }
+            }
+            count
+        },
+    );
+
+    rename_in_files(
+        &mut model.model.hlpbs,
+        &mut model.changed.hlpbs,
+        folder_index,
+        &should_apply,
+        &mut changes,
+        |hlpb, apply| {
+            let mut count = 0;
+            for o in &mut hlpb.orient_constraints {
+                for bone_name in [
+                    &mut o.parent_bone_name1,
+                    &mut o.parent_bone_name2,
+                    &mut o.source_bone_name,
+                    &mut o.target_bone_name,
+                ] {
+                    count += rename_bone_name(bone_name, name, new_name, apply);
+                }
+            }
+            for a in &mut hlpb.aim_constraints {
+                for bone_name in [
+                    &mut a.aim_bone_name1,
+                    &mut a.aim_bone_name2,
+                    &mut a.target_bone_name1,
+                    &mut a.target_bone_name2,
+                ] {
+                    count += rename_bone_name(bone_name, name, new_name, apply);
+                }
+            }
+            count
+        },
+    );
+
+    for anim_folder_index in anim_folder_indices {
+        let model = &mut models[anim_folder_index];
+        rename_in_files(
+            &mut model.model.anims,
+            &mut model.changed.anims,
+            anim_folder_index,
+            &should_apply,
+            &mut changes,
+            |anim, apply| rename_transform_nodes(anim, name, new_name, apply),
+        );
+    }
+
+    changes
+}
+
+fn rename_bone_name(bone_name: &mut String, name: &str, new_name: &str, apply: bool) -> usize {
+    if bone_name == name {
+        if apply {
+            *bone_name = new_name.to_owned();
+        }
+        1
+    } else {
+        0
+    }
+}
+
+fn rename_transform_nodes(anim: &mut AnimData, name: &str, new_name: &str, apply: bool) -> usize {
+    let mut count = 0;
+    for group in &mut anim.groups {
+        // Avoid creating duplicate nodes for the same bone.
+        if group.group_type != GroupType::Transform
+            || group.nodes.iter().any(|n| n.name == new_name)
+        {
+            continue;
+        }
+
+        for node in &mut group.nodes {
+            count += rename_bone_name(&mut node.name, name, new_name, apply);
+        }
+    }
+    count
+}
+
+fn rename_matl_entries(
+    entries: &mut [MatlEntryData],
+    label: &str,
+    new_label: &str,
+    apply: bool,
+) -> usize {
+    let mut count = 0;
+    for e in entries {
+        if e.material_label == label {
+            count += 1;
+            if apply {
+                e.material_label = new_label.to_owned();
+            }
+        }
+    }
+    count
+}
+
+fn rename_material_nodes(anim: &mut AnimData, label: &str, new_label: &str, apply: bool) -> usize {
+    let mut count = 0;
+    for group in &mut anim.groups {
+        // Avoid creating duplicate nodes for the same material.
+        if group.group_type != GroupType::Material
+            || group.nodes.iter().any(|n| n.name == new_label)
+        {
+            continue;
+        }
+
+        for node in &mut group.nodes {
+            if node.name == label {
+                count += 1;
+                if apply {
+                    node.name = new_label.to_owned();
+                }
+            }
+        }
+    }
+    count
+}
+
+fn rename_in_files<T>(
+    files: &mut [(String, Option<T>)],
+    changed: &mut [bool],
+    folder_index: usize,
+    should_apply: &impl Fn(Option<usize>, &str) -> bool,
+    changes: &mut Vec<RenameChange>,
+    rename: impl Fn(&mut T, bool) -> usize,
+) {
+    for (i, (file_name, file)) in files.iter_mut().enumerate() {
+        if let Some(file) = file {
+            let apply = should_apply(Some(folder_index), file_name);
+            let count = rename(file, apply);
+            if count > 0 {
+                if apply && let Some(changed) = changed.get_mut(i) {
+                    *changed = true;
+                }
+                changes.push(RenameChange {
+                    folder_index: Some(folder_index),
+                    file_name: file_name.clone(),
+                    count,
+                });
+            }
+        }
+    }
+}
+
+/// Move the entry for `name` and `subindex` to the group for `renamed_object`.
+/// `old_subindices` and `new_subindices` are the subindices of the other mesh objects
+/// with the old and new name.
+fn rename_meshex_entry(
+    meshex: &mut MeshExData,
+    name: &str,
+    subindex: u64,
+    old_subindices: &[u64],
+    new_subindices: &[u64],
+    renamed_object: &MeshObjectData,
+    apply: bool,
+) -> usize {
+    let Some(group_index) = meshex
+        .mesh_object_groups
+        .iter()
+        .position(|g| g.mesh_object_full_name == name)
+    else {
+        return 0;
+    };
+
+    // Entries are in order of subindex, so find the position of the renamed object.
+    let position = old_subindices.iter().filter(|s| **s < subindex).count();
+    let Some(flags) = meshex.mesh_object_groups[group_index]
+        .entry_flags
+        .get(position)
+        .cloned()
+    else {
+        return 0;
+    };
+
+    if apply {
+        // The remaining entries stay in order for the other mesh objects with the old name.
+        let group = &mut meshex.mesh_object_groups[group_index];
+        group.entry_flags.remove(position);
+        if group.entry_flags.is_empty() {
+            meshex.mesh_object_groups.remove(group_index);
+        }
+
+        let new_group_index = meshex
+            .mesh_object_groups
+            .iter()
+            .position(|g| g.mesh_object_full_name == renamed_object.name)
+            .unwrap_or_else(|| {
+                // Use the same naming conventions and bounding data as a rebuilt numshexb.
+                let mut new_groups =
+                    MeshExData::from_mesh_objects(std::slice::from_ref(renamed_object))
+                        .mesh_object_groups;
+                for group in &mut new_groups {
+                    group.entry_flags.clear();
+                }
+                meshex.mesh_object_groups.extend(new_groups);
+                meshex.mesh_object_groups.len() - 1
+            });
+
+        let group = &mut meshex.mesh_object_groups[new_group_index];
+        let new_position = new_subindices
+            .iter()
+            .filter(|s| **s < renamed_object.subindex)
+            .count()
+            .min(group.entry_flags.len());
+        group.entry_flags.insert(new_position, flags);
+    }
+
+    1
+}
+
+/// Returns the visibility track name that affects mesh objects named `mesh_object_name`.
+fn visibility_node_name(mesh_object_name: &str) -> &str {
+    mesh_object_name
+        .split("_VIS")
+        .next()
+        .unwrap_or(mesh_object_name)
+}
+
+fn rename_visibility_nodes(
+    anim: &mut AnimData,
+    name: &str,
+    new_name: &str,
+    other_names: &[String],
+    apply: bool,
+) -> usize {
+    let new_node_name = visibility_node_name(new_name);
+
+    let mut count = 0;
+    for group in &mut anim.groups {
+        if group.group_type != GroupType::Visibility
+            || group.nodes.iter().any(|n| n.name == new_node_name)
+        {
+            continue;
+        }
+
+        let Some(i) = visibility_node_index(&group.nodes, name) else {
+            continue;
+        };
+        if new_name.starts_with(&group.nodes[i].name) {
+            continue;
+        }
+
+        count += 1;
+        if apply {
+            // Keep the original track if other mesh objects still use it.
+            if other_names
+                .iter()
+                .any(|n| visibility_node_index(&group.nodes, n) == Some(i))
+            {
+                let node = NodeData {
+                    name: new_node_name.to_owned(),
+                    ..group.nodes[i].clone()
+                };
+                group.nodes.push(node);
+            } else {
+                group.nodes[i].name = new_node_name.to_owned();
+            }
+        }
+    }
+    count
+}
+
+/// Visibility tracks affect mesh objects starting with the node name.
+/// Only the longest matching node name applies to each mesh object.
+fn visibility_node_index(nodes: &[NodeData], mesh_object_name: &str) -> Option<usize> {
+    nodes
+        .iter()
+        .enumerate()
+        .filter(|(_, n)| mesh_object_name.starts_with(&n.name))
+        .max_by_key(|(_, n)| n.name.len())
+        .map(|(i, _)| i)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{model_folder::FileChanged, validation::ModelValidationErrors};
+    use ssbh_data::{
+        anim_data::{GroupData, TrackData, TrackValues, TransformFlags},
+        mesh_data::BoneInfluence,
+        modl_data::ModlEntryData,
+        skel_data::{BillboardType, BoneData},
+    };
+    use ssbh_wgpu::ModelFolder;
+
+    fn model_folder(mesh: MeshData, modl: ModlData, anim: AnimData) -> ModelFolderState {
+        ModelFolderState {
+            folder_path: "/model/body/c00".into(),
+            model: ModelFolder {
+                meshes: vec![("model.numshb".to_owned(), Some(mesh))],
+                skels: Vec::new(),
+                matls: Vec::new(),
+                modls: vec![("model.numdlb".to_owned(), Some(modl))],
+                adjs: Vec::new(),
+                anims: vec![("model.nuanmb".to_owned(), Some(anim))],
+                hlpbs: Vec::new(),
+                nutexbs: Vec::new(),
+                meshexes: Vec::new(),
+                xmbs: Vec::new(),
+            },
+            swing_prc: None,
+            thumbnails: Vec::new(),
+            validation: ModelValidationErrors::default(),
+            changed: FileChanged {
+                meshes: vec![false],
+                modls: vec![false],
+                anims: vec![false],
+                ..Default::default()
+            },
+            is_meshlist_open: true,
+        }
+    }
+
+    fn mesh_object(name: &str, subindex: u64) -> MeshObjectData {
+        MeshObjectData {
+            name: name.to_owned(),
+            subindex,
+            ..Default::default()
+        }
+    }
+
+    fn modl_entry(name: &str, subindex: u64) -> ModlEntryData {
+        ModlEntryData {
+            mesh_object_name: name.to_owned(),
+            mesh_object_subindex: subindex,
+            material_label: "a".to_owned(),
+        }
+    }
+
+    fn visibility_anim(node_names: &[&str]) -> AnimData {
+        AnimData {
+            major_version: 2,
+            minor_version: 0,
+            final_frame_index: 0.0,
+            groups: vec![GroupData {
+                group_type: GroupType::Visibility,
+                nodes: node_names
+                    .iter()
+                    .map(|name| NodeData {
+                        name: name.to_string(),
+                        tracks: vec![TrackData {
+                            name: "Visibility".to_owned(),
+                            compensate_scale: false,
+                            transform_flags: TransformFlags::default(),
+                            values: TrackValues::Boolean(vec![true]),
+                        }],
+                    })
+                    .collect(),
+            }],
+        }
+    }
+
+    fn test_models() -> Vec<ModelFolderState> {
+        vec![model_folder(
+            MeshData {
+                major_version: 1,
+                minor_version: 10,
+                objects: vec![
+                    mesh_object("a_VIS_O_OBJShape", 0),
+                    mesh_object("b_VIS_O_OBJShape", 0),
+                    mesh_object("b_VIS_O_OBJShape", 1),
+                ],
+            },
+            ModlData {
+                major_version: 1,
+                minor_version: 0,
+                model_name: String::new(),
+                skeleton_file_name: String::new(),
+                material_file_names: Vec::new(),
+                animation_file_name: None,
+                mesh_file_name: String::new(),
+                entries: vec![
+                    modl_entry("a_VIS_O_OBJShape", 0),
+                    modl_entry("b_VIS_O_OBJShape", 0),
+                    modl_entry("b_VIS_O_OBJShape", 1),
+                ],
+            },
+            visibility_anim(&["a", "b"]),
+        )]
+    }
+
+    #[test]
+    fn rename_meshex_entry_middle_subindex() {
+        let mut meshex = MeshExData::from_mesh_objects(&[
+            mesh_object("a", 0),
+            mesh_object("a", 1),
+            mesh_object("a", 2),
+        ]);
+
+        let count = rename_meshex_entry(
+            &mut meshex,
+            "a",
+            1,
+            &[0, 2],
+            &[],
+            &mesh_object("b", 0),
+            true,
+        );
+        assert_eq!(1, count);
+
+        let entry_count = |meshex: &MeshExData, name: &str| {
+            meshex
+                .mesh_object_groups
+                .iter()
+                .find(|g| g.mesh_object_full_name == name)
+                .map(|g| g.entry_flags.len())
+        };
+        assert_eq!(Some(2), entry_count(&meshex, "a"));
+        assert_eq!(Some(1), entry_count(&meshex, "b"));
+
+        // Missing entries shouldn't report any changes.
+        let count = rename_meshex_entry(
+            &mut meshex,
+            "a",
+            5,
+            &[0, 1, 2, 3, 4],
+            &[],
+            &mesh_object("c", 0),
+            true,
+        );
+        assert_eq!(0, count);
+        assert_eq!(None, entry_count(&meshex, "c"));
+    }
+
+    #[test]
+    fn rename_mesh_object_duplicate() {
+        let models = test_models();
+        let mut state = RenameState::new(
+            0,
+            RenameTarget::MeshObject {
+                name: "b_VIS_O_OBJShape".to_owned(),
+                subindex: 1,
+            },
+        );
+        state.new_subindex = 0;
+        assert!(rename_error(&models, &state).is_some());
+
+        state.new_subindex = 2;
+        assert_eq!(None, rename_error(&models, &state));
+    }
+
+    #[test]
+    fn rename_mesh_object_preview() {
+        let mut models = test_models();
+        let mut state = RenameState::new(
+            0,
+            RenameTarget::MeshObject {
+                name: "a_VIS_O_OBJShape".to_owned(),
+                subindex: 0,
+            },
+        );
+        state.new_name = "c_VIS_O_OBJShape".to_owned();
+
+        let changes = rename(&mut models, &mut [], &state, |_, _| false);
+        assert_eq!(
+            vec![
+                RenameChange {
+                    folder_index: Some(0),
+                    file_name: "model.numshb".to_owned(),
+                    count: 1
+                },
+                RenameChange {
+                    folder_index: Some(0),
+                    file_name: "model.numdlb".to_owned(),
+                    count: 1
+                },
+                RenameChange {
+                    folder_index: Some(0),
+                    file_name: "model.nuanmb".to_owned(),
+                    count: 1
+                },
+            ],
+            changes
+        );
+
+        // The preview shouldn't modify any files.
+        let mesh = models[0].model.meshes[0].1.as_ref().unwrap();
+        assert_eq!("a_VIS_O_OBJShape", mesh.objects[0].name);
+        assert_eq!(vec![false], models[0].changed.meshes);
+    }
+
+    #[test]
+    fn rename_mesh_object_shared_visibility_track() {
+        let mut models = test_models();
+        let mut state = RenameState::new(
+            0,
+            RenameTarget::MeshObject {
+                name: "b_VIS_O_OBJShape".to_owned(),
+                subindex: 1,
+            },
+        );
+        state.new_name = "c_VIS_O_OBJShape".to_owned();
+        state.new_subindex = 0;
+
+        rename(&mut models, &mut [], &state, |_, _| true);
+
+        let mesh = models[0].model.meshes[0].1.as_ref().unwrap();
+        assert_eq!("c_VIS_O_OBJShape", mesh.objects[2].name);
+        assert_eq!(0, mesh.objects[2].subindex);
+
+        let modl = models[0].model.modls[0].1.as_ref().unwrap();
+        assert_eq!(modl_entry("c_VIS_O_OBJShape", 0), modl.entries[2]);
+
+        // The other subindex still uses the original track.
+        let anim = models[0].model.anims[0].1.as_ref().unwrap();
+        let node_names: Vec<_> = anim.groups[0].nodes.iter().map(|n| &n.name).collect();
+        assert_eq!(vec!["a", "b", "c"], node_names);
+
+        assert_eq!(vec![true], models[0].changed.meshes);
+        assert_eq!(vec![true], models[0].changed.modls);
+        assert_eq!(vec![true], models[0].changed.anims);
+    }
+
+    #[test]
+    fn rename_mesh_object_longest_visibility_node() {
+        let mut models = test_models();
+        models[0].model.anims[0].1 = Some(visibility_anim(&["a", "a_VIS", "b"]));
+        let mut state = RenameState::new(
+            0,
+            RenameTarget::MeshObject {
+                name: "a_VIS_O_OBJShape".to_owned(),
+                subindex: 0,
+            },
+        );
+        state.new_name = "c_VIS_O_OBJShape".to_owned();
+
+        rename(&mut models, &mut [], &state, |_, _| true);
+
+        // Only the longest matching node applies to the mesh object.
+        let anim = models[0].model.anims[0].1.as_ref().unwrap();
+        let node_names: Vec<_> = anim.groups[0].nodes.iter().map(|n| &n.name).collect();
+        assert_eq!(vec!["a", "c", "b"], node_names);
+    }
+
+    fn matl_entry(label: &str) -> MatlEntryData {
+        MatlEntryData {
+            material_label: label.to_owned(),
+            shader_label: "SFX_PBS_0100000008008269_opaque".to_owned(),
+            blend_states: Vec::new(),
+            floats: Vec::new(),
+            booleans: Vec::new(),
+            vectors: Vec::new(),
+            rasterizer_states: Vec::new(),
+            samplers: Vec::new(),
+            textures: Vec::new(),
+            uv_transforms: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn rename_material_label_workspace() {
+        let mut models = test_models();
+        models[0].model.matls = vec![(
+            "model.numatb".to_owned(),
+            Some(MatlData {
+                major_version: 1,
+                minor_version: 6,
+                entries: vec![matl_entry("a"), matl_entry("b")],
+            }),
+        )];
+        models[0].changed.matls = vec![false];
+        if let Some(anim) = models[0].model.anims[0].1.as_mut() {
+            anim.groups.push(GroupData {
+                group_type: GroupType::Material,
+                nodes: vec![NodeData {
+                    name: "a".to_owned(),
+                    tracks: Vec::new(),
+                }],
+            });
+        }
+        let mut presets = vec![matl_entry("a")];
+
+        let mut state = RenameState::new(
+            0,
+            RenameTarget::MaterialLabel {
+                label: "a".to_owned(),
+            },
+        );
+        state.new_name = "b".to_owned();
+        assert!(rename_error(&models, &state).is_some());
+
+        state.new_name = "c".to_owned();
+        assert_eq!(None, rename_error(&models, &state));
+
+        // Skip renaming the presets.
+        let changes = rename(&mut models, &mut presets, &state, |i, _| i.is_some());
+        assert_eq!(
+            vec![
+                (Some(0), "model.numatb", 1),
+                (Some(0), "model.numdlb", 3),
+                (Some(0), "model.nuanmb", 1),
+                (None, "Material Presets", 1),
+            ],
+            changes
+                .iter()
+                .map(|c| (c.folder_index, c.file_name.as_str(), c.count))
+                .collect::<Vec<_>>()
+        );
+
+        let matl = models[0].model.matls[0].1.as_ref().unwrap();
+        assert_eq!("c", matl.entries[0].material_label);
+        let modl = models[0].model.modls[0].1.as_ref().unwrap();
+        assert!(modl.entries.iter().all(|e| e.material_label == "c"));
+        let anim = models[0].model.anims[0].1.as_ref().unwrap();
+        assert_eq!("c", anim.groups[1].nodes[0].name);
+        assert_eq!("a", presets[0].material_label);
+    }
+
+    #[test]
+    fn rename_bone_workspace() {
+        let mut models = test_models();
+        models[0].model.skels = vec![(
+            "model.nusktb".to_owned(),
+            Some(SkelData {
+                major_version: 1,
+                minor_version: 0,
+                bones: vec![
+                    BoneData {
+                        name: "Trans".to_owned(),
+                        transform: [[0.0; 4]; 4],
+                        parent_index: None,
+                        billboard_type: BillboardType::Disabled,
+                    },
+                    BoneData {
+                        name: "Hip".to_owned(),
+                        transform: [[0.0; 4]; 4],
+                        parent_index: Some(0),
+                        billboard_type: BillboardType::Disabled,
+                    },
+                ],
+            }),
+        )];
+        models[0].changed.skels = vec![false];
+        if let Some(mesh) = models[0].model.meshes[0].1.as_mut() {
+            mesh.objects[0].parent_bone_name = "Trans".to_owned();
+            mesh.objects[1].bone_influences = vec![BoneInfluence {
+                bone_name: "Trans".to_owned(),
+                vertex_weights: Vec::new(),
+            }];
+        }
+        if let Some(anim) = models[0].model.anims[0].1.as_mut() {
+            anim.groups.push(GroupData {
+                group_type: GroupType::Transform,
+                nodes: vec![NodeData {
+                    name: "Trans".to_owned(),
+                    tracks: Vec::new(),
+                }],
+            });
+        }
+
+        let mut state = RenameState::new(
+            0,
+            RenameTarget::Bone {
+                name: "Trans".to_owned(),
+            },
+        );
+        state.new_name = "Hip".to_owned();
+        assert!(rename_error(&models, &state).is_some());
+
+        state.new_name = "Root".to_owned();
+        assert_eq!(None, rename_error(&models, &state));
+
+        let changes = rename(&mut models, &mut [], &state, |_, _| true);
+        assert_eq!(
+            vec![
+                (Some(0), "model.nusktb", 1),
+                (Some(0), "model.numshb", 2),
+                (Some(0), "model.nuanmb", 1),
+            ],
+            changes
+                .iter()
+                .map(|c| (c.folder_index, c.file_name.as_str(), c.count))
+                .collect::<Vec<_>>()
+        );
+
+        let skel = models[0].model.skels[0].1.as_ref().unwrap();
+        assert_eq!("Root", skel.bones[0].name);
+        let mesh = models[0].model.meshes[0].1.as_ref().unwrap();
+        assert_eq!("Root", mesh.objects[0].parent_bone_name);
+        assert_eq!("Root", mesh.objects[1].bone_influences[0].bone_name);
+        let anim = models[0].model.anims[0].1.as_ref().unwrap();
+        assert_eq!("Root", anim.groups[1].nodes[0].name);
+        assert_eq!(vec![true], models[0].changed.skels);
+    }
+}
@@ -0,0 +1,357 @@
+use crate::model_folder::ModelFolderState;
+
+#[derive(Default)]
+pub struct SearchState {
+    pub query: String,
+    pub results: Vec<SearchResult>,
+}
+
+/// A matching item and the information needed to open it in an editor.
+#[derive(Debug, PartialEq, Clone)]
+pub struct SearchResult {
+    pub folder_index: usize,
+    pub file_name: String,
+    pub item: SearchItem,
+    pub description: String,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum SearchItem {
+    Bone {
+        file_index: usize,
+        bone_index: usize,
+    },
+    MeshObject {
+        file_index: usize,
+        object_index: usize,
+    },
+    ModlEntry {
+        file_index: usize,
+        entry_index: usize,
+    },
+    Material {
+        file_index: usize,
+        entry_index: usize,
+    },
+    AnimNode {
+        file_index: usize,
+        group_index: usize,
+        node_index: usize,
+    },
+    OrientConstraint {
+        file_index: usize,
+        constraint_index: usize,
+    },
+    AimConstraint {
+        file_index: usize,
+        constraint_index: usize,
+    },
+    SwingBone {
+        swing_bone_index: usize,
+    },
+}
+
+impl SearchItem {
+    pub fn category(&self) -> &'static str {
+        match self {
+            SearchItem::Bone { .. } => "Bones",
+            SearchItem::MeshObject { .. } => "Mesh Objects",
+            SearchItem::ModlEntry { .. } => "Modl Entries",
+            SearchItem::Material { .. } => "Materials",
+            SearchItem::AnimNode { .. } => "Anim Nodes",
+            SearchItem::OrientConstraint { .. } | SearchItem::AimConstraint { .. } => {
+                "Helper Bone Constraints"
+            }
+            SearchItem::SwingBone { .. } => "Swing Bones",
+        }
+    }
+}
+
+/// Find names and references containing `query` in all files in the workspace.
+/// The search ignores case.
+pub fn search_workspace(models: &[ModelFolderState], query: &str) -> Vec<SearchResult> {
+    let mut results = Vec::new();
+    if query.is_empty() {
+        return results;
+    }
+
+    let query_lower = query.to_lowercase();
+    let is_match = |name: &str| name.to_lowercase().contains(&query_lower);
+
+    // Swing bone names are stored as hashes of the lowercase bone name.
+    let query_hash = hash40::hash40(&query_lower);
+
+    for (folder_index, model) in models.iter().enumerate() {
+        let mut push = |file_name: &str, item, description: String| {
+            results.push(SearchResult {
+                folder_index,
+                file_name: file_name.to_owned(),
+                item,
+                description,
+            })
+        };
+
+        for (file_index, (file_name, skel)) in model.model.skels.iter().enumerate() {
+            for (bone_index, bone) in skel.iter().flat_map(|s| s.bones.iter().enumerate()) {
+                if is_match(&bone.name) {
+                    push(
+                        file_name,
+                        SearchItem::Bone {
+                            file_index,
+                            bone_index,
+                        },
+                        format!("Bone {}", bone.name),
+                    );
+                }
+            }
+        }
+
+        for (file_index, (file_name, mesh)) in model.model.meshes.iter().enumerate() {
+            for (object_index, o) in mesh.iter().flat_map(|m| m.objects.iter().enumerate()) {
+                let item = SearchItem::MeshObject {
+                    file_index,
+                    object_index,
+                };
+                if is_match(&o.name) {
+                    push(file_name, item, format!("Mesh object {}", o.name));
+                }
+                if is_match(&o.parent_bone_name) {
+                    push(
+                        file_name,
+                        item,
+                        format!("Parent bone {} for {}", o.parent_bone_name, o.name),
+                    );
+                }
+                for influence in &o.bone_influences {
+                    if is_match(&influence.bone_name) {
+                        push(
+                            file_name,
+                            item,
+                            format!("Bone influence {} for {}", influence.bone_name, o.name),
+                        );
+                    }
+                }
+            }
+        }
+
+        for (file_index, (file_name, modl)) in model.model.modls.iter().enumerate() {
+            for (entry_index, e) in modl.iter().flat_map(|m| m.entries.iter().enumerate()) {
+                if is_match(&e.mesh_object_name) || is_match(&e.material_label) {
+                    push(
+                        file_name,
+                        SearchItem::ModlEntry {
+                            file_index,
+                            entry_index,
+                        },
+                        format!(
+                            "Entry {} ({}) assigned to {}",
+                            e.mesh_object_name, e.mesh_object_subindex, e.material_label
+                        ),
+                    );
+                }
+            }
+        }
+
+        for (file_index, (file_name, matl)) in model.model.matls.iter().enumerate() {
+            for (entry_index, e) in matl.iter().flat_map(|m| m.entries.iter().enumerate()) {
+                let item = SearchItem::Material {
+                    file_index,
+                    entry_index,
+                };
+                if is_match(&e.material_label) {
+                    push(file_name, item, format!("Material {}", e.material_label));
+                }
+                for texture in &e.textures {
+                    if is_match(&texture.data) {
+                        push(
+                            file_name,
+                            item,
+                            format!(
+                                "{:?} {} for {}",
+                                texture.param_id, texture.data, e.material_label
+                            ),
+                        );
+                    }
+                }
+            }
+        }
+
+        for (file_index, (file_name, anim)) in model.model.anims.iter().enumerate() {
+            for (group_index, group) in anim.iter().flat_map(|a| a.groups.iter().enumerate()) {
+                for (node_index, node) in group.nodes.iter().enumerate() {
+                    if is_match(&node.name) {
+                        push(
+                            file_name,
+                            SearchItem::AnimNode {
+                                file_index,
+                                group_index,
+                                node_index,
+                            },
+                            format!("{} node {}", group.group_type, node.name),
+                        );
+                    }
+                }
+            }
+        }
+
+        for (file_index, (file_name, hlpb)) in model.model.hlpbs.iter().enumerate() {
+            for hlpb in hlpb {
+                for (constraint_index, o) in hlpb.orient_constraints.iter().enumerate() {
+                    let names = [
+                        &o.name,
+                        &o.parent_bone_name1,
+                        &o.parent_bone_name2,
+                        &o.source_bone_name,
+                        &o.target_bone_name,
+                    ];
+                    if names.iter().any(|n| is_match(n)) {
+                        push(
+                            file_name,
+                            SearchItem::OrientConstraint {
+                                file_index,
+                                constraint_index,
+                            },
+                            format!("Orient constraint {} ({})", o.name, o.target_bone_name),
+                        );
+                    }
+                }
+
+                for (constraint_index, a) in hlpb.aim_constraints.iter().enumerate() {
+                    let names = [
+                        &a.name,
+                        &a.aim_bone_name1,
+                        &a.aim_bone_name2,
+                        &a.target_bone_name1,
+                        &a.target_bone_name2,
+                    ];
+                    if names.iter().any(|n| is_match(n)) {
+                        push(
+                            file_name,
+                            SearchItem::AimConstraint {
+                                file_index,
+                                constraint_index,
+                            },
+                            format!(
+                                "Aim constraint {} ({}, {})",
+                                a.name, a.target_bone_name1, a.target_bone_name2
+                            ),
+                        );
+                    }
+                }
+            }
+        }
+
+        if let Some(swing_prc) = &model.swing_prc {
+            for (swing_bone_index, swing_bone) in swing_prc.swingbones.iter().enumerate() {
+                let hashes = [
+                    &swing_bone.name,
+                    &swing_bone.start_bonename,
+                    &swing_bone.end_bonename,
+                ];
+                // Hashes without a known label display as hex.
+                if hashes
+                    .iter()
+                    .any(|h| h.0 == query_hash.0 || is_match(&h.to_string()))
+                {
+                    push(
+                        "swing.prc",
+                        SearchItem::SwingBone { swing_bone_index },
+                        format!(
+                            "Swing bone {} ({} to {})",
+                            swing_bone.name, swing_bone.start_bonename, swing_bone.end_bonename
+                        ),
+                    );
+                }
+            }
+        }
+    }
+
+    results
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{model_folder::FileChanged, validation::ModelValidationErrors};
+    use ssbh_data::{
+        mesh_data::{BoneInfluence, MeshObjectData},
+        prelude::*,
+        skel_data::{BillboardType, BoneData},
+    };
+    use ssbh_wgpu::ModelFolder;
+
+    #[test]
+    fn search_bone_references() {
+        let models = vec![ModelFolderState {
+            folder_path: "/model/body/c00".into(),
+            model: ModelFolder {
+                meshes: vec![(
+                    "model.numshb".to_owned(),
+                    Some(MeshData {
+                        major_version: 1,
+                        minor_version: 10,
+                        objects: vec![MeshObjectData {
+                            name: "body".to_owned(),
+                            bone_influences: vec![BoneInfluence {
+                                bone_name: "Hip".to_owned(),
+                                vertex_weights: Vec::new(),
+                            }],
+                            ..Default::default()
+                        }],
+                    }),
+                )],
+                skels: vec![(
+                    "model.nusktb".to_owned(),
+                    Some(SkelData {
+                        major_version: 1,
+                        minor_version: 0,
+                        bones: vec![BoneData {
+                            name: "Hip".to_owned(),
+                            transform: [[0.0; 4]; 4],
+                            parent_index: None,
+                            billboard_type: BillboardType::Disabled,
+                        }],
+                    }),
+                )],
+                matls: Vec::new(),
+                modls: Vec::new(),
+                adjs: Vec::new(),
+                anims: Vec::new(),
+                hlpbs: Vec::new(),
+                nutexbs: Vec::new(),
+                meshexes: Vec::new(),
+                xmbs: Vec::new(),
+            },
+            swing_prc: None,
+            thumbnails: Vec::new(),
+            validation: ModelValidationErrors::default(),
+            changed: FileChanged::default(),
+            is_meshlist_open: true,
+        }];
+
+        assert!(search_workspace(&models, "").is_empty());
+        assert_eq!(
+            vec![
+                SearchResult {
+                    folder_index: 0,
+                    file_name: "model.nusktb".to_owned(),
+                    item: SearchItem::Bone {
+                        file_index: 0,
+                        bone_index: 0
+                    },
+                    description: "Bone Hip".to_owned()
+                },
+                SearchResult {
+                    folder_index: 0,
+                    file_name: "model.numshb".to_owned(),
+                    item: SearchItem::MeshObject {
+                        file_index: 0,
+                        object_index: 0
+                    },
+                    description: "Bone influence Hip for body".to_owned()
+                }
+            ],
+            search_workspace(&models, "hip")
+        );
+    }
+}
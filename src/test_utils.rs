@@ -0,0 +1,21 @@
+use ssbh_data::{
+    prelude::*,
+    skel_data::{BillboardType, BoneData},
+};
+
+pub fn bone(name: &str, parent_index: Option<usize>, transform: glam::Mat4) -> BoneData {
+    BoneData {
+        name: name.to_owned(),
+        transform: transform.to_cols_array_2d(),
+        parent_index,
+        billboard_type: BillboardType::Disabled,
+    }
+}
+
+pub fn skel(bones: Vec<BoneData>) -> SkelData {
+    SkelData {
+        major_version: 1,
+        minor_version: 0,
+        bones,
+    }
+}